@@ -0,0 +1,116 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use data_structure_with_colin::avl_tree::set::AVLTreeSet;
+use data_structure_with_colin::btree::BPlusTreeMap;
+use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, SeedableRng};
+use std::collections::BTreeMap;
+
+const SIZES: [u32; 3] = [1_000, 10_000, 100_000];
+
+fn shuffled(count: u32) -> Vec<u32> {
+    let mut keys: Vec<u32> = (0..count).collect();
+    keys.shuffle(&mut StdRng::seed_from_u64(42));
+    keys
+}
+
+fn bench_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("insert");
+
+    for size in SIZES.iter() {
+        let keys = shuffled(*size);
+
+        group.bench_with_input(BenchmarkId::new("BPlusTreeMap", size), &keys, |b, keys| {
+            b.iter(|| {
+                let mut map = BPlusTreeMap::new();
+                for key in keys {
+                    map.insert(*key, ());
+                }
+                map
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("AVLTreeSet", size), &keys, |b, keys| {
+            b.iter(|| {
+                let mut set = AVLTreeSet::new();
+                for key in keys {
+                    set.insert(*key);
+                }
+                set
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+            b.iter(|| {
+                let mut map = BTreeMap::new();
+                for key in keys {
+                    map.insert(*key, ());
+                }
+                map
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+
+    for size in SIZES.iter() {
+        let keys = shuffled(*size);
+        let bplus = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<BPlusTreeMap<_, _>>();
+        let avl = keys.iter().copied().collect::<AVLTreeSet<_>>();
+        let btree = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<BTreeMap<_, _>>();
+
+        group.bench_with_input(BenchmarkId::new("BPlusTreeMap", size), &keys, |b, keys| {
+            b.iter(|| keys.iter().filter(|key| bplus.contains_key(key)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("AVLTreeSet", size), &keys, |b, keys| {
+            b.iter(|| keys.iter().filter(|key| avl.contains(key)).count())
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeMap", size), &keys, |b, keys| {
+            b.iter(|| keys.iter().filter(|key| btree.contains_key(key)).count())
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_range(c: &mut Criterion) {
+    let mut group = c.benchmark_group("range");
+
+    for size in SIZES.iter() {
+        let keys = shuffled(*size);
+        let (start, end) = (size / 4, size / 4 * 3);
+        let bplus = BPlusTreeMap::from_sorted_iter((0..*size).map(|key| (key, ())), 64);
+        let avl = keys.iter().copied().collect::<AVLTreeSet<_>>();
+        let btree = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<BTreeMap<_, _>>();
+
+        group.bench_function(BenchmarkId::new("BPlusTreeMap", size), |b| {
+            b.iter(|| bplus.range(black_box(start..end)).count())
+        });
+        group.bench_function(BenchmarkId::new("AVLTreeSet", size), |b| {
+            b.iter(|| {
+                avl.iter()
+                    .skip_while(|key| **key < black_box(start))
+                    .take_while(|key| **key < black_box(end))
+                    .count()
+            })
+        });
+        group.bench_function(BenchmarkId::new("BTreeMap", size), |b| {
+            b.iter(|| btree.range(black_box(start..end)).count())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert, bench_lookup, bench_range);
+criterion_main!(benches);
@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use data_structure_with_colin::linked_list::LinkedList;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+const SIZE: usize = 1_000_000;
+
+fn random_values(count: usize) -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(42);
+    (0..count).map(|_| rng.gen()).collect()
+}
+
+fn bench_sort(c: &mut Criterion) {
+    let values = random_values(SIZE);
+    let mut group = c.benchmark_group("sort");
+    group.sample_size(10);
+
+    group.bench_function("LinkedList", |b| {
+        b.iter_batched(
+            || LinkedList::from(values.clone()),
+            |mut list| {
+                list.sort();
+                list
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("Vec", |b| {
+        b.iter_batched(
+            || values.clone(),
+            |mut vec| {
+                vec.sort();
+                vec
+            },
+            BatchSize::LargeInput,
+        )
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_sort);
+criterion_main!(benches);
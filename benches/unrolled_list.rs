@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use data_structure_with_colin::dlist::DList;
+use data_structure_with_colin::linked_list::LinkedList;
+use data_structure_with_colin::unrolled_list::UnrolledList;
+
+const SIZES: [u32; 2] = [10_000, 1_000_000];
+
+fn bench_iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("iterate");
+
+    for size in SIZES.iter() {
+        let vec: Vec<u32> = (0..*size).collect();
+        let linked_list: LinkedList<u32> = (0..*size).collect();
+        let dlist: DList<u32> = (0..*size).collect();
+        let unrolled_list: UnrolledList<u32> = (0..*size).collect();
+
+        group.bench_with_input(BenchmarkId::new("Vec", size), &vec, |b, vec| {
+            b.iter(|| black_box(vec.iter().map(|value| u64::from(*value)).sum::<u64>()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("LinkedList", size),
+            &linked_list,
+            |b, list| b.iter(|| black_box(list.iter().map(|value| u64::from(*value)).sum::<u64>())),
+        );
+        group.bench_with_input(BenchmarkId::new("DList", size), &dlist, |b, list| {
+            b.iter(|| black_box(list.iter().map(|value| u64::from(*value)).sum::<u64>()))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("UnrolledList", size),
+            &unrolled_list,
+            |b, list| b.iter(|| black_box(list.iter().map(|value| u64::from(*value)).sum::<u64>())),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("UnrolledList/rev", size),
+            &unrolled_list,
+            |b, list| {
+                b.iter(|| {
+                    black_box(
+                        list.iter()
+                            .rev()
+                            .map(|value| u64::from(*value))
+                            .sum::<u64>(),
+                    )
+                })
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_iterate);
+criterion_main!(benches);
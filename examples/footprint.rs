@@ -0,0 +1,70 @@
+//! Prints how many bytes the lists allocate per element, next to a `Vec`.
+//!
+//! Criterion only measures time, so the memory footprint is counted with a
+//! global allocator here instead. Run it with
+//! `cargo run --release --example footprint`.
+
+use data_structure_with_colin::dlist::DList;
+use data_structure_with_colin::linked_list::LinkedList;
+use data_structure_with_colin::unrolled_list::UnrolledList;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+const SIZES: [u32; 2] = [10_000, 1_000_000];
+
+/// Counts the bytes currently allocated, to measure how much memory a list takes.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns the bytes allocated by the value `build` returns, per element.
+fn bytes_per_element<L, F: FnOnce() -> L>(count: u32, build: F) -> f64 {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let list = build();
+    let bytes = ALLOCATED.load(Ordering::Relaxed) - before;
+    drop(list);
+
+    bytes as f64 / f64::from(count)
+}
+
+fn main() {
+    for size in SIZES.iter() {
+        let footprints = [
+            (
+                "Vec",
+                bytes_per_element(*size, || (0..*size).collect::<Vec<_>>()),
+            ),
+            (
+                "LinkedList",
+                bytes_per_element(*size, || (0..*size).collect::<LinkedList<_>>()),
+            ),
+            (
+                "DList",
+                bytes_per_element(*size, || (0..*size).collect::<DList<_>>()),
+            ),
+            (
+                "UnrolledList",
+                bytes_per_element(*size, || (0..*size).collect::<UnrolledList<_>>()),
+            ),
+        ];
+
+        for (name, footprint) in footprints.iter() {
+            println!("{}/{}: {:.2} bytes per u32", name, size, footprint);
+        }
+    }
+}
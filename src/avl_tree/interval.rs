@@ -0,0 +1,612 @@
+//! An interval tree on top of the AVL tree.
+//!
+//! Intervals are ordered by their start and every node caches the largest end
+//! of its subtree. Subtrees that end before a query starts, or that start after
+//! it ends, are skipped, so finding the `k` intervals overlapping a query takes
+//! `O(k + log n)`.
+//!
+//! The same interval may be inserted several times, like two reservations of
+//! the same slot, and every one of its values is kept.
+//!
+//! Both bounds of an interval may be inclusive, exclusive or unbounded, just
+//! like the ranges of the standard library. Empty intervals such as `3..3` can
+//! be stored but never overlap anything, a degenerate interval `3..=3` covers
+//! exactly one point.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use crate::avl_tree::monoid::Monoid;
+use crate::avl_tree::tree::*;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+/// An interval with arbitrary bounds, as stored in an [`IntervalTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interval<K> {
+    start: Bound<K>,
+    end: Bound<K>,
+}
+
+/// A collection of intervals with a value each. An interval may be inserted several
+/// times, its values are kept in insertion order.
+#[derive(Clone)]
+pub struct IntervalTree<K: Ord + Clone, V> {
+    root: AVLTree<Entry<K, V>, MaxEnd<K>>,
+    len: usize,
+    /// The sequence number of the next inserted entry.
+    next_sequence: u64,
+}
+
+/// An interval and its value stored in a node. Entries are ordered by interval and
+/// entries of the same interval by their insertion.
+#[derive(Clone)]
+struct Entry<K, V> {
+    interval: Interval<K>,
+    sequence: u64,
+    value: V,
+}
+
+/// Aggregates the largest end bound of a subtree.
+#[derive(Clone)]
+struct MaxEnd<K>(PhantomData<K>);
+
+/// Compares two start bounds. An unbounded start comes first, an inclusive
+/// start comes before an exclusive one at the same point.
+fn cmp_start<K: Ord>(left: Bound<&K>, right: Bound<&K>) -> Ordering {
+    match (left, right) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Less,
+        (_, Bound::Unbounded) => Ordering::Greater,
+        (Bound::Included(left), Bound::Included(right))
+        | (Bound::Excluded(left), Bound::Excluded(right)) => left.cmp(right),
+        (Bound::Included(left), Bound::Excluded(right)) => left.cmp(right).then(Ordering::Less),
+        (Bound::Excluded(left), Bound::Included(right)) => left.cmp(right).then(Ordering::Greater),
+    }
+}
+
+/// Compares two end bounds. An unbounded end comes last, an exclusive end
+/// comes before an inclusive one at the same point.
+fn cmp_end<K: Ord>(left: Bound<&K>, right: Bound<&K>) -> Ordering {
+    match (left, right) {
+        (Bound::Unbounded, Bound::Unbounded) => Ordering::Equal,
+        (Bound::Unbounded, _) => Ordering::Greater,
+        (_, Bound::Unbounded) => Ordering::Less,
+        (Bound::Included(left), Bound::Included(right))
+        | (Bound::Excluded(left), Bound::Excluded(right)) => left.cmp(right),
+        (Bound::Included(left), Bound::Excluded(right)) => left.cmp(right).then(Ordering::Greater),
+        (Bound::Excluded(left), Bound::Included(right)) => left.cmp(right).then(Ordering::Less),
+    }
+}
+
+/// Checks if some point lies behind `start` and before `end`.
+fn starts_before<K: Ord>(start: Bound<&K>, end: Bound<&K>) -> bool {
+    match (start, end) {
+        (Bound::Unbounded, _) | (_, Bound::Unbounded) => true,
+        (Bound::Included(start), Bound::Included(end)) => start <= end,
+        (Bound::Included(start), Bound::Excluded(end))
+        | (Bound::Excluded(start), Bound::Included(end))
+        | (Bound::Excluded(start), Bound::Excluded(end)) => start < end,
+    }
+}
+
+impl<K> Interval<K> {
+    /// Creates an interval from its two bounds.
+    pub fn new(start: Bound<K>, end: Bound<K>) -> Self {
+        Interval { start, end }
+    }
+
+    /// Creates an interval covering the same points as `range`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::Interval;
+    /// use std::ops::RangeBounds;
+    /// let interval = Interval::from_range(&(1..4));
+    /// assert!(interval.contains(&1));
+    /// assert!(!interval.contains(&4));
+    /// ```
+    pub fn from_range<R: RangeBounds<K>>(range: &R) -> Self
+    where
+        K: Clone,
+    {
+        Interval {
+            start: range.start_bound().cloned(),
+            end: range.end_bound().cloned(),
+        }
+    }
+
+    /// Checks if the interval doesn't contain any point, like `3..3`.
+    pub fn is_empty(&self) -> bool
+    where
+        K: Ord,
+    {
+        !starts_before(self.start.as_ref(), self.end.as_ref())
+    }
+
+    /// Checks if both intervals share at least one point.
+    pub fn overlaps(&self, other: &Interval<K>) -> bool
+    where
+        K: Ord,
+    {
+        starts_before(self.start.as_ref(), other.end.as_ref())
+            && starts_before(other.start.as_ref(), self.end.as_ref())
+            && !self.is_empty()
+            && !other.is_empty()
+    }
+}
+
+impl<K> RangeBounds<K> for Interval<K> {
+    fn start_bound(&self) -> Bound<&K> {
+        self.start.as_ref()
+    }
+
+    fn end_bound(&self) -> Bound<&K> {
+        self.end.as_ref()
+    }
+}
+
+impl<K: Ord> PartialOrd for Interval<K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Intervals are ordered by their start first and their end second.
+impl<K: Ord> Ord for Interval<K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_start(self.start.as_ref(), other.start.as_ref())
+            .then_with(|| cmp_end(self.end.as_ref(), other.end.as_ref()))
+    }
+}
+
+impl<K: Ord, V> PartialEq for Entry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.interval == other.interval && self.sequence == other.sequence
+    }
+}
+
+impl<K: Ord, V> Eq for Entry<K, V> {}
+
+impl<K: Ord, V> PartialOrd for Entry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for Entry<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.interval
+            .cmp(&other.interval)
+            .then(self.sequence.cmp(&other.sequence))
+    }
+}
+
+impl<K: Ord + Clone, V> Monoid<Entry<K, V>> for MaxEnd<K> {
+    type Output = Option<Bound<K>>;
+
+    fn identity() -> Self::Output {
+        None
+    }
+
+    fn lift(entry: &Entry<K, V>) -> Self::Output {
+        Some(entry.interval.end.clone())
+    }
+
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        match (left, right) {
+            (Some(left), Some(right)) => match cmp_end(left.as_ref(), right.as_ref()) {
+                Ordering::Less => Some(right.clone()),
+                _ => Some(left.clone()),
+            },
+            (Some(end), None) | (None, Some(end)) => Some(end.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+impl<K: Ord + Clone, V> IntervalTree<K, V> {
+    /// Creates a new and empty `IntervalTree`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let tree = IntervalTree::<u32, ()>::new();
+    /// assert!(tree.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        IntervalTree {
+            root: None,
+            len: 0,
+            next_sequence: 0,
+        }
+    }
+
+    /// Returns the number of intervals in the tree, counting every insertion of the same
+    /// interval.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts an interval with its value. An interval which is already present is
+    /// inserted once more and keeps all of its values.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let mut tree = IntervalTree::new();
+    /// tree.insert(1..4, "a");
+    /// tree.insert(1..=4, "b");
+    /// tree.insert(1..4, "c");
+    /// assert_eq!(tree.len(), 3);
+    /// assert_eq!(tree.containing(&2).count(), 3);
+    /// ```
+    pub fn insert<R: RangeBounds<K>>(&mut self, range: R, value: V) {
+        let entry = Entry {
+            interval: Interval::from_range(&range),
+            sequence: self.next_sequence,
+            value,
+        };
+        // The sequence number makes every entry unique, so nothing gets replaced
+        insert_or_replace(&mut self.root, entry);
+
+        self.next_sequence += 1;
+        self.len += 1;
+    }
+
+    /// Returns the first inserted value of exactly this interval.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let mut tree = IntervalTree::new();
+    /// tree.insert(1..4, "a");
+    /// tree.insert(1..4, "b");
+    /// assert_eq!(tree.get(1..4), Some(&"a"));
+    /// assert_eq!(tree.get(1..=4), None);
+    /// ```
+    pub fn get<R: RangeBounds<K>>(&self, range: R) -> Option<&V> {
+        self.first(&Interval::from_range(&range))
+            .map(|entry| &entry.value)
+    }
+
+    /// Removes the first inserted value of exactly this interval and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let mut tree = IntervalTree::new();
+    /// tree.insert(1..4, "a");
+    /// tree.insert(1..4, "b");
+    /// assert_eq!(tree.remove(1..=4), None);
+    /// assert_eq!(tree.remove(1..4), Some("a"));
+    /// assert_eq!(tree.remove(1..4), Some("b"));
+    /// ```
+    pub fn remove<R: RangeBounds<K>>(&mut self, range: R) -> Option<V> {
+        let interval = Interval::from_range(&range);
+        let sequence = self.first(&interval)?.sequence;
+        let taken = take_by(&mut self.root, &mut |entry: &Entry<K, V>| {
+            entry
+                .interval
+                .cmp(&interval)
+                .then(entry.sequence.cmp(&sequence))
+        })?;
+        self.len -= 1;
+
+        Some(taken.value)
+    }
+
+    /// Returns the first inserted entry of exactly this interval.
+    fn first(&self, interval: &Interval<K>) -> Option<&Entry<K, V>> {
+        let mut current_tree = &self.root;
+        let mut found = None;
+
+        while let Some(current_node) = current_tree {
+            match current_node.value.interval.cmp(interval) {
+                Ordering::Less => current_tree = &current_node.right,
+                // An earlier entry of the same interval can only be on the left
+                Ordering::Equal => {
+                    found = Some(&current_node.value);
+                    current_tree = &current_node.left;
+                }
+                Ordering::Greater => current_tree = &current_node.left,
+            }
+        }
+
+        found
+    }
+
+    /// Returns an `Iterator` over all intervals sharing at least one point with
+    /// `range`, ordered by interval. The values of the same interval come in insertion order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let mut tree = IntervalTree::new();
+    /// tree.insert(0..10, "a");
+    /// tree.insert(10..20, "b");
+    /// tree.insert(15..=30, "c");
+    ///
+    /// let values: Vec<_> = tree.overlapping(5..16).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, vec!["a", "b", "c"]);
+    /// let values: Vec<_> = tree.overlapping(10..15).map(|(_, value)| *value).collect();
+    /// assert_eq!(values, vec!["b"]);
+    /// ```
+    pub fn overlapping<R: RangeBounds<K>>(&self, range: R) -> Overlapping<'_, K, V> {
+        let mut iter = Overlapping {
+            stack: Vec::new(),
+            query: Interval::from_range(&range),
+        };
+
+        if !iter.query.is_empty() {
+            iter.push_left(&self.root);
+        }
+
+        iter
+    }
+
+    /// Returns an `Iterator` over all intervals containing `point`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::interval::IntervalTree;
+    /// let mut tree = IntervalTree::new();
+    /// tree.insert(0..10, "a");
+    /// tree.insert(10..20, "b");
+    ///
+    /// assert_eq!(tree.containing(&10).count(), 1);
+    /// ```
+    pub fn containing(&self, point: &K) -> Overlapping<'_, K, V> {
+        self.overlapping(point..=point)
+    }
+
+    /// Checks if any interval shares at least one point with `range`.
+    pub fn any_overlap<R: RangeBounds<K>>(&self, range: R) -> bool {
+        self.overlapping(range).next().is_some()
+    }
+
+    /// Returns an `Iterator` over all intervals of the tree, ordered by interval.
+    pub fn iter(&self) -> Overlapping<'_, K, V> {
+        self.overlapping(..)
+    }
+}
+
+impl<K: Ord + Clone, V> Default for IntervalTree<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V, R: RangeBounds<K>> FromIterator<(R, V)> for IntervalTree<K, V> {
+    fn from_iter<I: IntoIterator<Item = (R, V)>>(iter: I) -> Self {
+        let mut tree = Self::new();
+
+        for (range, value) in iter {
+            tree.insert(range, value);
+        }
+
+        tree
+    }
+}
+
+impl<K, V> std::fmt::Debug for IntervalTree<K, V>
+where
+    K: Ord + Clone + std::fmt::Debug,
+    V: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An in-order Iterator over the intervals overlapping a query.
+/// Instances are created by [`IntervalTree::overlapping()`] and [`IntervalTree::containing()`].
+pub struct Overlapping<'a, K: Ord + Clone, V> {
+    stack: Vec<&'a AVLNode<Entry<K, V>, MaxEnd<K>>>,
+    query: Interval<K>,
+}
+
+impl<'a, K: Ord + Clone, V> Overlapping<'a, K, V> {
+    /// Descends to the left, skipping every subtree that ends before the query starts.
+    fn push_left(&mut self, mut tree: &'a AVLTree<Entry<K, V>, MaxEnd<K>>) {
+        while let Some(node) = tree {
+            let reaches_query = node
+                .aggregate
+                .as_ref()
+                .is_some_and(|max_end| starts_before(self.query.start.as_ref(), max_end.as_ref()));
+
+            if !reaches_query {
+                return;
+            }
+
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone, V> Iterator for Overlapping<'a, K, V> {
+    type Item = (&'a Interval<K>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(node) = self.stack.pop() {
+            let interval = &node.value.interval;
+
+            // Every following interval starts even later, none of them can overlap
+            if !starts_before(interval.start.as_ref(), self.query.end.as_ref()) {
+                self.stack.clear();
+                return None;
+            }
+
+            self.push_left(&node.right);
+
+            if interval.overlaps(&self.query) {
+                return Some((interval, &node.value.value));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    fn random_bound<R: Rng>(rng: &mut R, point: u32) -> Bound<u32> {
+        match rng.gen_range(0, 10) {
+            0 => Bound::Unbounded,
+            1..=4 => Bound::Excluded(point),
+            _ => Bound::Included(point),
+        }
+    }
+
+    fn random_interval<R: Rng>(rng: &mut R) -> Interval<u32> {
+        let start = rng.gen_range(0, 1000);
+        let end = start + rng.gen_range(0, 50);
+
+        Interval::new(random_bound(rng, start), random_bound(rng, end))
+    }
+
+    /// Returns all values overlapping `query` by scanning all intervals.
+    fn naive_overlapping(
+        intervals: &[(Interval<u32>, usize)],
+        query: &Interval<u32>,
+    ) -> Vec<usize> {
+        let mut found: Vec<_> = intervals
+            .iter()
+            .filter(|(interval, _)| interval.overlaps(query))
+            .map(|(_, value)| *value)
+            .collect();
+        found.sort_unstable();
+        found
+    }
+
+    fn tree_overlapping(tree: &IntervalTree<u32, usize>, query: &Interval<u32>) -> Vec<usize> {
+        let mut found: Vec<_> = tree.overlapping(query.clone()).map(|(_, v)| *v).collect();
+        found.sort_unstable();
+        found
+    }
+
+    #[test]
+    fn test_interval_overlaps() {
+        let closed = Interval::from_range(&(1..=5));
+        let open = Interval::new(Bound::Excluded(5), Bound::Excluded(9));
+        let point = Interval::from_range(&(5..=5));
+
+        assert!(closed.overlaps(&point));
+        assert!(!open.overlaps(&point));
+        assert!(!open.overlaps(&closed));
+        assert!(Interval::from_range(&(..)).overlaps(&open));
+        assert!(Interval::from_range(&(3..3)).is_empty());
+        assert!(!Interval::from_range(&(3..3)).overlaps(&closed));
+        assert!(!Interval::new(Bound::Excluded(3), Bound::Included(3)).overlaps(&closed));
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut tree = IntervalTree::new();
+        tree.insert(1..4, "a");
+        tree.insert(1..=4, "b");
+        tree.insert(..4, "c");
+        tree.insert(1..4, "d");
+        assert_eq!(tree.len(), 4);
+        assert_eq!(tree.get(1..4), Some(&"a"));
+        assert_eq!(tree.get(..4), Some(&"c"));
+        assert_eq!(tree.remove(1..=4), Some("b"));
+        assert_eq!(tree.remove(1..=4), None);
+        assert_eq!(tree.remove(1..4), Some("a"));
+        assert_eq!(tree.get(1..4), Some(&"d"));
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn test_same_interval_reservations() {
+        let mut tree = IntervalTree::new();
+        tree.insert(10..20, "first");
+        tree.insert(0..5, "other");
+        tree.insert(10..20, "second");
+
+        let values: Vec<_> = tree.overlapping(15..30).map(|(_, v)| *v).collect();
+        assert_eq!(values, vec!["first", "second"]);
+        assert_eq!(tree.containing(&10).count(), 2);
+
+        assert_eq!(tree.remove(10..20), Some("first"));
+        let values: Vec<_> = tree.overlapping(15..30).map(|(_, v)| *v).collect();
+        assert_eq!(values, vec!["second"]);
+    }
+
+    #[test]
+    fn test_degenerate_and_empty_intervals() {
+        let mut tree = IntervalTree::new();
+        tree.insert(5..=5, "point");
+        tree.insert(5..5, "empty");
+        tree.insert(..=2, "up to 2");
+        tree.insert(8.., "from 8");
+
+        assert_eq!(
+            tree.containing(&5).map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec!["point"]
+        );
+        assert_eq!(
+            tree.containing(&0).map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec!["up to 2"]
+        );
+        assert_eq!(
+            tree.containing(&900).map(|(_, v)| *v).collect::<Vec<_>>(),
+            vec!["from 8"]
+        );
+        assert!(!tree.any_overlap(3..5));
+        assert!(tree.any_overlap(3..=5));
+        assert!(!tree.any_overlap(4..4));
+        assert_eq!(tree.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_overlapping_against_naive_scan() {
+        let mut rng = thread_rng();
+        let mut intervals: Vec<(Interval<u32>, usize)> = Vec::new();
+        let mut tree = IntervalTree::new();
+
+        for value in 0..2000 {
+            let interval = random_interval(&mut rng);
+            tree.insert(interval.clone(), value);
+            intervals.push((interval, value));
+        }
+
+        for _ in 0..500 {
+            let query = random_interval(&mut rng);
+            assert_eq!(
+                tree_overlapping(&tree, &query),
+                naive_overlapping(&intervals, &query)
+            );
+        }
+
+        // Removing has to keep the cached end bounds up to date. The first inserted value
+        // of an interval goes first.
+        let removed: Vec<_> = intervals[800..].iter().map(|(i, _)| i.clone()).collect();
+        for interval in removed {
+            let index = intervals
+                .iter()
+                .position(|(other, _)| *other == interval)
+                .unwrap();
+            let (_, value) = intervals.remove(index);
+            assert_eq!(tree.remove(interval), Some(value));
+        }
+        assert_eq!(tree.len(), intervals.len());
+
+        for _ in 0..500 {
+            let query = random_interval(&mut rng);
+            assert_eq!(
+                tree_overlapping(&tree, &query),
+                naive_overlapping(&intervals, &query)
+            );
+
+            let point = rng.gen_range(0, 1100);
+            let point_query = Interval::from_range(&(point..=point));
+            let mut containing: Vec<_> = tree.containing(&point).map(|(_, v)| *v).collect();
+            containing.sort_unstable();
+            assert_eq!(containing, naive_overlapping(&intervals, &point_query));
+        }
+    }
+}
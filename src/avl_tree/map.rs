@@ -0,0 +1,379 @@
+//! An ordered map on top of the AVL tree.
+//!
+//! Every node can be augmented with a [`Monoid`] over the values of the map,
+//! which allows folding over arbitrary key ranges in `O(log n)`.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use crate::avl_tree::monoid::Monoid;
+use crate::avl_tree::tree::*;
+use crate::key_value::{after_start, before_end, KeyValue};
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::replace;
+use std::ops::RangeBounds;
+
+/// An ordered map that keeps its entries in an AVL tree. The optional monoid
+/// `M` is aggregated over the values, see [`AVLTreeMap::aggregate`].
+#[derive(Clone)]
+pub struct AVLTreeMap<K: Ord, V, M: Monoid<V> = ()> {
+    root: AVLTree<KeyValue<K, V>, ByValue<M>>,
+    len: usize,
+}
+
+/// Lifts a monoid over values to a monoid over entries.
+#[derive(Clone)]
+struct ByValue<M>(PhantomData<M>);
+
+impl<K, V, M: Monoid<V>> Monoid<KeyValue<K, V>> for ByValue<M> {
+    type Output = M::Output;
+
+    #[inline]
+    fn identity() -> Self::Output {
+        M::identity()
+    }
+
+    #[inline]
+    fn lift(entry: &KeyValue<K, V>) -> Self::Output {
+        M::lift(&entry.value)
+    }
+
+    #[inline]
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        M::combine(left, right)
+    }
+}
+
+impl<K: Ord, V, M: Monoid<V>> AVLTreeMap<K, V, M> {
+    /// Creates a new and empty `AVLTreeMap`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::map::AVLTreeMap;
+    /// let map = AVLTreeMap::<u32, ()>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        AVLTreeMap { root: None, len: 0 }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a key value pair into the map. If the key was already present its value
+    /// is replaced and the old value is returned. The key is not updated.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::map::AVLTreeMap;
+    /// let mut map = AVLTreeMap::<_, _>::new();
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), Some("a"));
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old_value = insert_or_merge(&mut self.root, KeyValue { key, value }, |old, new| {
+            replace(&mut old.value, new.value)
+        });
+
+        if old_value.is_none() {
+            self.len += 1;
+        }
+
+        old_value
+    }
+
+    /// Returns a reference to the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current_tree = &self.root;
+
+        while let Some(current_node) = current_tree {
+            match current_node.value.key.cmp(key) {
+                Ordering::Less => current_tree = &current_node.right,
+                Ordering::Equal => return Some(&current_node.value.value),
+                Ordering::Greater => current_tree = &current_node.left,
+            }
+        }
+
+        None
+    }
+
+    /// Checks if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` from the map and returns its value, if it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::map::AVLTreeMap;
+    /// let mut map = AVLTreeMap::<_, _>::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let taken = take_by(&mut self.root, &mut |entry: &KeyValue<K, V>| {
+            entry.key.cmp(key)
+        })?;
+        self.len -= 1;
+
+        Some(taken.value)
+    }
+
+    /// Folds the monoid `M` over all values whose keys lie in `range`, in key order.
+    /// Runs in `O(log n)` as every node caches the aggregate of its subtree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::avl_tree::map::AVLTreeMap;
+    /// use data_structure_with_colin::avl_tree::monoid::Sum;
+    /// let mut map = AVLTreeMap::<u32, u64, Sum<u64>>::new();
+    /// for i in 0..10 {
+    ///     map.insert(i, u64::from(i) * 10);
+    /// }
+    ///
+    /// assert_eq!(map.aggregate(2..5), 20 + 30 + 40);
+    /// assert_eq!(map.aggregate(..), 450);
+    /// ```
+    pub fn aggregate<R: RangeBounds<K>>(&self, range: R) -> M::Output {
+        Self::aggregate_tree(&self.root, &range, false, false)
+    }
+
+    /// `start_reached` and `end_reached` are set once all keys of `tree` are known to lie
+    /// behind the start or before the end of `range`, so only one path per bound is followed.
+    fn aggregate_tree<R: RangeBounds<K>>(
+        tree: &AVLTree<KeyValue<K, V>, ByValue<M>>,
+        range: &R,
+        start_reached: bool,
+        end_reached: bool,
+    ) -> M::Output {
+        let node = match tree {
+            Some(node) => node,
+            None => return M::identity(),
+        };
+
+        if start_reached && end_reached {
+            return node.aggregate.clone();
+        }
+
+        let key = &node.value.key;
+
+        if !start_reached && !after_start(range.start_bound(), key) {
+            return Self::aggregate_tree(&node.right, range, start_reached, end_reached);
+        }
+
+        if !end_reached && !before_end(range.end_bound(), key) {
+            return Self::aggregate_tree(&node.left, range, start_reached, end_reached);
+        }
+
+        let left = Self::aggregate_tree(&node.left, range, start_reached, true);
+        let right = Self::aggregate_tree(&node.right, range, true, end_reached);
+
+        M::combine(&M::combine(&left, &M::lift(&node.value.value)), &right)
+    }
+
+    /// Returns an `Iterator` over the entries of the map, ordered by key.
+    pub fn iter(&self) -> Iter<'_, K, V, M> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(&self.root);
+        iter
+    }
+}
+
+impl<K: Ord, V, M: Monoid<V>> Default for AVLTreeMap<K, V, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V, M: Monoid<V>> FromIterator<(K, V)> for AVLTreeMap<K, V, M> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug, M: Monoid<V>> std::fmt::Debug
+    for AVLTreeMap<K, V, M>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An in-order Iterator over the entries of an `AVLTreeMap`.
+/// Instances are created by [`AVLTreeMap::iter()`].
+pub struct Iter<'a, K: Ord, V, M: Monoid<V>> {
+    stack: Vec<&'a AVLNode<KeyValue<K, V>, ByValue<M>>>,
+}
+
+impl<'a, K: Ord, V, M: Monoid<V>> Iter<'a, K, V, M> {
+    fn push_left(&mut self, mut tree: &'a AVLTree<KeyValue<K, V>, ByValue<M>>) {
+        while let Some(node) = tree {
+            self.stack.push(node);
+            tree = &node.left;
+        }
+    }
+}
+
+impl<'a, K: Ord, V, M: Monoid<V>> Iterator for Iter<'a, K, V, M> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+
+        Some((&node.value.key, &node.value.value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::avl_tree::monoid::{Count, Max, Min, Sum};
+    use crate::key_value::Keyed;
+    use rand::seq::SliceRandom;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    fn random_pairs(count: u32) -> Vec<(u32, i64)> {
+        let mut rng = thread_rng();
+        let mut keys: Vec<u32> = (0..count).collect();
+        keys.shuffle(&mut rng);
+
+        keys.into_iter()
+            .map(|key| (key, rng.gen_range(-1000, 1000)))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = AVLTreeMap::<_, _>::new();
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(2, "zwei"), Some("two"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&"zwei"));
+        assert!(map.contains_key(&1));
+        assert_eq!(map.remove(&1), Some("one"));
+        assert_eq!(map.remove(&1), None);
+        assert!(!map.contains_key(&1));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_keeps_key() {
+        let mut map = AVLTreeMap::<_, _>::new();
+        assert_eq!(map.insert(Keyed(1, "old"), "a"), None);
+        assert_eq!(map.insert(Keyed(1, "new"), "b"), Some("a"));
+
+        let (key, value) = map.iter().next().unwrap();
+        assert_eq!((key.1, *value), ("old", "b"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_iter_against_btree_map() {
+        let pairs = random_pairs(2000);
+        let mut avl = pairs.iter().cloned().collect::<AVLTreeMap<_, _>>();
+        let mut btree = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+
+        for (key, _) in pairs.iter().take(700) {
+            assert_eq!(avl.remove(key), btree.remove(key));
+        }
+
+        assert_eq!(avl.len(), btree.len());
+        assert!(avl.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_aggregate_empty() {
+        let map = AVLTreeMap::<u32, i64, Sum<i64>>::new();
+        assert_eq!(map.aggregate(..), 0);
+        let map = AVLTreeMap::<u32, i64, Min<i64>>::new();
+        assert_eq!(map.aggregate(..), None);
+    }
+
+    #[test]
+    fn test_aggregate_bounds() {
+        let map = (0..10u32)
+            .map(|key| (key, 1))
+            .collect::<AVLTreeMap<_, u32, Sum<u32>>>();
+
+        assert_eq!(map.aggregate(..), 10);
+        assert_eq!(map.aggregate(3..7), 4);
+        assert_eq!(map.aggregate(3..=7), 5);
+        assert_eq!(map.aggregate(..3), 3);
+        assert_eq!(map.aggregate(7..), 3);
+        assert_eq!(map.aggregate((Bound::Excluded(3), Bound::Excluded(7))), 3);
+        assert_eq!(map.aggregate(20..30), 0);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = map.aggregate(7..3);
+        assert_eq!(reversed, 0);
+    }
+
+    /// Compares every built-in monoid with a naive scan over a `BTreeMap` for random ranges.
+    fn check_aggregates(
+        sum: &AVLTreeMap<u32, i64, Sum<i64>>,
+        min: &AVLTreeMap<u32, i64, Min<i64>>,
+        max: &AVLTreeMap<u32, i64, Max<i64>>,
+        count: &AVLTreeMap<u32, i64, Count>,
+        naive: &BTreeMap<u32, i64>,
+    ) {
+        let mut rng = thread_rng();
+
+        for _ in 0..500 {
+            let start = rng.gen_range(0, 1100);
+            let end = rng.gen_range(start, 1100);
+            let scan = || naive.range(start..end).map(|(_, value)| *value);
+
+            assert_eq!(sum.aggregate(start..end), scan().sum::<i64>());
+            assert_eq!(min.aggregate(start..end), scan().min());
+            assert_eq!(max.aggregate(start..end), scan().max());
+            assert_eq!(count.aggregate(start..end), scan().count());
+        }
+    }
+
+    #[test]
+    fn test_aggregates_against_naive_scan() {
+        let pairs = random_pairs(1000);
+        let mut sum = pairs.iter().cloned().collect::<AVLTreeMap<_, _, Sum<_>>>();
+        let mut min = pairs.iter().cloned().collect::<AVLTreeMap<_, _, Min<_>>>();
+        let mut max = pairs.iter().cloned().collect::<AVLTreeMap<_, _, Max<_>>>();
+        let mut count = pairs.iter().cloned().collect::<AVLTreeMap<_, _, Count>>();
+        let mut naive = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+
+        check_aggregates(&sum, &min, &max, &count, &naive);
+
+        // Removing and overwriting has to keep the cached aggregates up to date
+        for (key, value) in pairs.iter().take(400) {
+            if key % 2 == 0 {
+                sum.remove(key);
+                min.remove(key);
+                max.remove(key);
+                count.remove(key);
+                naive.remove(key);
+            } else {
+                sum.insert(*key, value * 3);
+                min.insert(*key, value * 3);
+                max.insert(*key, value * 3);
+                count.insert(*key, value * 3);
+                naive.insert(*key, value * 3);
+            }
+        }
+
+        check_aggregates(&sum, &min, &max, &count, &naive);
+    }
+}
@@ -1,2 +1,5 @@
+pub mod interval;
+pub mod map;
+pub mod monoid;
 pub mod set;
 mod tree;
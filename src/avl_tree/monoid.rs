@@ -0,0 +1,134 @@
+//! Monoids used to augment the nodes of an AVL tree.
+//!
+//! Every node of an augmented tree caches the combination of all values in its
+//! subtree. As long as the combining operation is associative and has an
+//! identity, the cached values can be recomputed from the children after every
+//! insertion, removal and rotation, which allows folding over any key range in
+//! `O(log n)`.
+
+use std::marker::PhantomData;
+use std::ops::Add;
+
+/// An associative operation with an identity element over values of type `T`.
+///
+/// `lift` turns a single value into an aggregate, `combine` merges the
+/// aggregates of two adjacent ranges. `combine` is always called with the
+/// lower range on the left, so non-commutative monoids are supported too.
+pub trait Monoid<T> {
+    type Output: Clone;
+
+    /// The aggregate of an empty range.
+    fn identity() -> Self::Output;
+
+    /// The aggregate of a range holding only `value`.
+    fn lift(value: &T) -> Self::Output;
+
+    /// Combines the aggregates of two adjacent ranges.
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output;
+}
+
+/// The trivial monoid. Used by trees that don't need any aggregate, it costs
+/// neither memory nor time.
+impl<T> Monoid<T> for () {
+    type Output = ();
+
+    #[inline]
+    fn identity() -> Self::Output {}
+
+    #[inline]
+    fn lift(_: &T) -> Self::Output {}
+
+    #[inline]
+    fn combine(_: &Self::Output, _: &Self::Output) -> Self::Output {}
+}
+
+/// Sums up all values. The sum of an empty range is `T::default()`.
+#[derive(Debug, Clone)]
+pub struct Sum<T>(PhantomData<T>);
+
+impl<T> Monoid<T> for Sum<T>
+where
+    T: Clone + Default + Add<Output = T>,
+{
+    type Output = T;
+
+    fn identity() -> Self::Output {
+        T::default()
+    }
+
+    fn lift(value: &T) -> Self::Output {
+        value.clone()
+    }
+
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        left.clone() + right.clone()
+    }
+}
+
+/// The smallest value, `None` for an empty range.
+#[derive(Debug, Clone)]
+pub struct Min<T>(PhantomData<T>);
+
+impl<T: Ord + Clone> Monoid<T> for Min<T> {
+    type Output = Option<T>;
+
+    fn identity() -> Self::Output {
+        None
+    }
+
+    fn lift(value: &T) -> Self::Output {
+        Some(value.clone())
+    }
+
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        match (left, right) {
+            (Some(left), Some(right)) => Some(left.min(right).clone()),
+            (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The largest value, `None` for an empty range.
+#[derive(Debug, Clone)]
+pub struct Max<T>(PhantomData<T>);
+
+impl<T: Ord + Clone> Monoid<T> for Max<T> {
+    type Output = Option<T>;
+
+    fn identity() -> Self::Output {
+        None
+    }
+
+    fn lift(value: &T) -> Self::Output {
+        Some(value.clone())
+    }
+
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        match (left, right) {
+            (Some(left), Some(right)) => Some(left.max(right).clone()),
+            (Some(value), None) | (None, Some(value)) => Some(value.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// The number of values.
+#[derive(Debug, Clone)]
+pub struct Count;
+
+impl<T> Monoid<T> for Count {
+    type Output = usize;
+
+    fn identity() -> Self::Output {
+        0
+    }
+
+    fn lift(_: &T) -> Self::Output {
+        1
+    }
+
+    fn combine(left: &Self::Output, right: &Self::Output) -> Self::Output {
+        left + right
+    }
+}
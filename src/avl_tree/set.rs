@@ -1,27 +1,31 @@
-extern crate rand;
-
 use crate::avl_tree::tree::*;
-use rand::seq::SliceRandom;
-use rand::thread_rng;
+use crate::metrics::Metrics;
+use crate::visualize::{DisplayNode, Visualize};
+use std::fmt::Display;
 use std::mem::replace;
 use std::{cmp::Ordering, iter::FromIterator};
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct AVLTreeSet<T: Ord> {
     root: AVLTree<T>,
+    metrics: Metrics,
 }
 
-impl<'a, T: 'a + Ord> AVLTreeSet<T> {
-    fn new() -> Self {
-        Self { root: None }
+impl<T: Ord> AVLTreeSet<T> {
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            metrics: Metrics::default(),
+        }
     }
 
-    fn insert(&mut self, value: T) -> bool {
+    pub fn insert(&mut self, value: T) -> bool {
         let mut prev_ptrs = Vec::<*mut AVLNode<T>>::new();
         let mut current = &mut self.root;
 
         while let Some(current_node) = current {
             prev_ptrs.push(&mut **current_node);
+            self.metrics.count_comparison();
 
             match current_node.value.cmp(&value) {
                 Ordering::Less => current = &mut current_node.right,
@@ -30,17 +34,12 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
             }
         }
 
-        *current = Some(Box::new(AVLNode {
-            value,
-            left: None,
-            right: None,
-            height: 1,
-        }));
+        *current = Some(Box::new(AVLNode::new(value)));
 
         for node_ptr in prev_ptrs.into_iter().rev() {
             let node = unsafe { &mut *node_ptr };
             node.update_height();
-            node.rebalance();
+            self.metrics.count_rotations(node.rebalance());
         }
 
         true
@@ -52,7 +51,9 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
         let mut target_value = None;
 
         while let Some(current_node) = current_tree {
-            match current_node.value.cmp(&value) {
+            self.metrics.count_comparison();
+
+            match current_node.value.cmp(value) {
                 Ordering::Less => {
                     prev_ptrs.push(&mut **current_node);
                     current_tree = &mut current_node.right;
@@ -69,11 +70,7 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
         }
 
         // Target does not exist in tree
-        if target_value.is_none() {
-            return None;
-        }
-
-        let target_node = target_value.unwrap();
+        let target_node = target_value?;
 
         // 3 Cases: No children, left child, right child
         let taken = if target_node.left.is_none() || target_node.right.is_none() {
@@ -106,7 +103,7 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
                 };
 
                 prev_node.update_height();
-                prev_node.rebalance();
+                self.metrics.count_rotations(prev_node.rebalance());
 
                 inner_value
             }
@@ -117,20 +114,20 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
         }
         // Find Inorder-Successor
         else {
-            AVLTreeSet::find_inorder_succesor(target_node)
+            AVLTreeSet::find_inorder_succesor(target_node, &mut self.metrics)
         };
 
         // Update for every touched Node
         for node_ptr in prev_ptrs.into_iter().rev() {
             let node = unsafe { &mut *node_ptr };
             node.update_height();
-            node.rebalance();
+            self.metrics.count_rotations(node.rebalance());
         }
 
         Some(taken)
     }
 
-    fn find_inorder_succesor(target_node: &mut AVLNode<T>) -> T {
+    fn find_inorder_succesor(target_node: &mut AVLNode<T>, metrics: &mut Metrics) -> T {
         let right_tree = &mut target_node.right;
 
         // Left tree of right is None, take first right
@@ -141,7 +138,7 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
             let _ = replace(&mut target_node.right, right_node.right.take());
 
             target_node.update_height();
-            target_node.rebalance();
+            metrics.count_rotations(target_node.rebalance());
 
             inner_value
         }
@@ -171,19 +168,19 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
 
             // Start at the bottom with updating
             parent_leftest_node.update_height();
-            parent_leftest_node.rebalance();
+            metrics.count_rotations(parent_leftest_node.rebalance());
 
             // Up to the children of target
             // Rev because into iter starts at the first inserted item, we need the last inserted first
             for node_ptr in left_ptrs.into_iter().rev() {
                 let node = unsafe { &mut *node_ptr };
                 node.update_height();
-                node.rebalance();
+                metrics.count_rotations(node.rebalance());
             }
 
             // At last of course target node to update
             target_node.update_height();
-            target_node.rebalance();
+            metrics.count_rotations(target_node.rebalance());
 
             inner_value
         }
@@ -201,7 +198,7 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
         let mut current_tree = &self.root;
 
         while let Some(current_node) = current_tree {
-            match current_node.value.cmp(&value) {
+            match current_node.value.cmp(value) {
                 Ordering::Less => {
                     current_tree = &current_node.right;
                 }
@@ -215,6 +212,94 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
         }
         None
     }
+
+    /// Adds a value to the set, replacing the equal value that may already be present.
+    /// Returns the replaced value.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        insert_or_replace(&mut self.root, value)
+    }
+
+    /// Removes all values from the set.
+    pub fn clear(&mut self) {
+        self.root = None;
+    }
+
+    /// Keeps only the values for which `keep` returns `true`. The values are visited
+    /// in order and the tree is rebuilt once afterwards, which takes `O(n)` no matter
+    /// how many values are removed.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut keep: F) {
+        self.extract_if(|value| !keep(value)).for_each(drop);
+    }
+
+    /// Returns an `Iterator` which removes and yields every value for which `extract`
+    /// returns `true`, in order. The set is rebuilt in `O(n)` when the iterator is
+    /// dropped. Values that weren't visited yet at that point stay in the set.
+    pub fn extract_if<F: FnMut(&T) -> bool>(&mut self, extract: F) -> ExtractIf<'_, T, F> {
+        let values = into_sorted_vec(self.root.take());
+
+        ExtractIf {
+            set: self,
+            kept: Vec::with_capacity(values.len()),
+            values: values.into_iter(),
+            extract,
+        }
+    }
+}
+
+/// An Iterator removing values from an `AVLTreeSet`.
+/// Instances are created by [`AVLTreeSet::extract_if()`].
+pub struct ExtractIf<'a, T: Ord, F: FnMut(&T) -> bool> {
+    set: &'a mut AVLTreeSet<T>,
+    values: std::vec::IntoIter<T>,
+    kept: Vec<T>,
+    extract: F,
+}
+
+impl<T: Ord, F: FnMut(&T) -> bool> Iterator for ExtractIf<'_, T, F> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for value in self.values.by_ref() {
+            if (self.extract)(&value) {
+                return Some(value);
+            }
+
+            self.kept.push(value);
+        }
+
+        None
+    }
+}
+
+impl<T: Ord, F: FnMut(&T) -> bool> Drop for ExtractIf<'_, T, F> {
+    fn drop(&mut self) {
+        let mut kept = std::mem::take(&mut self.kept);
+        kept.extend(self.values.by_ref());
+
+        self.set.root = from_sorted(kept);
+    }
+}
+
+/// Two sets are equal if their trees have the same shape and values, no matter
+/// what their metrics recorded.
+impl<T: Ord> PartialEq for AVLTreeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Ord> AVLTreeSet<T> {
+    /// Returns the comparisons made by `insert` and `take` and the rotations done
+    /// while rebalancing since creation or the last reset.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets all metrics back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
 }
 
 impl<T: Ord> FromIterator<T> for AVLTreeSet<T> {
@@ -229,9 +314,38 @@ impl<T: Ord> FromIterator<T> for AVLTreeSet<T> {
     }
 }
 
+impl<T: Ord> Default for AVLTreeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Display> Visualize for AVLTreeSet<T> {
+    fn display_tree(&self) -> Option<DisplayNode> {
+        self.root.as_deref().map(display_node)
+    }
+}
+
+/// Uses the stored height, so a broken height shows up in the rendering.
+fn display_node<T: Ord + Display>(node: &AVLNode<T>) -> DisplayNode {
+    DisplayNode {
+        label: node.value.to_string(),
+        height: node.height,
+        balance_factor: node.balance_factor() as isize,
+        left: node
+            .left
+            .as_deref()
+            .map(|left| Box::new(display_node(left))),
+        right: node
+            .right
+            .as_deref()
+            .map(|right| Box::new(display_node(right))),
+    }
+}
+
 /// Iterator
 impl<'a, T: 'a + Ord> AVLTreeSet<T> {
-    fn iter(&'a self) -> AVLTreeSetNodeIter<'a, T> {
+    pub fn iter(&'a self) -> AVLTreeSetNodeIter<'a, T> {
         AVLTreeSetNodeIter {
             prev_nodes: Vec::new(),
             current_tree: &self.root,
@@ -240,7 +354,7 @@ impl<'a, T: 'a + Ord> AVLTreeSet<T> {
 }
 
 #[derive(Debug)]
-struct AVLTreeSetNodeIter<'a, T: Ord> {
+pub struct AVLTreeSetNodeIter<'a, T: Ord> {
     prev_nodes: Vec<&'a AVLNode<T>>,
     current_tree: &'a AVLTree<T>,
 }
@@ -253,14 +367,14 @@ impl<'a, T: 'a + Ord> Iterator for AVLTreeSetNodeIter<'a, T> {
             match *self.current_tree {
                 None => match self.prev_nodes.pop() {
                     None => return None,
-                    Some(ref prev_node) => {
+                    Some(prev_node) => {
                         self.current_tree = &prev_node.right;
                         return Some(&prev_node.value);
                     }
                 },
                 Some(ref current_node) => {
                     if current_node.left.is_some() {
-                        self.prev_nodes.push(&current_node);
+                        self.prev_nodes.push(current_node);
                         self.current_tree = &current_node.left;
 
                         continue;
@@ -283,6 +397,9 @@ impl<'a, T: 'a + Ord> Iterator for AVLTreeSetNodeIter<'a, T> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::key_value::Keyed;
+    use rand::seq::SliceRandom;
+    use rand::thread_rng;
     use std::collections::BTreeSet;
 
     #[test]
@@ -291,20 +408,20 @@ mod test {
         tree.insert(50);
         tree.insert(70);
         tree.insert(90);
-        assert_eq!(tree.insert(90), false);
-        assert_eq!(tree.contains(&50), true);
-        assert_eq!(tree.contains(&70), true);
-        assert_eq!(tree.contains(&90), true);
+        assert!(!tree.insert(90));
+        assert!(tree.contains(&50));
+        assert!(tree.contains(&70));
+        assert!(tree.contains(&90));
         tree.remove(&70);
-        assert_eq!(tree.contains(&70), false);
+        assert!(!tree.contains(&70));
         assert_eq!(tree.get(&50).unwrap(), &50);
         assert_eq!(tree.root.unwrap().value, 90);
     }
 
     #[test]
     fn test_insert_randomly() {
-        let avl = (1..50 as u8).collect::<AVLTreeSet<_>>();
-        let btree = (1..50 as u8).collect::<BTreeSet<_>>();
+        let avl = (1..50_u8).collect::<AVLTreeSet<_>>();
+        let btree = (1..50_u8).collect::<BTreeSet<_>>();
 
         for it in avl.iter().zip(btree.iter()) {
             let (a, b) = it;
@@ -314,8 +431,8 @@ mod test {
 
     #[test]
     fn test_delete_somehow_randomly() {
-        let mut avl = (1..100 as u16).collect::<AVLTreeSet<_>>();
-        let mut btree = (1..100 as u16).collect::<BTreeSet<_>>();
+        let mut avl = (1..100_u16).collect::<AVLTreeSet<_>>();
+        let mut btree = (1..100_u16).collect::<BTreeSet<_>>();
 
         avl.remove(&45);
         avl.remove(&12);
@@ -331,7 +448,7 @@ mod test {
         btree.remove(&75);
         btree.remove(&80);
 
-        assert_eq!(avl.contains(&90), true);
+        assert!(avl.contains(&90));
         assert_eq!(avl.get(&90).unwrap(), &90);
 
         for it in avl.iter().zip(btree.iter()) {
@@ -391,4 +508,91 @@ mod test {
             assert_eq!(a, b)
         }
     }
+
+    #[test]
+    fn test_replace() {
+        let mut avl = AVLTreeSet::new();
+        assert!(avl.replace(Keyed(1, "old")).is_none());
+        avl.insert(Keyed(2, "other"));
+
+        let replaced = avl.replace(Keyed(1, "new")).unwrap();
+        assert_eq!(replaced.1, "old");
+        assert_eq!(avl.get(&Keyed(1, "")).unwrap().1, "new");
+        assert_eq!(avl.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut avl = (1..100).collect::<AVLTreeSet<_>>();
+        avl.clear();
+        assert_eq!(avl.iter().next(), None);
+        assert!(!avl.contains(&50));
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut vec: Vec<u32> = (0..10000).collect();
+        vec.shuffle(&mut thread_rng());
+        let mut avl = vec.iter().collect::<AVLTreeSet<_>>();
+        let mut btree = vec.iter().collect::<BTreeSet<_>>();
+
+        avl.retain(|value| *value % 3 == 0);
+        btree.retain(|value| *value % 3 == 0);
+
+        assert!(avl.iter().eq(btree.iter()));
+        assert!(avl.contains(&&9));
+        assert!(!avl.contains(&&10));
+
+        // The rebuilt tree has to stay usable
+        avl.insert(&10);
+        avl.remove(&&9);
+        btree.insert(&10);
+        btree.remove(&&9);
+        assert!(avl.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_extract_if() {
+        let mut avl = (0..100u32).collect::<AVLTreeSet<_>>();
+
+        let extracted: Vec<_> = avl.extract_if(|value| *value >= 90).collect();
+        assert_eq!(extracted, (90..100).collect::<Vec<_>>());
+        assert!(avl.iter().eq((0..90).collect::<Vec<_>>().iter()));
+
+        // Dropping the iterator early keeps everything that wasn't visited
+        let mut iter = avl.extract_if(|value| *value % 2 == 0);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(2));
+        drop(iter);
+
+        let expected: Vec<u32> = (1..90).filter(|value| *value != 2).collect();
+        assert!(avl.iter().eq(expected.iter()));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        let mut avl = AVLTreeSet::new();
+        avl.insert(1);
+        avl.insert(2);
+        assert_eq!(avl.metrics().comparisons(), 1);
+        assert_eq!(avl.metrics().rotations(), 0);
+
+        // Right-right case, a single rotation
+        avl.insert(3);
+        assert_eq!(avl.metrics().comparisons(), 3);
+        assert_eq!(avl.metrics().rotations(), 1);
+
+        avl.reset_metrics();
+        assert_eq!(avl.metrics().comparisons(), 0);
+
+        // Right-left case, a double rotation
+        avl.insert(5);
+        avl.insert(4);
+        assert_eq!(avl.metrics().rotations(), 2);
+        assert_eq!(avl.metrics().comparisons(), 5);
+
+        // Comparing sets ignores the metrics
+        assert_eq!(avl, (1..=5).collect::<AVLTreeSet<_>>());
+    }
 }
@@ -1,17 +1,41 @@
-use std::cmp::max;
+use crate::avl_tree::monoid::Monoid;
+use std::cmp::{max, Ordering};
 use std::mem::{replace, swap};
 
+/// A node of an AVL tree. Besides its height every node caches the aggregate
+/// `M` of all values in its subtree, which is recomputed whenever the height is.
+/// Trees without an aggregate use the trivial monoid `()`.
 #[derive(Debug, PartialEq, Clone)]
-pub struct AVLNode<T: Ord> {
+pub struct AVLNode<T: Ord, M: Monoid<T> = ()> {
     pub value: T,
-    pub left: AVLTree<T>,
-    pub right: AVLTree<T>,
+    pub left: AVLTree<T, M>,
+    pub right: AVLTree<T, M>,
     pub height: usize,
+    pub aggregate: M::Output,
 }
 
-pub type AVLTree<T> = Option<Box<AVLNode<T>>>;
+pub type AVLTree<T, M = ()> = Option<Box<AVLNode<T, M>>>;
+
+/// Returns the aggregate of a whole (sub-)tree, the identity for an empty one.
+pub fn aggregate<T: Ord, M: Monoid<T>>(tree: &AVLTree<T, M>) -> M::Output {
+    tree.as_ref()
+        .map_or_else(M::identity, |node| node.aggregate.clone())
+}
+
+impl<T: Ord, M: Monoid<T>> AVLNode<T, M> {
+    /// Creates a new leaf.
+    pub fn new(value: T) -> Self {
+        let aggregate = M::lift(&value);
+
+        AVLNode {
+            value,
+            left: None,
+            right: None,
+            height: 1,
+            aggregate,
+        }
+    }
 
-impl<'a, T: 'a + Ord> AVLNode<T> {
     // Overflow precautions
     pub fn balance_factor(&self) -> i8 {
         let left_height = self.left_height();
@@ -24,8 +48,14 @@ impl<'a, T: 'a + Ord> AVLNode<T> {
         }
     }
 
+    /// Recomputes height and aggregate from the children. Has to be called
+    /// bottom-up for every node whose subtree changed.
     pub fn update_height(&mut self) {
-        self.height = 1 + max(self.left_height(), self.right_height())
+        self.height = 1 + max(self.left_height(), self.right_height());
+        self.aggregate = M::combine(
+            &M::combine(&aggregate(&self.left), &M::lift(&self.value)),
+            &aggregate(&self.right),
+        );
     }
 
     fn left_height(&self) -> usize {
@@ -86,27 +116,188 @@ impl<'a, T: 'a + Ord> AVLNode<T> {
         self.update_height();
     }
 
-    pub fn rebalance(&mut self) {
+    /// Restores the AVL property of this node and returns the number of rotations
+    /// that were necessary, which is at most two.
+    pub fn rebalance(&mut self) -> usize {
         match self.balance_factor() {
             -2 => {
                 let right_node = self.right.as_mut().unwrap();
+                let mut rotations = 1;
 
                 if right_node.balance_factor() == 1 {
                     right_node.rotate_right();
+                    rotations += 1;
                 }
 
                 self.rotate_left();
+                rotations
             }
             2 => {
                 let left_node = self.left.as_mut().unwrap();
+                let mut rotations = 1;
 
                 if left_node.balance_factor() == -1 {
                     left_node.rotate_left();
+                    rotations += 1;
                 }
 
                 self.rotate_right();
+                rotations
+            }
+            _ => 0,
+        }
+    }
+}
+
+/// Inserts `value` into the tree. If an equal value is already present it is
+/// replaced and returned instead.
+pub fn insert_or_replace<T: Ord, M: Monoid<T>>(tree: &mut AVLTree<T, M>, value: T) -> Option<T> {
+    insert_or_merge(tree, value, |old_value, value| replace(old_value, value))
+}
+
+/// Inserts `value` into the tree. If an equal value is already present `merge`
+/// updates it with `value` in place and its result is returned instead.
+pub fn insert_or_merge<T, M, R, F>(tree: &mut AVLTree<T, M>, value: T, merge: F) -> Option<R>
+where
+    T: Ord,
+    M: Monoid<T>,
+    F: FnOnce(&mut T, T) -> R,
+{
+    let mut prev_ptrs = Vec::<*mut AVLNode<T, M>>::new();
+    let mut current = tree;
+
+    while let Some(current_node) = current {
+        match current_node.value.cmp(&value) {
+            Ordering::Less => {
+                prev_ptrs.push(&mut **current_node);
+                current = &mut current_node.right;
+            }
+            Ordering::Equal => {
+                let merged = merge(&mut current_node.value, value);
+
+                // Even a merged value may change the aggregates along the path
+                current_node.update_height();
+                update_path(prev_ptrs);
+
+                return Some(merged);
+            }
+            Ordering::Greater => {
+                prev_ptrs.push(&mut **current_node);
+                current = &mut current_node.left;
+            }
+        }
+    }
+
+    *current = Some(Box::new(AVLNode::new(value)));
+    update_path(prev_ptrs);
+
+    None
+}
+
+/// Updates and rebalances the nodes on a path from the root, starting at the bottom.
+fn update_path<T: Ord, M: Monoid<T>>(prev_ptrs: Vec<*mut AVLNode<T, M>>) {
+    for node_ptr in prev_ptrs.into_iter().rev() {
+        let node = unsafe { &mut *node_ptr };
+        node.update_height();
+        node.rebalance();
+    }
+}
+
+/// Removes the value `cmp` reports as `Ordering::Equal` from the tree and
+/// rebalances every node on the way back up. `cmp` compares the value of a
+/// node with the searched one, just like `node.value.cmp(&searched)` would.
+pub fn take_by<T, M, F>(tree: &mut AVLTree<T, M>, cmp: &mut F) -> Option<T>
+where
+    T: Ord,
+    M: Monoid<T>,
+    F: FnMut(&T) -> Ordering,
+{
+    let node = tree.as_mut()?;
+
+    let taken = match cmp(&node.value) {
+        Ordering::Less => take_by(&mut node.right, cmp),
+        Ordering::Greater => take_by(&mut node.left, cmp),
+        Ordering::Equal => {
+            if node.left.is_some() && node.right.is_some() {
+                let successor = take_min(&mut node.right).unwrap();
+                Some(replace(&mut node.value, successor))
+            } else {
+                // At most one child, which simply takes the place of the node
+                let mut node = tree.take().unwrap();
+                *tree = node.left.take().or_else(|| node.right.take());
+                return Some(node.value);
             }
-            _ => return,
         }
+    };
+
+    if let Some(node) = tree.as_mut() {
+        node.update_height();
+        node.rebalance();
     }
+
+    taken
+}
+
+/// Removes the smallest value of the tree.
+pub fn take_min<T: Ord, M: Monoid<T>>(tree: &mut AVLTree<T, M>) -> Option<T> {
+    let node = tree.as_mut()?;
+
+    if node.left.is_some() {
+        let min = take_min(&mut node.left);
+        node.update_height();
+        node.rebalance();
+        min
+    } else {
+        let mut node = tree.take().unwrap();
+        *tree = node.right.take();
+        Some(node.value)
+    }
+}
+
+/// Consumes the tree and returns its values in order. Runs in `O(n)`.
+pub fn into_sorted_vec<T: Ord, M: Monoid<T>>(tree: AVLTree<T, M>) -> Vec<T> {
+    let mut values = Vec::new();
+    let mut prev_nodes = Vec::new();
+    let mut current_tree = tree;
+
+    loop {
+        while let Some(mut current_node) = current_tree {
+            current_tree = current_node.left.take();
+            prev_nodes.push(current_node);
+        }
+
+        match prev_nodes.pop() {
+            Some(mut prev_node) => {
+                current_tree = prev_node.right.take();
+                values.push(prev_node.value);
+            }
+            None => return values,
+        }
+    }
+}
+
+/// Builds a perfectly balanced tree from values that are already sorted and
+/// free of duplicates. Runs in `O(n)`.
+pub fn from_sorted<T: Ord, M: Monoid<T>>(values: Vec<T>) -> AVLTree<T, M> {
+    let len = values.len();
+    build_balanced(&mut values.into_iter(), len)
+}
+
+fn build_balanced<T, M, I>(values: &mut I, len: usize) -> AVLTree<T, M>
+where
+    T: Ord,
+    M: Monoid<T>,
+    I: Iterator<Item = T>,
+{
+    if len == 0 {
+        return None;
+    }
+
+    let left = build_balanced(values, len / 2);
+    let mut node = AVLNode::new(values.next().unwrap());
+    node.left = left;
+    node.right = build_balanced(values, len - len / 2 - 1);
+    node.update_height();
+
+    Some(Box::new(node))
 }
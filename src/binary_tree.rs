@@ -3,33 +3,100 @@
 //! The `binary tree` allows inserting, removing and is naturally sorted.
 //!
 //! NOTE: This was written for a learning purpose.
+
+pub mod map;
+
+use super::metrics::Metrics;
+use super::queue::Queue;
 use super::stack::Stack;
+use super::visualize::{DisplayNode, Visualize};
 use std::cmp::Ordering;
+use std::fmt::Display;
 
 /// A binary tree build from Nodes. This struct represents a binary tree
 /// holding a root node.
+///
+/// A plain tree keeps whatever shape the insertion order produces and can be rebalanced
+/// explicitly with [`BinaryTree::rebalance()`]. A tree created with
+/// [`BinaryTree::with_scapegoat()`] rebuilds unbalanced subtrees on its own.
 pub struct BinaryTree<T> {
     head: Link<T>,
+    len: usize,
+    /// The alpha of the scapegoat mode, `None` for a plain tree.
+    scapegoat: Option<f64>,
+    /// The largest length since the last full rebuild, used by the scapegoat mode.
+    max_len: usize,
+    metrics: Metrics,
 }
 
 /// A Link between Nodes.
-type Link<T> = Option<Box<Node<T>>>;
+pub(crate) type Link<T> = Option<Box<Node<T>>>;
 
 /// A Node in a binary tree which holds a reference to the left and right Nodes as well as a value.
+/// The splay tree is built from the same nodes.
 #[derive(Debug, Eq, PartialEq)]
-struct Node<T> {
-    left: Link<T>,
-    right: Link<T>,
-    value: T,
+pub(crate) struct Node<T> {
+    pub(crate) left: Link<T>,
+    pub(crate) right: Link<T>,
+    pub(crate) value: T,
 }
 
 /// The Iterator for a binary tree, containing a stack with all nodes that should be visited.
-/// Instances are created by [`BinaryTree::iter()`]. See its
-/// documentation for more.
+/// It visits the tree preorder. Instances are created by [`BinaryTree::iter()`] and
+/// [`BinaryTree::preorder_iter()`]. See their documentation for more.
 pub struct Iter<'a, T: 'a> {
-    visited: Box<Stack<&'a Box<Node<T>>>>,
+    visited: Box<Stack<&'a Node<T>>>,
+}
+
+/// The preorder Iterator for a binary tree.
+pub type PreorderIter<'a, T> = Iter<'a, T>;
+
+/// A double-ended Iterator visiting a binary tree inorder. The front stack holds the nodes
+/// on the path to the next smallest, the back stack the nodes on the path to the next largest
+/// element. Instances are created by [`BinaryTree::inorder_iter()`].
+pub struct InorderIter<'a, T: 'a> {
+    front: Stack<&'a Node<T>>,
+    back: Stack<&'a Node<T>>,
+    front_last: Option<&'a Node<T>>,
+    back_last: Option<&'a Node<T>>,
+    finished: bool,
+}
+
+/// An Iterator visiting a binary tree postorder. Every node is on the stack twice,
+/// once to expand its children and once to be returned.
+/// Instances are created by [`BinaryTree::postorder_iter()`].
+pub struct PostorderIter<'a, T: 'a> {
+    visited: Stack<(&'a Node<T>, bool)>,
+}
+
+/// An Iterator visiting a binary tree level by level from left to right, containing a queue
+/// with all nodes that should be visited. Instances are created by [`BinaryTree::level_order_iter()`].
+pub struct LevelOrderIter<'a, T: 'a> {
+    visited: Queue<&'a Node<T>>,
+}
+
+/// The reason why [`BinaryTree::deserialize_structure()`] rejected its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureError {
+    /// The input ended before every node got both of its children or markers.
+    Incomplete,
+    /// The input goes on after the tree is complete.
+    Trailing,
+}
+
+impl std::fmt::Display for StructureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            StructureError::Incomplete => "the structure ended before the tree was complete",
+            StructureError::Trailing => "the structure goes on after the tree is complete",
+        };
+
+        f.write_str(description)
+    }
 }
 
+impl std::error::Error for StructureError {}
+
 impl<T: Eq + std::cmp::Ord> BinaryTree<T> {
     /// Creates a new and empty `BinaryTree`.
     /// # Example
@@ -39,7 +106,42 @@ impl<T: Eq + std::cmp::Ord> BinaryTree<T> {
     /// assert!(binary_tree.is_empty());
     ///```
     pub fn new() -> Self {
-        BinaryTree { head: None }
+        BinaryTree {
+            head: None,
+            len: 0,
+            scapegoat: None,
+            max_len: 0,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Creates a new and empty `BinaryTree` in scapegoat mode. Every subtree is kept
+    /// alpha-weight-balanced: no child may hold more than `alpha` times the elements of its
+    /// parents subtree. Whenever an insert ends up too deep, the subtree of the lowest
+    /// violating ancestor (the scapegoat) is rebuilt into a balanced one. A smaller alpha keeps
+    /// the tree flatter at the cost of more rebuilds.
+    ///
+    /// Panics if `alpha` is not in `0.5..1.0`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::with_scapegoat(0.7);
+    /// for value in 0..1000 {
+    ///     binary_tree.insert(value);
+    /// }
+    ///
+    /// assert!(binary_tree.height() < 30);
+    /// ```
+    pub fn with_scapegoat(alpha: f64) -> Self {
+        assert!(
+            (0.5..1.0).contains(&alpha),
+            "alpha must be in 0.5..1.0, got {}",
+            alpha
+        );
+
+        let mut binary_tree = BinaryTree::new();
+        binary_tree.scapegoat = Some(alpha);
+        binary_tree
     }
 
     /// Inserts a new element into the tree. The tree alway keeps an ordered structure by
@@ -55,11 +157,105 @@ impl<T: Eq + std::cmp::Ord> BinaryTree<T> {
     /// assert!(binary_tree.contains(2));
     /// ```
     pub fn insert(&mut self, value: T) -> bool {
-        if self.is_empty() {
-            self.head = Some(Box::new(Node::new(value)));
-            return true;
+        let mut current = &mut self.head;
+        // The directions taken, only recorded in scapegoat mode
+        let mut path = Vec::new();
+
+        while let Some(node) = current {
+            self.metrics.count_visited_node();
+
+            let direction = value.cmp(&node.value);
+            if self.scapegoat.is_some() {
+                path.push(direction);
+            }
+
+            match direction {
+                Ordering::Less => current = &mut node.left,
+                Ordering::Equal => return false,
+                Ordering::Greater => current = &mut node.right,
+            }
         }
-        self.head.as_mut().unwrap().insert(value)
+
+        *current = Some(Box::new(Node::new(value)));
+        self.len += 1;
+
+        if let Some(alpha) = self.scapegoat {
+            self.max_len = self.max_len.max(self.len);
+
+            if path.len() > depth_limit(alpha, self.len) {
+                self.rebuild_scapegoat(alpha, &path);
+            }
+        }
+
+        true
+    }
+
+    /// Walks up from the node at the end of `path` to the first ancestor that isn't
+    /// alpha-weight-balanced and rebuilds its subtree.
+    fn rebuild_scapegoat(&mut self, alpha: f64, path: &[Ordering]) {
+        let mut ancestors = Vec::with_capacity(path.len());
+        let mut node = self.head.as_deref().unwrap();
+
+        for direction in path {
+            ancestors.push(node);
+            node = node.child(*direction).as_deref().unwrap();
+        }
+
+        let mut size = 1;
+        let mut depth = path.len();
+
+        // A node deeper than the limit always has an unbalanced ancestor
+        while depth > 0 {
+            depth -= 1;
+            let sibling = ancestors[depth].child(path[depth].reverse());
+            let parent_size = size + 1 + Node::size(sibling);
+
+            if size as f64 > alpha * parent_size as f64 {
+                break;
+            }
+
+            size = parent_size;
+        }
+
+        let mut link = &mut self.head;
+        for direction in &path[..depth] {
+            link = link.as_mut().unwrap().child_mut(*direction);
+        }
+
+        let rotations = Node::rebuild(link);
+        self.metrics.count_rotations(rotations);
+    }
+
+    /// Rebuilds the whole tree in scapegoat mode once enough elements got removed.
+    fn shrink_scapegoat(&mut self) {
+        if let Some(alpha) = self.scapegoat {
+            if (self.len as f64) < alpha * self.max_len as f64 {
+                self.rebalance();
+            }
+        }
+    }
+
+    /// Rebuilds the tree into a balanced one using the Day-Stout-Warren algorithm. The tree
+    /// is first rotated into a vine, a tree where every node only has a right child, which is
+    /// then folded back into a tree by left rotations. All levels except the last one end up
+    /// full. This takes `O(n)` time and no extra space.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in 0..7 {
+    ///     binary_tree.insert(value);
+    /// }
+    /// assert_eq!(binary_tree.height(), 7);
+    ///
+    /// binary_tree.rebalance();
+    /// assert_eq!(binary_tree.height(), 3);
+    /// assert_eq!(binary_tree.preorder(), vec![&3, &1, &0, &2, &5, &4, &6]);
+    /// ```
+    pub fn rebalance(&mut self) {
+        let rotations = Node::rebuild(&mut self.head);
+        self.metrics.count_rotations(rotations);
+        self.max_len = self.len;
     }
 
     /// Checks if the tree is empty.
@@ -67,259 +263,1459 @@ impl<T: Eq + std::cmp::Ord> BinaryTree<T> {
         self.head.is_none()
     }
 
-    /// Searches for an element in the tree and returns whether the tree contains the element
-    /// or not. As the tree is always ordered, this happens relatively fast. (As long as it's not)
-    /// super unordered.
+    /// Returns the number of elements in the tree. The length is tracked, so this takes `O(1)`.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::binary_tree::BinaryTree;
     /// let mut binary_tree = BinaryTree::new();
     /// binary_tree.insert(1);
+    /// binary_tree.insert(2);
+    /// binary_tree.insert(2);
     ///
-    /// assert!(binary_tree.contains(1));
+    /// assert_eq!(binary_tree.len(), 2);
     /// ```
-    pub fn contains(&self, value: T) -> bool {
-        match self.head.as_ref() {
-            Some(node) => match value.cmp(&node.value) {
-                Ordering::Equal => true,
-                Ordering::Greater => match node.right.as_ref() {
-                    Some(node) => node.contains(value),
-                    None => false,
-                },
-                Ordering::Less => match node.left.as_ref() {
-                    Some(node) => node.contains(value),
-                    None => false,
-                },
-            },
-            None => false,
-        }
+    pub fn len(&self) -> usize {
+        self.len
     }
 
-    /// Traverses the tree inorder. That means it goes through the tree and recursively
-    /// visites the leftmost child, the root and then the rightmost child.
+    /// Returns the number of levels of the tree, 0 for an empty tree.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::binary_tree::BinaryTree;
     /// let mut binary_tree = BinaryTree::new();
     /// binary_tree.insert(10);
     /// binary_tree.insert(8);
-    /// binary_tree.insert(11);
     /// binary_tree.insert(9);
-    /// binary_tree.insert(12);
     ///
-    /// assert_eq!(binary_tree.inorder(), vec![&8, &9, &10, &11, &12]);
+    /// assert_eq!(binary_tree.height(), 3);
     /// ```
-    pub fn inorder(&self) -> Vec<&T> {
-        match &self.head {
-            Some(node) => node.inorder(),
-            None => Vec::new(),
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut visited = Queue::new();
+
+        if let Some(head) = self.head.as_deref() {
+            visited.enqueue(head);
+        }
+
+        // The queue holds exactly one level at the start of every round
+        while !visited.is_empty() {
+            for _ in 0..visited.size() {
+                let node = visited.dequeue().unwrap();
+
+                if let Some(left) = node.left.as_deref() {
+                    visited.enqueue(left);
+                }
+                if let Some(right) = node.right.as_deref() {
+                    visited.enqueue(right);
+                }
+            }
+
+            height += 1;
         }
+
+        height
     }
 
-    /// Traverses the tree preorder. That means it goes through the tree and recursively
-    /// visites the root, the leftmost child, and then the rightmost child.
+    /// Checks if the heights of the two subtrees of every node differ by at most one.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::binary_tree::BinaryTree;
     /// let mut binary_tree = BinaryTree::new();
     /// binary_tree.insert(10);
     /// binary_tree.insert(8);
-    /// binary_tree.insert(11);
-    /// binary_tree.insert(9);
-    /// binary_tree.insert(12);
     ///
-    /// assert_eq!(binary_tree.preorder(), vec![&10, &8, &9, &11, &12]);
+    /// assert!(binary_tree.is_balanced());
+    /// binary_tree.insert(9);
+    /// assert!(!binary_tree.is_balanced());
     /// ```
-    pub fn preorder(&self) -> Vec<&T> {
-        match &self.head {
-            Some(node) => node.preorder(),
-            None => Vec::new(),
+    pub fn is_balanced(&self) -> bool {
+        // Postorder, so the heights of both subtrees are known when a node is expanded
+        let mut visited = Stack::new();
+        let mut heights: Vec<usize> = Vec::new();
+
+        if let Some(head) = self.head.as_deref() {
+            visited.push((head, false));
         }
+
+        while let Some((node, expanded)) = visited.pop() {
+            if !expanded {
+                visited.push((node, true));
+                if let Some(right) = node.right.as_deref() {
+                    visited.push((right, false));
+                }
+                if let Some(left) = node.left.as_deref() {
+                    visited.push((left, false));
+                }
+                continue;
+            }
+
+            // The right subtree was finished last, its height is on top
+            let right_height = node.right.as_ref().map_or(0, |_| heights.pop().unwrap());
+            let left_height = node.left.as_ref().map_or(0, |_| heights.pop().unwrap());
+
+            if left_height.abs_diff(right_height) > 1 {
+                return false;
+            }
+
+            heights.push(1 + left_height.max(right_height));
+        }
+
+        true
     }
 
-    /// Traverses the tree postorder. That means it goes through the tree and recursively
-    /// visites the the leftmost child, the righmost child and then the root node.
+    /// Checks if every level except the last one is full and the last level is filled
+    /// from the left.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::binary_tree::BinaryTree;
     /// let mut binary_tree = BinaryTree::new();
     /// binary_tree.insert(10);
     /// binary_tree.insert(8);
-    /// binary_tree.insert(11);
-    /// binary_tree.insert(9);
-    /// binary_tree.insert(12);
     ///
-    /// assert_eq!(binary_tree.preorder(), vec![&10, &8, &9, &11, &12]);
+    /// assert!(binary_tree.is_complete());
+    /// binary_tree.insert(9);
+    /// assert!(!binary_tree.is_complete());
     /// ```
-    pub fn postorder(&self) -> Vec<&T> {
-        match &self.head {
-            Some(node) => node.postorder(),
-            None => Vec::new(),
+    pub fn is_complete(&self) -> bool {
+        let mut visited = Queue::new();
+        let mut gap_seen = false;
+
+        visited.enqueue(self.head.as_deref());
+
+        // Level order including the missing children, no node may follow the first gap
+        while let Some(link) = visited.dequeue() {
+            match link {
+                Some(node) => {
+                    if gap_seen {
+                        return false;
+                    }
+                    visited.enqueue(node.left.as_deref());
+                    visited.enqueue(node.right.as_deref());
+                }
+                None => gap_seen = true,
+            }
         }
+
+        true
     }
 
-    /// Returns an `Iterator` over the elements of a tree. First the root node is returned,
-    /// than an ordering from the lowest to the highest element.
+    /// Checks if every level of the tree is full.
     /// # Example
-    /// ```
+    /// ```rust
     /// use data_structure_with_colin::binary_tree::BinaryTree;
     /// let mut binary_tree = BinaryTree::new();
     /// binary_tree.insert(10);
     /// binary_tree.insert(8);
-    /// binary_tree.insert(11);
     ///
-    /// for elem in binary_tree.iter() {
-    ///     println!("{}", elem);   
-    /// }
+    /// assert!(!binary_tree.is_perfect());
+    /// binary_tree.insert(11);
+    /// assert!(binary_tree.is_perfect());
     /// ```
-    pub fn iter<'a>(&'a self) -> Iter<'a, T> {
-        let mut visited = Box::new(Stack::new());
-        visited.push(self.head.as_ref().unwrap());
-        Iter { visited: visited }
+    pub fn is_perfect(&self) -> bool {
+        // A perfect tree of height h holds 2^h - 1 elements
+        1usize
+            .checked_shl(self.height() as u32)
+            .is_some_and(|size| size == self.len + 1)
     }
-}
 
-impl<T: Eq + std::cmp::Ord> Node<T> {
-    pub fn new(value: T) -> Self {
-        Node {
-            left: None,
-            right: None,
-            value,
-        }
-    }
+    /// Returns the smallest element of the tree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    ///
+    /// assert_eq!(binary_tree.min(), Some(&8));
+    /// ```
+    pub fn min(&self) -> Option<&T> {
+        let mut node = self.head.as_deref()?;
 
-    pub fn insert(&mut self, value: T) -> bool {
-        match value.cmp(&self.value) {
-            Ordering::Less => match &mut self.left {
-                Some(node) => node.insert(value),
-                None => {
-                    self.left = Some(Box::new(Node::new(value)));
-                    return true;
-                }
-            },
-            Ordering::Equal => return false,
-            Ordering::Greater => match &mut self.right {
-                Some(node) => node.insert(value),
-                None => {
-                    self.right = Some(Box::new(Node::new(value)));
-                    return true;
-                }
-            },
+        while let Some(left) = node.left.as_deref() {
+            node = left;
         }
+
+        Some(&node.value)
     }
 
-    fn contains(&self, value: T) -> bool {
-        match value.cmp(&self.value) {
-            Ordering::Equal => true,
-            Ordering::Greater => match self.right.as_ref() {
-                Some(node) => node.contains(value),
-                None => false,
-            },
-            Ordering::Less => match self.left.as_ref() {
-                Some(node) => node.contains(value),
-                None => false,
-            },
+    /// Returns the largest element of the tree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    ///
+    /// assert_eq!(binary_tree.max(), Some(&10));
+    /// ```
+    pub fn max(&self) -> Option<&T> {
+        let mut node = self.head.as_deref()?;
+
+        while let Some(right) = node.right.as_deref() {
+            node = right;
         }
+
+        Some(&node.value)
     }
 
-    pub fn inorder(&self) -> Vec<&T> {
-        let mut result = vec![];
-        match self.left.as_ref() {
-            Some(node) => {
-                let mut left_vec = node.inorder();
-                result.append(left_vec.as_mut());
-            }
-            None => (),
-        }
-        result.push(&self.value);
-        match self.right.as_ref() {
-            Some(node) => {
-                let mut right_vec = node.inorder();
-                result.append(right_vec.as_mut());
+    /// Returns the largest element that is smaller than or equal to `value`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(20);
+    ///
+    /// assert_eq!(binary_tree.floor(&15), Some(&10));
+    /// assert_eq!(binary_tree.floor(&20), Some(&20));
+    /// assert_eq!(binary_tree.floor(&5), None);
+    /// ```
+    pub fn floor(&self, value: &T) -> Option<&T> {
+        let mut current = self.head.as_deref();
+        let mut floor = None;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => {
+                    floor = Some(&node.value);
+                    current = node.right.as_deref();
+                }
             }
-            None => (),
         }
-        result
+
+        floor
     }
 
-    pub fn postorder(&self) -> Vec<&T> {
-        let mut result = vec![];
-        match self.left.as_ref() {
-            Some(node) => {
-                let mut vec_left = node.postorder();
-                result.append(vec_left.as_mut());
-            }
-            None => (),
-        }
-        match self.right.as_ref() {
-            Some(node) => {
-                let mut right_vec = node.postorder();
-                result.append(right_vec.as_mut());
+    /// Returns the smallest element that is greater than or equal to `value`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(20);
+    ///
+    /// assert_eq!(binary_tree.ceiling(&15), Some(&20));
+    /// assert_eq!(binary_tree.ceiling(&10), Some(&10));
+    /// assert_eq!(binary_tree.ceiling(&25), None);
+    /// ```
+    pub fn ceiling(&self, value: &T) -> Option<&T> {
+        let mut current = self.head.as_deref();
+        let mut ceiling = None;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => {
+                    ceiling = Some(&node.value);
+                    current = node.left.as_deref();
+                }
+                Ordering::Equal => return Some(&node.value),
+                Ordering::Greater => current = node.right.as_deref(),
             }
-            None => (),
         }
-        result.push(&self.value);
-        result
+
+        ceiling
     }
 
-    pub fn preorder(&self) -> Vec<&T> {
-        let mut result = vec![];
-        result.push(&self.value);
-        match self.left.as_ref() {
-            Some(node) => {
-                let mut vec_left = node.preorder();
-                result.append(vec_left.as_mut());
-            }
-            None => (),
-        }
-        match self.right.as_ref() {
-            Some(node) => {
-                let mut right_vec = node.preorder();
-                result.append(right_vec.as_mut());
-            }
-            None => (),
-        }
-        result
+    /// Returns the `k`-th smallest element, counting from 0 like [`Iterator::nth`].
+    /// Only the first `k` elements are visited.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    ///
+    /// assert_eq!(binary_tree.kth_smallest(0), Some(&8));
+    /// assert_eq!(binary_tree.kth_smallest(2), Some(&11));
+    /// assert_eq!(binary_tree.kth_smallest(3), None);
+    /// ```
+    pub fn kth_smallest(&self, k: usize) -> Option<&T> {
+        self.inorder_iter().nth(k)
     }
-}
 
-impl<'a, T: std::cmp::Eq> Iterator for Iter<'a, T> {
-    type Item = &'a T;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.visited.is_empty() {
-            return None;
+    /// Returns the deepest element that has both `a` and `b` in its subtree, or `None` if
+    /// one of them isn't part of the tree. An element is its own ancestor.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(5);
+    /// binary_tree.insert(3);
+    /// binary_tree.insert(7);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.lowest_common_ancestor(&3, &7), Some(&5));
+    /// assert_eq!(binary_tree.lowest_common_ancestor(&3, &12), Some(&10));
+    /// assert_eq!(binary_tree.lowest_common_ancestor(&5, &7), Some(&5));
+    /// assert_eq!(binary_tree.lowest_common_ancestor(&5, &8), None);
+    /// ```
+    pub fn lowest_common_ancestor(&self, a: &T, b: &T) -> Option<&T> {
+        let mut node = self.head.as_deref()?;
+
+        // Descend until a and b end up in different subtrees
+        loop {
+            node = match (a.cmp(&node.value), b.cmp(&node.value)) {
+                (Ordering::Less, Ordering::Less) => node.left.as_deref()?,
+                (Ordering::Greater, Ordering::Greater) => node.right.as_deref()?,
+                _ => break,
+            };
         }
 
-        let node = self.visited.pop();
-        match (
-            node.unwrap().as_ref().left.as_ref(),
-            node.unwrap().as_ref().right.as_ref(),
-        ) {
-            (None, None) => (),
-            (leaf @ Some(_), None) | (None, leaf @ Some(_)) => self.visited.push(&leaf.unwrap()),
-            (Some(left), Some(right)) => {
-                self.visited.push(right);
-                self.visited.push(left);
+        let contains = |value: &T| {
+            let mut current = Some(node);
+            while let Some(current_node) = current {
+                match value.cmp(&current_node.value) {
+                    Ordering::Less => current = current_node.left.as_deref(),
+                    Ordering::Equal => return true,
+                    Ordering::Greater => current = current_node.right.as_deref(),
+                }
             }
-        }
+            false
+        };
 
-        return Some(&node.unwrap().as_ref().value);
+        if contains(a) && contains(b) {
+            Some(&node.value)
+        } else {
+            None
+        }
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    /// Removes an element from the tree and returns whether it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(1);
+    ///
+    /// assert!(binary_tree.remove(&1));
+    /// assert!(!binary_tree.remove(&1));
+    /// ```
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
 
-    #[test]
-    fn test_insert() {
-        let mut sut = BinaryTree::new();
-        assert!(sut.insert(42));
-        assert!(sut.insert(41));
-        assert!(sut.insert(43));
+    /// Removes an element from the tree and returns it. A node with two children is
+    /// replaced by its inorder successor, the smallest element of its right subtree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.take(&10), Some(10));
+    /// assert_eq!(binary_tree.preorder(), vec![&12, &8]);
+    /// ```
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let value = Node::take_by(&mut self.head, |node_value| value.cmp(node_value))?;
+        self.len -= 1;
+        self.shrink_scapegoat();
+        Some(value)
     }
 
-    #[test]
+    /// Removes the smallest element of the tree and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(2);
+    /// binary_tree.insert(1);
+    ///
+    /// assert_eq!(binary_tree.pop_min(), Some(1));
+    /// ```
+    pub fn pop_min(&mut self) -> Option<T> {
+        let min = Node::take_leftmost(&mut self.head)?;
+        self.len -= 1;
+        self.shrink_scapegoat();
+        Some(min)
+    }
+
+    /// Removes the largest element of the tree and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(2);
+    /// binary_tree.insert(1);
+    ///
+    /// assert_eq!(binary_tree.pop_max(), Some(2));
+    /// ```
+    pub fn pop_max(&mut self) -> Option<T> {
+        let max = Node::take_rightmost(&mut self.head)?;
+        self.len -= 1;
+        self.shrink_scapegoat();
+        Some(max)
+    }
+
+    /// Removes all elements from the tree.
+    pub fn clear(&mut self) {
+        self.head = None;
+        self.len = 0;
+        self.max_len = 0;
+    }
+
+    /// Searches for an element in the tree and returns whether the tree contains the element
+    /// or not. As the tree is always ordered, this happens relatively fast. (As long as it's not)
+    /// super unordered.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(1);
+    ///
+    /// assert!(binary_tree.contains(1));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        let mut current = self.head.as_deref();
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return true,
+                Ordering::Greater => current = node.right.as_deref(),
+            }
+        }
+
+        false
+    }
+
+    /// Traverses the tree inorder. That means it goes through the tree and recursively
+    /// visites the leftmost child, the root and then the rightmost child.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.inorder(), vec![&8, &9, &10, &11, &12]);
+    /// ```
+    pub fn inorder(&self) -> Vec<&T> {
+        self.inorder_iter().collect()
+    }
+
+    /// Traverses the tree preorder. That means it goes through the tree and recursively
+    /// visites the root, the leftmost child, and then the rightmost child.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.preorder(), vec![&10, &8, &9, &11, &12]);
+    /// ```
+    pub fn preorder(&self) -> Vec<&T> {
+        self.preorder_iter().collect()
+    }
+
+    /// Traverses the tree postorder. That means it goes through the tree and recursively
+    /// visites the the leftmost child, the righmost child and then the root node.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.postorder(), vec![&9, &8, &12, &11, &10]);
+    /// ```
+    pub fn postorder(&self) -> Vec<&T> {
+        self.postorder_iter().collect()
+    }
+
+    /// Traverses the tree level by level, every level from left to right.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.level_order(), vec![&10, &8, &11, &9, &12]);
+    /// ```
+    pub fn level_order(&self) -> Vec<&T> {
+        self.level_order_iter().collect()
+    }
+
+    /// Groups the elements by their depth. Every level is ordered from left to right.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.levels(), vec![vec![&10], vec![&8, &11], vec![&9, &12]]);
+    /// ```
+    pub fn levels(&self) -> Vec<Vec<&T>> {
+        let mut levels = Vec::new();
+        let mut visited = Queue::new();
+
+        if let Some(head) = self.head.as_deref() {
+            visited.enqueue(head);
+        }
+
+        // The queue holds exactly one level at the start of every round
+        while !visited.is_empty() {
+            let mut level = Vec::with_capacity(visited.size());
+
+            for _ in 0..visited.size() {
+                let node = visited.dequeue().unwrap();
+                level.push(&node.value);
+
+                if let Some(left) = node.left.as_deref() {
+                    visited.enqueue(left);
+                }
+                if let Some(right) = node.right.as_deref() {
+                    visited.enqueue(right);
+                }
+            }
+
+            levels.push(level);
+        }
+
+        levels
+    }
+
+    /// Traverses the tree level by level, alternating between left to right and right to left,
+    /// starting with left to right at the root.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.zigzag_order(), vec![&10, &11, &8, &9, &12]);
+    /// ```
+    pub fn zigzag_order(&self) -> Vec<&T> {
+        let mut result = Vec::new();
+
+        for (depth, mut level) in self.levels().into_iter().enumerate() {
+            if depth % 2 == 1 {
+                level.reverse();
+            }
+            result.append(&mut level);
+        }
+
+        result
+    }
+
+    /// Returns the leftmost element of every level, that is what is visible when looking
+    /// at the tree from the left.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.left_view(), vec![&10, &8, &12]);
+    /// ```
+    pub fn left_view(&self) -> Vec<&T> {
+        self.levels().into_iter().map(|level| level[0]).collect()
+    }
+
+    /// Returns the rightmost element of every level, that is what is visible when looking
+    /// at the tree from the right.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    ///
+    /// assert_eq!(binary_tree.right_view(), vec![&10, &11, &9]);
+    /// ```
+    pub fn right_view(&self) -> Vec<&T> {
+        self.levels()
+            .into_iter()
+            .map(|level| level[level.len() - 1])
+            .collect()
+    }
+
+    /// Returns the maximum number of elements on a single level, 0 for an empty tree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    /// binary_tree.insert(12);
+    ///
+    /// assert_eq!(binary_tree.width(), 2);
+    /// ```
+    pub fn width(&self) -> usize {
+        self.levels().iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Returns the depth of an element, the root having depth 0, or `None` if the tree
+    /// doesn't contain the element.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(9);
+    ///
+    /// assert_eq!(binary_tree.depth_of(&10), Some(0));
+    /// assert_eq!(binary_tree.depth_of(&9), Some(2));
+    /// assert_eq!(binary_tree.depth_of(&7), None);
+    /// ```
+    pub fn depth_of(&self, value: &T) -> Option<usize> {
+        let mut current = self.head.as_deref();
+        let mut depth = 0;
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some(depth),
+                Ordering::Greater => current = node.right.as_deref(),
+            }
+            depth += 1;
+        }
+
+        None
+    }
+
+    /// Checks if both trees have the same shape, no matter which values they hold.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut numbers = BinaryTree::new();
+    /// for value in vec![2, 1, 3] {
+    ///     numbers.insert(value);
+    /// }
+    /// let mut letters = BinaryTree::new();
+    /// for value in vec!['b', 'a', 'c'] {
+    ///     letters.insert(value);
+    /// }
+    /// let mut list = BinaryTree::new();
+    /// for value in vec![1, 2, 3] {
+    ///     list.insert(value);
+    /// }
+    ///
+    /// assert!(numbers.same_shape(&letters));
+    /// assert!(!numbers.same_shape(&list));
+    /// ```
+    pub fn same_shape<U>(&self, other: &BinaryTree<U>) -> bool {
+        Node::pairs_match(
+            self.head.as_deref(),
+            other.head.as_deref(),
+            false,
+            |_, _| true,
+        )
+    }
+
+    /// Swaps the left and right child of every node, so the tree becomes its mirror image.
+    ///
+    /// A mirrored tree is ordered from the largest to the smallest element. Operations which
+    /// search the tree, like [`BinaryTree::insert()`] or [`BinaryTree::contains()`], don't
+    /// work on it until it is mirrored back.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 8, 11, 9] {
+    ///     binary_tree.insert(value);
+    /// }
+    /// binary_tree.mirror();
+    ///
+    /// assert_eq!(binary_tree.inorder(), vec![&11, &10, &9, &8]);
+    /// ```
+    pub fn mirror(&mut self) {
+        let mut pending: Vec<&mut Node<T>> = self.head.as_deref_mut().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            std::mem::swap(&mut node.left, &mut node.right);
+            pending.extend(node.left.as_deref_mut());
+            pending.extend(node.right.as_deref_mut());
+        }
+    }
+
+    /// Checks if this tree is the mirror image of `other`, holding the same values.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 8, 11, 9] {
+    ///     binary_tree.insert(value);
+    /// }
+    /// let mut mirrored = BinaryTree::new();
+    /// for value in vec![10, 8, 11, 9] {
+    ///     mirrored.insert(value);
+    /// }
+    /// mirrored.mirror();
+    ///
+    /// assert!(mirrored.is_mirror_of(&binary_tree));
+    /// assert!(!binary_tree.is_mirror_of(&binary_tree));
+    /// ```
+    pub fn is_mirror_of(&self, other: &BinaryTree<T>) -> bool {
+        Node::pairs_match(self.head.as_deref(), other.head.as_deref(), true, T::eq)
+    }
+
+    /// Checks if `other` has a node whose subtree equals this tree in shape and values.
+    /// The empty tree is a subtree of every tree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 5, 15, 3, 7] {
+    ///     binary_tree.insert(value);
+    /// }
+    /// let mut subtree = BinaryTree::new();
+    /// for value in vec![5, 3, 7] {
+    ///     subtree.insert(value);
+    /// }
+    /// let mut partial = BinaryTree::new();
+    /// for value in vec![5, 3] {
+    ///     partial.insert(value);
+    /// }
+    ///
+    /// assert!(subtree.is_subtree_of(&binary_tree));
+    /// assert!(!partial.is_subtree_of(&binary_tree));
+    /// ```
+    pub fn is_subtree_of(&self, other: &BinaryTree<T>) -> bool {
+        let head = match self.head.as_deref() {
+            Some(head) => head,
+            None => return true,
+        };
+        let mut pending: Vec<&Node<T>> = other.head.as_deref().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            if node.value == head.value && Node::pairs_match(Some(head), Some(node), false, T::eq) {
+                return true;
+            }
+
+            pending.extend(node.left.as_deref());
+            pending.extend(node.right.as_deref());
+        }
+
+        false
+    }
+
+    /// Returns the tree in preorder with a `None` marker for every missing child, which
+    /// describes the shape completely. [`BinaryTree::deserialize_structure()`] builds the
+    /// same tree again.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![2, 1] {
+    ///     binary_tree.insert(value);
+    /// }
+    ///
+    /// assert_eq!(
+    ///     binary_tree.serialize_structure(),
+    ///     vec![Some(&2), Some(&1), None, None, None]
+    /// );
+    /// ```
+    pub fn serialize_structure(&self) -> Vec<Option<&T>> {
+        let mut structure = Vec::new();
+        let mut pending = vec![self.head.as_deref()];
+
+        while let Some(link) = pending.pop() {
+            match link {
+                Some(node) => {
+                    structure.push(Some(&node.value));
+                    pending.push(node.right.as_deref());
+                    pending.push(node.left.as_deref());
+                }
+                None => structure.push(None),
+            }
+        }
+
+        structure
+    }
+
+    /// Builds a tree from its preorder with `None` markers for missing children, as returned
+    /// by [`BinaryTree::serialize_structure()`]. The values are taken as they are, so the
+    /// result is only ordered if the input describes an ordered tree.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::{BinaryTree, StructureError};
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 8, 11, 9] {
+    ///     binary_tree.insert(value);
+    /// }
+    /// let structure = binary_tree.serialize_structure().into_iter().map(|value| value.copied());
+    /// let copy = BinaryTree::deserialize_structure(structure).unwrap();
+    ///
+    /// assert!(copy.same_shape(&binary_tree));
+    /// assert_eq!(copy.preorder(), binary_tree.preorder());
+    /// assert_eq!(
+    ///     BinaryTree::deserialize_structure(vec![Some(1), None]).err(),
+    ///     Some(StructureError::Incomplete)
+    /// );
+    /// ```
+    pub fn deserialize_structure<I: IntoIterator<Item = Option<T>>>(
+        structure: I,
+    ) -> Result<Self, StructureError> {
+        let structure: Vec<Option<T>> = structure.into_iter().collect();
+
+        // Every value opens two more places for a child, every entry fills one
+        let mut open_places = 1;
+        for entry in structure.iter() {
+            if open_places == 0 {
+                return Err(StructureError::Trailing);
+            }
+            open_places -= 1;
+            if entry.is_some() {
+                open_places += 2;
+            }
+        }
+        if open_places > 0 {
+            return Err(StructureError::Incomplete);
+        }
+
+        // Backwards, the subtrees of a node are complete before the node itself comes up
+        let mut tree = BinaryTree::new();
+        let mut subtrees: Vec<Link<T>> = Vec::new();
+
+        for entry in structure.into_iter().rev() {
+            let link = entry.map(|value| {
+                tree.len += 1;
+                Box::new(Node {
+                    left: subtrees.pop().expect("checked to be complete"),
+                    right: subtrees.pop().expect("checked to be complete"),
+                    value,
+                })
+            });
+            subtrees.push(link);
+        }
+
+        tree.head = subtrees.pop().expect("checked to be complete");
+        tree.max_len = tree.len;
+        Ok(tree)
+    }
+
+    /// Returns every path from the root down to a leaf, from the leftmost to the rightmost leaf.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 8, 11, 9] {
+    ///     binary_tree.insert(value);
+    /// }
+    ///
+    /// assert_eq!(binary_tree.paths_to_leaves(), vec![vec![&10, &8, &9], vec![&10, &11]]);
+    /// ```
+    pub fn paths_to_leaves(&self) -> Vec<Vec<&T>> {
+        let mut paths = Vec::new();
+        let mut path = Vec::new();
+        let mut pending: Vec<(&Node<T>, usize)> = self
+            .head
+            .as_deref()
+            .map(|head| (head, 0))
+            .into_iter()
+            .collect();
+
+        while let Some((node, depth)) = pending.pop() {
+            path.truncate(depth);
+            path.push(&node.value);
+
+            if node.left.is_none() && node.right.is_none() {
+                paths.push(path.clone());
+            }
+
+            pending.extend(node.right.as_deref().map(|right| (right, depth + 1)));
+            pending.extend(node.left.as_deref().map(|left| (left, depth + 1)));
+        }
+
+        paths
+    }
+
+    /// Builds a tree of the same shape holding `f` applied to every value, calling `f` in
+    /// preorder. The result is only ordered if `f` keeps the order of the values.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// for value in vec![10, 8, 11] {
+    ///     binary_tree.insert(value);
+    /// }
+    /// let labels = binary_tree.map(|value| format!("x{}", value));
+    ///
+    /// assert!(labels.same_shape(&binary_tree));
+    /// assert_eq!(labels.preorder(), vec!["x10", "x8", "x11"]);
+    /// ```
+    pub fn map<U: Eq + Ord, F: FnMut(&T) -> U>(&self, mut f: F) -> BinaryTree<U> {
+        let structure = self
+            .serialize_structure()
+            .into_iter()
+            .map(|value| value.map(&mut f));
+
+        let mut tree =
+            BinaryTree::deserialize_structure(structure).expect("a serialized tree is complete");
+        tree.scapegoat = self.scapegoat;
+        tree
+    }
+
+    /// Returns an `Iterator` over the elements of a tree. First the root node is returned,
+    /// than an ordering from the lowest to the highest element.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    ///
+    /// for elem in binary_tree.iter() {
+    ///     println!("{}", elem);   
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.preorder_iter()
+    }
+
+    /// Returns a lazy `Iterator` visiting the tree preorder: the root, the left and then the
+    /// right subtree.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    ///
+    /// let mut iter = binary_tree.preorder_iter();
+    /// assert_eq!(iter.next(), Some(&10));
+    /// assert_eq!(iter.next(), Some(&8));
+    /// assert_eq!(iter.next(), Some(&9));
+    /// assert_eq!(iter.next(), Some(&11));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn preorder_iter(&self) -> PreorderIter<'_, T> {
+        let mut visited = Box::new(Stack::new());
+        if let Some(head) = self.head.as_deref() {
+            visited.push(head);
+        }
+        Iter { visited }
+    }
+
+    /// Returns a lazy `Iterator` visiting the tree inorder, that is from the lowest to the
+    /// highest element. It can be consumed from both ends.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    ///
+    /// let mut iter = binary_tree.inorder_iter();
+    /// assert_eq!(iter.next(), Some(&8));
+    /// assert_eq!(iter.next_back(), Some(&11));
+    /// assert_eq!(iter.next_back(), Some(&10));
+    /// assert_eq!(iter.next(), Some(&9));
+    /// assert_eq!(iter.next(), None);
+    /// assert_eq!(iter.next_back(), None);
+    /// ```
+    pub fn inorder_iter(&self) -> InorderIter<'_, T> {
+        let mut iter = InorderIter {
+            front: Stack::new(),
+            back: Stack::new(),
+            front_last: None,
+            back_last: None,
+            finished: false,
+        };
+        iter.push_left(&self.head);
+        iter.push_right(&self.head);
+        iter
+    }
+
+    /// Returns a lazy `Iterator` visiting the tree postorder: the left subtree, the right
+    /// subtree and then the root.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    ///
+    /// let postorder: Vec<_> = binary_tree.postorder_iter().collect();
+    /// assert_eq!(postorder, vec![&9, &8, &11, &10]);
+    /// ```
+    pub fn postorder_iter(&self) -> PostorderIter<'_, T> {
+        let mut visited = Stack::new();
+        if let Some(head) = self.head.as_deref() {
+            visited.push((head, false));
+        }
+        PostorderIter { visited }
+    }
+
+    /// Returns a lazy `Iterator` visiting the tree level by level, every level from left to right.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(10);
+    /// binary_tree.insert(8);
+    /// binary_tree.insert(11);
+    /// binary_tree.insert(9);
+    ///
+    /// let level_order: Vec<_> = binary_tree.level_order_iter().collect();
+    /// assert_eq!(level_order, vec![&10, &8, &11, &9]);
+    /// ```
+    pub fn level_order_iter(&self) -> LevelOrderIter<'_, T> {
+        let mut visited = Queue::new();
+        if let Some(head) = self.head.as_deref() {
+            visited.enqueue(head);
+        }
+        LevelOrderIter { visited }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Eq + std::cmp::Ord> BinaryTree<T> {
+    /// Returns the nodes visited by `insert` since creation or the last reset.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets all metrics back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+}
+
+impl<T: Eq + std::cmp::Ord> Default for BinaryTree<T> {
+    fn default() -> Self {
+        BinaryTree::new()
+    }
+}
+
+impl<T> Drop for BinaryTree<T> {
+    /// A tree built from sorted input is a long path, so the nodes are freed iteratively.
+    fn drop(&mut self) {
+        Node::drop_tree(self.head.take());
+    }
+}
+
+impl<T: Eq + std::cmp::Ord + Display> Visualize for BinaryTree<T> {
+    fn display_tree(&self) -> Option<DisplayNode> {
+        self.head.as_deref().map(Node::display_node)
+    }
+}
+
+/// The deepest depth a node of a scapegoat tree with `len` elements may have,
+/// `log(len)` to the base of `1 / alpha`.
+fn depth_limit(alpha: f64, len: usize) -> usize {
+    ((len as f64).ln() / (1.0 / alpha).ln()).floor() as usize
+}
+
+impl<T: Eq + std::cmp::Ord> Node<T> {
+    pub fn new(value: T) -> Self {
+        Node {
+            left: None,
+            right: None,
+            value,
+        }
+    }
+
+    /// Returns the left child for `Ordering::Less` and the right child otherwise.
+    fn child(&self, direction: Ordering) -> &Link<T> {
+        match direction {
+            Ordering::Less => &self.left,
+            _ => &self.right,
+        }
+    }
+
+    fn child_mut(&mut self, direction: Ordering) -> &mut Link<T> {
+        match direction {
+            Ordering::Less => &mut self.left,
+            _ => &mut self.right,
+        }
+    }
+
+    /// Counts the nodes below `link`.
+    fn size(link: &Link<T>) -> usize {
+        let mut size = 0;
+        let mut visited = Stack::new();
+
+        if let Some(node) = link.as_deref() {
+            visited.push(node);
+        }
+
+        while let Some(node) = visited.pop() {
+            size += 1;
+            if let Some(left) = node.left.as_deref() {
+                visited.push(left);
+            }
+            if let Some(right) = node.right.as_deref() {
+                visited.push(right);
+            }
+        }
+
+        size
+    }
+
+    /// Lets the left child of the node at `link` take its place.
+    fn rotate_right(link: &mut Link<T>) {
+        let mut node = link.take().unwrap();
+        let mut left = node.left.take().unwrap();
+        node.left = left.right.take();
+        left.right = Some(node);
+        *link = Some(left);
+    }
+
+    /// Lets the right child of the node at `link` take its place.
+    fn rotate_left(link: &mut Link<T>) {
+        let mut node = link.take().unwrap();
+        let mut right = node.right.take().unwrap();
+        node.right = right.left.take();
+        right.left = Some(node);
+        *link = Some(right);
+    }
+
+    /// Rebuilds the subtree below `link` into a balanced one using the Day-Stout-Warren
+    /// algorithm and returns the number of rotations done.
+    fn rebuild(link: &mut Link<T>) -> usize {
+        let (size, mut rotations) = Node::tree_to_vine(link);
+
+        // Only the surplus of a perfect tree ends up on the last level
+        let leaves = size + 1 - (1 << (size + 1).ilog2());
+        rotations += Node::compress(link, leaves);
+
+        let mut size = size - leaves;
+        while size > 1 {
+            size /= 2;
+            rotations += Node::compress(link, size);
+        }
+
+        rotations
+    }
+
+    /// Rotates the subtree below `link` into a vine of right children. Returns the number
+    /// of nodes and the number of rotations done.
+    fn tree_to_vine(mut link: &mut Link<T>) -> (usize, usize) {
+        let mut size = 0;
+        let mut rotations = 0;
+
+        loop {
+            match link.as_ref().map(|node| node.left.is_some()) {
+                None => return (size, rotations),
+                Some(true) => {
+                    Node::rotate_right(link);
+                    rotations += 1;
+                }
+                Some(false) => {
+                    size += 1;
+                    link = &mut link.as_mut().unwrap().right;
+                }
+            }
+        }
+    }
+
+    /// Rotates every second node of the first `2 * count` nodes on the right spine below
+    /// `link` to the left, halving the length of the spine.
+    fn compress(mut link: &mut Link<T>, count: usize) -> usize {
+        for _ in 0..count {
+            Node::rotate_left(link);
+            link = &mut link.as_mut().unwrap().right;
+        }
+
+        count
+    }
+
+    /// Searches below `link` for the node `cmp` returns `Ordering::Equal` for, removes it and
+    /// returns its value. `cmp` compares the searched value with the value of a node.
+    fn take_by<F: FnMut(&T) -> Ordering>(mut link: &mut Link<T>, mut cmp: F) -> Option<T> {
+        loop {
+            match cmp(&link.as_ref()?.value) {
+                Ordering::Equal => return Node::take(link),
+                Ordering::Less => link = &mut link.as_mut().unwrap().left,
+                Ordering::Greater => link = &mut link.as_mut().unwrap().right,
+            }
+        }
+    }
+
+    /// Removes the node at `link` and returns its value. A node with two children is
+    /// replaced by its inorder successor.
+    fn take(link: &mut Link<T>) -> Option<T> {
+        let mut node = link.take()?;
+
+        match (node.left.take(), node.right.take()) {
+            // Leaf
+            (None, None) => (),
+            // One child, which takes the place of the node
+            (Some(child), None) | (None, Some(child)) => *link = Some(child),
+            // Two children, the inorder successor takes the place of the node
+            (Some(left), Some(right)) => {
+                let mut right = Some(right);
+                let successor = Node::take_leftmost(&mut right).unwrap();
+                let value = std::mem::replace(&mut node.value, successor);
+
+                node.left = Some(left);
+                node.right = right;
+                *link = Some(node);
+
+                return Some(value);
+            }
+        }
+
+        Some(node.value)
+    }
+
+    /// Removes the leftmost node below `link` and returns its value. Its right child takes its place.
+    fn take_leftmost(mut link: &mut Link<T>) -> Option<T> {
+        while link.as_ref()?.left.is_some() {
+            link = &mut link.as_mut().unwrap().left;
+        }
+
+        let mut node = link.take()?;
+        *link = node.right.take();
+        Some(node.value)
+    }
+
+    /// Removes the rightmost node below `link` and returns its value. Its left child takes its place.
+    fn take_rightmost(mut link: &mut Link<T>) -> Option<T> {
+        while link.as_ref()?.right.is_some() {
+            link = &mut link.as_mut().unwrap().right;
+        }
+
+        let mut node = link.take()?;
+        *link = node.left.take();
+        Some(node.value)
+    }
+}
+
+impl<T> Node<T> {
+    /// Frees a tree without recursing once per level. Left children are rotated up until
+    /// the current node has none and can be dropped.
+    pub(crate) fn drop_tree(mut current: Link<T>) {
+        while let Some(mut node) = current {
+            current = match node.left.take() {
+                Some(mut left) => {
+                    node.left = left.right.take();
+                    left.right = Some(node);
+                    Some(left)
+                }
+                None => node.right.take(),
+            };
+        }
+    }
+
+    /// Walks both subtrees side by side and checks that they have the same shape and all
+    /// value pairs match. With `mirrored` the left children of `a` are paired with the
+    /// right children of `b`.
+    fn pairs_match<U>(
+        a: Option<&Node<T>>,
+        b: Option<&Node<U>>,
+        mirrored: bool,
+        mut values_match: impl FnMut(&T, &U) -> bool,
+    ) -> bool {
+        let mut pending = vec![(a, b)];
+
+        while let Some(pair) = pending.pop() {
+            match pair {
+                (None, None) => {}
+                (Some(a), Some(b)) => {
+                    if !values_match(&a.value, &b.value) {
+                        return false;
+                    }
+
+                    let (b_left, b_right) = if mirrored {
+                        (&b.right, &b.left)
+                    } else {
+                        (&b.left, &b.right)
+                    };
+                    pending.push((a.left.as_deref(), b_left.as_deref()));
+                    pending.push((a.right.as_deref(), b_right.as_deref()));
+                }
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+impl<T: Display> Node<T> {
+    pub(crate) fn display_node(&self) -> DisplayNode {
+        DisplayNode::from_tree(
+            self,
+            |node| node.value.to_string(),
+            |node| (node.left.as_deref(), node.right.as_deref()),
+        )
+    }
+}
+
+impl<'a, T: std::cmp::Eq> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.visited.is_empty() {
+            return None;
+        }
+
+        let node = self.visited.pop();
+        match (
+            node.unwrap().left.as_deref(),
+            node.unwrap().right.as_deref(),
+        ) {
+            (None, None) => (),
+            (leaf @ Some(_), None) | (None, leaf @ Some(_)) => self.visited.push(leaf.unwrap()),
+            (Some(left), Some(right)) => {
+                self.visited.push(right);
+                self.visited.push(left);
+            }
+        }
+
+        Some(&node.unwrap().value)
+    }
+}
+
+impl<'a, T: std::cmp::Eq> InorderIter<'a, T> {
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link.as_deref() {
+            self.front.push(node);
+            link = &node.left;
+        }
+    }
+
+    fn push_right(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link.as_deref() {
+            self.back.push(node);
+            link = &node.right;
+        }
+    }
+}
+
+impl<'a, T: std::cmp::Eq> Iterator for InorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let node = self.front.pop()?;
+
+        // The front caught up with the back, every element was returned
+        if self.back_last.is_some_and(|back| std::ptr::eq(back, node)) {
+            self.finished = true;
+            return None;
+        }
+
+        self.push_left(&node.right);
+        self.front_last = Some(node);
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: std::cmp::Eq> DoubleEndedIterator for InorderIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let node = self.back.pop()?;
+
+        // The back caught up with the front, every element was returned
+        if self
+            .front_last
+            .is_some_and(|front| std::ptr::eq(front, node))
+        {
+            self.finished = true;
+            return None;
+        }
+
+        self.push_right(&node.left);
+        self.back_last = Some(node);
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: std::cmp::Eq> Iterator for PostorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, expanded)) = self.visited.pop() {
+            if expanded {
+                return Some(&node.value);
+            }
+
+            self.visited.push((node, true));
+            if let Some(right) = node.right.as_deref() {
+                self.visited.push((right, false));
+            }
+            if let Some(left) = node.left.as_deref() {
+                self.visited.push((left, false));
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, T: std::cmp::Eq> Iterator for LevelOrderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.visited.dequeue()?;
+
+        if let Some(left) = node.left.as_deref() {
+            self.visited.enqueue(left);
+        }
+        if let Some(right) = node.right.as_deref() {
+            self.visited.enqueue(right);
+        }
+
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{seq::SliceRandom, thread_rng, Rng};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_insert() {
+        let mut sut = BinaryTree::new();
+        assert!(sut.insert(42));
+        assert!(sut.insert(41));
+        assert!(sut.insert(43));
+    }
+
+    #[test]
     fn test_traversal() {
         let mut sut = BinaryTree::new();
         sut.insert(10);
@@ -377,4 +1773,571 @@ mod test {
         assert_eq!(iter.next(), Some(&11));
         assert_eq!(iter.next(), Some(&12));
     }
+
+    #[test]
+    fn test_remove_leaf() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(11);
+
+        assert!(sut.remove(&8));
+        assert!(!sut.remove(&8));
+        assert_eq!(sut.preorder(), vec![&10, &11]);
+    }
+
+    #[test]
+    fn test_remove_one_child() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(9);
+
+        assert_eq!(sut.take(&8), Some(8));
+        assert_eq!(sut.preorder(), vec![&10, &9]);
+        assert_eq!(sut.take(&10), Some(10));
+        assert_eq!(sut.preorder(), vec![&9]);
+    }
+
+    #[test]
+    fn test_remove_two_children() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(14);
+        sut.insert(12);
+        sut.insert(13);
+        sut.insert(15);
+
+        // 12 is the successor of 10, its right child 13 takes its place
+        assert_eq!(sut.take(&10), Some(10));
+        assert_eq!(sut.preorder(), vec![&12, &8, &14, &13, &15]);
+        assert_eq!(sut.take(&14), Some(14));
+        assert_eq!(sut.preorder(), vec![&12, &8, &15, &13]);
+    }
+
+    #[test]
+    fn test_pop_min_max_and_clear() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.pop_min(), None);
+        assert_eq!(sut.pop_max(), None);
+
+        for value in [5, 3, 8, 4, 9, 1].iter() {
+            sut.insert(*value);
+        }
+
+        assert_eq!(sut.pop_min(), Some(1));
+        assert_eq!(sut.pop_min(), Some(3));
+        assert_eq!(sut.pop_max(), Some(9));
+        assert_eq!(sut.pop_max(), Some(8));
+        assert_eq!(sut.inorder(), vec![&4, &5]);
+
+        sut.clear();
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn test_random_model_against_btree_set() {
+        let mut rng = thread_rng();
+        let mut sut = BinaryTree::new();
+        let mut model = BTreeSet::new();
+
+        for _ in 0..20000 {
+            let value = rng.gen_range(0, 500);
+
+            match rng.gen_range(0, 5) {
+                0 | 1 => assert_eq!(sut.insert(value), model.insert(value)),
+                2 => assert_eq!(sut.remove(&value), model.remove(&value)),
+                3 => assert_eq!(sut.take(&value), model.take(&value)),
+                _ => {
+                    if rng.gen() {
+                        assert_eq!(sut.pop_min(), model.pop_first());
+                    } else {
+                        assert_eq!(sut.pop_max(), model.pop_last());
+                    }
+                }
+            }
+        }
+
+        assert!(sut.inorder().into_iter().eq(model.iter()));
+    }
+
+    #[test]
+    fn test_iter_empty() {
+        let sut = BinaryTree::<u32>::new();
+
+        assert_eq!(sut.iter().next(), None);
+        assert_eq!(sut.inorder_iter().next(), None);
+        assert_eq!(sut.inorder_iter().next_back(), None);
+        assert_eq!(sut.postorder_iter().next(), None);
+        assert_eq!(sut.level_order_iter().next(), None);
+        assert!(sut.inorder().is_empty());
+    }
+
+    #[test]
+    fn test_lazy_traversals() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(11);
+        sut.insert(9);
+        sut.insert(12);
+        sut.insert(7);
+
+        assert!(sut.inorder_iter().eq([7, 8, 9, 10, 11, 12].iter()));
+        assert!(sut.preorder_iter().eq([10, 8, 7, 9, 11, 12].iter()));
+        assert!(sut.postorder_iter().eq([7, 9, 8, 12, 11, 10].iter()));
+        assert!(sut.level_order_iter().eq([10, 8, 11, 7, 9, 12].iter()));
+        assert!(sut.inorder_iter().rev().eq([12, 11, 10, 9, 8, 7].iter()));
+    }
+
+    #[test]
+    fn test_inorder_iter_double_ended() {
+        let mut rng = thread_rng();
+        let mut sut = BinaryTree::new();
+        let mut model = BTreeSet::new();
+
+        for _ in 0..200 {
+            let value = rng.gen_range(0, 1000);
+            sut.insert(value);
+            model.insert(value);
+        }
+
+        for _ in 0..20 {
+            let mut iter = sut.inorder_iter();
+            let mut model_iter = model.iter();
+
+            for _ in 0..model.len() + 2 {
+                if rng.gen() {
+                    assert_eq!(iter.next(), model_iter.next());
+                } else {
+                    assert_eq!(iter.next_back(), model_iter.next_back());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_tree_traversals() {
+        // A list shaped tree must not overflow the stack while iterating
+        let mut sut = BinaryTree::new();
+        for value in 0..20_000 {
+            sut.insert(value);
+        }
+
+        assert_eq!(sut.inorder_iter().count(), 20_000);
+        assert_eq!(sut.inorder_iter().next_back(), Some(&19_999));
+        assert_eq!(sut.postorder_iter().next(), Some(&19_999));
+        assert_eq!(sut.level_order_iter().last(), Some(&19_999));
+    }
+
+    #[test]
+    fn test_levels() {
+        let mut sut = BinaryTree::new();
+        assert!(sut.levels().is_empty());
+        assert!(sut.zigzag_order().is_empty());
+        assert!(sut.left_view().is_empty());
+        assert!(sut.right_view().is_empty());
+        assert_eq!(sut.width(), 0);
+
+        for value in [50, 30, 70, 20, 40, 60, 80, 35, 65].iter() {
+            sut.insert(*value);
+        }
+
+        assert_eq!(
+            sut.levels(),
+            vec![
+                vec![&50],
+                vec![&30, &70],
+                vec![&20, &40, &60, &80],
+                vec![&35, &65]
+            ]
+        );
+        assert_eq!(
+            sut.level_order(),
+            vec![&50, &30, &70, &20, &40, &60, &80, &35, &65]
+        );
+        assert_eq!(
+            sut.zigzag_order(),
+            vec![&50, &70, &30, &20, &40, &60, &80, &65, &35]
+        );
+        assert_eq!(sut.left_view(), vec![&50, &30, &20, &35]);
+        assert_eq!(sut.right_view(), vec![&50, &70, &80, &65]);
+        assert_eq!(sut.width(), 4);
+    }
+
+    #[test]
+    fn test_depth_of() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.depth_of(&1), None);
+
+        for value in [50, 30, 70, 20, 40, 35].iter() {
+            sut.insert(*value);
+        }
+
+        for (depth, level) in sut.levels().iter().enumerate() {
+            for value in level {
+                assert_eq!(sut.depth_of(value), Some(depth));
+            }
+        }
+        assert_eq!(sut.depth_of(&36), None);
+    }
+
+    #[test]
+    fn test_len() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.len(), 0);
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(11);
+        sut.insert(11);
+        assert_eq!(sut.len(), 3);
+        sut.remove(&8);
+        sut.remove(&8);
+        assert_eq!(sut.len(), 2);
+        sut.pop_min();
+        sut.pop_max();
+        sut.pop_max();
+        assert_eq!(sut.len(), 0);
+        sut.insert(1);
+        sut.clear();
+        assert_eq!(sut.len(), 0);
+    }
+
+    #[test]
+    fn test_shape() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.height(), 0);
+        assert!(sut.is_balanced());
+        assert!(sut.is_complete());
+        assert!(sut.is_perfect());
+
+        for value in [50, 30, 70, 20, 40, 60, 80].iter() {
+            sut.insert(*value);
+        }
+        assert_eq!(sut.height(), 3);
+        assert!(sut.is_balanced());
+        assert!(sut.is_complete());
+        assert!(sut.is_perfect());
+
+        sut.insert(10);
+        assert!(sut.is_balanced());
+        assert!(sut.is_complete());
+        assert!(!sut.is_perfect());
+
+        // A gap on the last level
+        sut.insert(45);
+        assert!(sut.is_balanced());
+        assert!(!sut.is_complete());
+
+        sut.insert(5);
+        assert_eq!(sut.height(), 5);
+        assert!(!sut.is_balanced());
+    }
+
+    #[test]
+    fn test_min_max_floor_ceiling() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.min(), None);
+        assert_eq!(sut.max(), None);
+        assert_eq!(sut.floor(&1), None);
+        assert_eq!(sut.ceiling(&1), None);
+
+        let mut rng = thread_rng();
+        let mut model = BTreeSet::new();
+        for _ in 0..300 {
+            let value = rng.gen_range(0, 1000);
+            sut.insert(value);
+            model.insert(value);
+        }
+
+        assert_eq!(sut.min(), model.iter().next());
+        assert_eq!(sut.max(), model.iter().next_back());
+
+        for value in 0..1000 {
+            assert_eq!(sut.floor(&value), model.range(..=value).next_back());
+            assert_eq!(sut.ceiling(&value), model.range(value..).next());
+        }
+
+        for (k, value) in model.iter().enumerate() {
+            assert_eq!(sut.kth_smallest(k), Some(value));
+        }
+        assert_eq!(sut.kth_smallest(model.len()), None);
+    }
+
+    #[test]
+    fn test_lowest_common_ancestor() {
+        let mut sut = BinaryTree::new();
+        assert_eq!(sut.lowest_common_ancestor(&1, &1), None);
+
+        for value in [50, 30, 70, 20, 40, 60, 80, 35].iter() {
+            sut.insert(*value);
+        }
+
+        assert_eq!(sut.lowest_common_ancestor(&20, &35), Some(&30));
+        assert_eq!(sut.lowest_common_ancestor(&35, &20), Some(&30));
+        assert_eq!(sut.lowest_common_ancestor(&35, &80), Some(&50));
+        assert_eq!(sut.lowest_common_ancestor(&40, &35), Some(&40));
+        assert_eq!(sut.lowest_common_ancestor(&60, &60), Some(&60));
+        assert_eq!(sut.lowest_common_ancestor(&20, &21), None);
+        assert_eq!(sut.lowest_common_ancestor(&1, &2), None);
+    }
+
+    #[test]
+    fn test_deep_tree_queries() {
+        // Sorted input degenerates to a list, none of the queries may recurse
+        let mut sut = BinaryTree::new();
+        for value in 0..20_000 {
+            sut.insert(value);
+        }
+
+        assert_eq!(sut.len(), 20_000);
+        assert_eq!(sut.height(), 20_000);
+        assert!(!sut.is_balanced());
+        assert!(!sut.is_complete());
+        assert!(!sut.is_perfect());
+        assert_eq!(sut.min(), Some(&0));
+        assert_eq!(sut.max(), Some(&19_999));
+        assert_eq!(sut.floor(&30_000), Some(&19_999));
+        assert_eq!(sut.kth_smallest(19_999), Some(&19_999));
+        assert_eq!(sut.lowest_common_ancestor(&19_998, &19_999), Some(&19_998));
+    }
+
+    #[test]
+    fn test_rebalance() {
+        let mut sut = BinaryTree::<u32>::new();
+        sut.rebalance();
+        assert!(sut.is_empty());
+
+        for size in 1..=64u32 {
+            let mut sut = BinaryTree::new();
+            for value in 0..size {
+                sut.insert(value);
+            }
+
+            sut.rebalance();
+            assert_eq!(sut.len(), size as usize);
+            assert_eq!(
+                sut.height(),
+                (size + 1).next_power_of_two().trailing_zeros() as usize
+            );
+            assert!(sut.is_balanced());
+            assert!(sut.inorder().into_iter().copied().eq(0..size));
+        }
+    }
+
+    #[test]
+    fn test_rebalance_random() {
+        let mut rng = thread_rng();
+        let mut values: Vec<u32> = (0..2000).collect();
+        values.shuffle(&mut rng);
+
+        let mut sut = BinaryTree::new();
+        for value in values.iter() {
+            sut.insert(*value);
+        }
+
+        sut.rebalance();
+        assert_eq!(sut.height(), 11);
+        assert!(sut.is_balanced());
+        assert!(sut.inorder().into_iter().copied().eq(0..2000));
+    }
+
+    #[test]
+    fn test_rebalance_deep_tree() {
+        let mut sut = BinaryTree::new();
+        for value in 0..20_000 {
+            sut.insert(value);
+        }
+
+        sut.rebalance();
+        assert_eq!(sut.height(), 15);
+        assert!(sut.contains(19_999));
+        assert!(sut.inorder().into_iter().copied().eq(0..20_000));
+    }
+
+    #[test]
+    #[should_panic(expected = "alpha must be in 0.5..1.0, got 1")]
+    fn test_scapegoat_invalid_alpha() {
+        BinaryTree::<u32>::with_scapegoat(1.0);
+    }
+
+    #[test]
+    fn test_scapegoat_sorted_insert() {
+        for alpha in [0.5, 0.6, 0.75, 0.9].iter() {
+            let mut sut = BinaryTree::with_scapegoat(*alpha);
+            for value in 0..2000 {
+                sut.insert(value);
+                assert!(sut.height() <= depth_limit(*alpha, sut.len()) + 1);
+            }
+
+            assert_eq!(sut.len(), 2000);
+            assert!(sut.inorder().into_iter().copied().eq(0..2000));
+        }
+    }
+
+    #[test]
+    fn test_scapegoat_random_model() {
+        let mut rng = thread_rng();
+        let mut sut = BinaryTree::with_scapegoat(0.7);
+        let mut model = BTreeSet::new();
+
+        for _ in 0..5000 {
+            let value = rng.gen_range(0, 500);
+            match rng.gen_range(0, 4) {
+                0 => assert_eq!(sut.take(&value), model.take(&value)),
+                1 => assert_eq!(sut.pop_min(), model.pop_first()),
+                _ => assert_eq!(sut.insert(value), model.insert(value)),
+            }
+        }
+
+        assert_eq!(sut.len(), model.len());
+        assert!(sut.inorder().into_iter().eq(model.iter()));
+        assert!(sut.height() <= depth_limit(0.7, sut.max_len) + 2);
+    }
+
+    fn tree_of(values: &[i32]) -> BinaryTree<i32> {
+        let mut tree = BinaryTree::new();
+        for value in values {
+            tree.insert(*value);
+        }
+        tree
+    }
+
+    #[test]
+    fn test_same_shape_and_mirror() {
+        let sut = tree_of(&[10, 5, 15, 3, 7, 20]);
+        let mut mirrored = tree_of(&[10, 5, 15, 3, 7, 20]);
+        mirrored.mirror();
+
+        assert!(!sut.same_shape(&tree_of(&[2, 1, 4, 0, 5])));
+        assert!(sut.same_shape(&tree_of(&[3, 1, 5, 0, 2, 6])));
+        assert!(!sut.same_shape(&mirrored));
+        assert!(mirrored.is_mirror_of(&sut));
+        assert!(sut.is_mirror_of(&mirrored));
+        assert_eq!(mirrored.preorder(), vec![&10, &15, &20, &5, &7, &3]);
+
+        mirrored.mirror();
+        assert!(mirrored.same_shape(&sut));
+        assert_eq!(mirrored.preorder(), sut.preorder());
+
+        let empty = BinaryTree::<i32>::new();
+        assert!(empty.same_shape(&BinaryTree::<char>::new()));
+        assert!(empty.is_mirror_of(&BinaryTree::new()));
+        assert!(!empty.same_shape(&sut));
+    }
+
+    #[test]
+    fn test_is_subtree_of() {
+        let sut = tree_of(&[10, 5, 15, 3, 7, 20]);
+
+        assert!(BinaryTree::new().is_subtree_of(&sut));
+        assert!(sut.is_subtree_of(&sut));
+        assert!(tree_of(&[5, 3, 7]).is_subtree_of(&sut));
+        assert!(tree_of(&[15, 20]).is_subtree_of(&sut));
+        assert!(tree_of(&[20]).is_subtree_of(&sut));
+        assert!(!tree_of(&[5, 3]).is_subtree_of(&sut));
+        assert!(!tree_of(&[15]).is_subtree_of(&sut));
+        assert!(!sut.is_subtree_of(&tree_of(&[5, 3, 7])));
+    }
+
+    #[test]
+    fn test_structure_round_trip() {
+        let mut values: Vec<i32> = (0..500).collect();
+        values.shuffle(&mut thread_rng());
+        let sut = tree_of(&values);
+
+        let structure = sut.serialize_structure();
+        assert_eq!(structure.len(), 2 * sut.len() + 1);
+
+        let copy =
+            BinaryTree::deserialize_structure(structure.into_iter().map(|value| value.copied()))
+                .unwrap();
+        assert_eq!(copy.len(), sut.len());
+        assert!(copy.same_shape(&sut));
+        assert_eq!(copy.preorder(), sut.preorder());
+
+        let empty = BinaryTree::<i32>::new();
+        assert_eq!(empty.serialize_structure(), vec![None]);
+        assert!(BinaryTree::<i32>::deserialize_structure(vec![None])
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_invalid_structure() {
+        assert_eq!(
+            BinaryTree::<i32>::deserialize_structure(vec![]).err(),
+            Some(StructureError::Incomplete)
+        );
+        assert_eq!(
+            BinaryTree::deserialize_structure(vec![Some(2), Some(1), None, None]).err(),
+            Some(StructureError::Incomplete)
+        );
+        assert_eq!(
+            BinaryTree::deserialize_structure(vec![Some(1), None, None, None]).err(),
+            Some(StructureError::Trailing)
+        );
+        assert_eq!(
+            BinaryTree::<i32>::deserialize_structure(vec![None, None]).err(),
+            Some(StructureError::Trailing)
+        );
+    }
+
+    #[test]
+    fn test_paths_to_leaves() {
+        let sut = tree_of(&[10, 5, 15, 3, 7, 20]);
+
+        assert_eq!(
+            sut.paths_to_leaves(),
+            vec![vec![&10, &5, &3], vec![&10, &5, &7], vec![&10, &15, &20]]
+        );
+        assert_eq!(tree_of(&[1]).paths_to_leaves(), vec![vec![&1]]);
+        assert!(BinaryTree::<i32>::new().paths_to_leaves().is_empty());
+    }
+
+    #[test]
+    fn test_map() {
+        let sut = tree_of(&[10, 5, 15, 3, 7, 20]);
+        let mut visited = Vec::new();
+
+        let doubled = sut.map(|value| {
+            visited.push(*value);
+            value * 2
+        });
+
+        assert_eq!(visited, vec![10, 5, 3, 7, 15, 20]);
+        assert_eq!(doubled.len(), sut.len());
+        assert!(doubled.same_shape(&sut));
+        assert!(doubled.contains(14));
+        assert_eq!(doubled.inorder(), vec![&6, &10, &14, &20, &30, &40]);
+    }
+
+    #[test]
+    fn test_deep_tree_structure() {
+        let values: Vec<i32> = (0..20_000).collect();
+        let sut = tree_of(&values);
+        let mut copy = sut.map(|value| *value);
+
+        assert!(copy.same_shape(&sut));
+        assert!(sut.is_subtree_of(&copy));
+        assert_eq!(sut.paths_to_leaves()[0].len(), 20_000);
+
+        copy.mirror();
+        assert!(copy.is_mirror_of(&sut));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(9);
+        assert_eq!(sut.metrics().visited_nodes(), 3);
+
+        sut.reset_metrics();
+        sut.insert(7);
+        assert_eq!(sut.metrics().visited_nodes(), 2);
+    }
 }
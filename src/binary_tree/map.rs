@@ -0,0 +1,611 @@
+//! An ordered map on top of the binary tree.
+//!
+//! The `BinaryTreeMap` stores its entries in the same nodes as the
+//! [`BinaryTree`](super::BinaryTree), ordered by key. It does not rebalance,
+//! so the shape depends on the insertion order.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use super::{Link, Node};
+use crate::key_value::{after_start, before_end, KeyValue};
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+
+/// An ordered map that keeps its entries in a binary search tree.
+pub struct BinaryTreeMap<K, V> {
+    head: Link<KeyValue<K, V>>,
+    len: usize,
+}
+
+/// A view into a single entry of a map, which is either vacant or occupied.
+/// Instances are created by [`BinaryTreeMap::entry()`].
+pub enum Entry<'a, K: Ord, V> {
+    Vacant(VacantEntry<'a, K, V>),
+    Occupied(OccupiedEntry<'a, K, V>),
+}
+
+/// A view into an entry whose key is not part of the map.
+pub struct VacantEntry<'a, K: Ord, V> {
+    key: K,
+    link: &'a mut Link<KeyValue<K, V>>,
+    len: &'a mut usize,
+}
+
+/// A view into an entry whose key is part of the map.
+pub struct OccupiedEntry<'a, K: Ord, V> {
+    /// Always holds the node of the entry.
+    link: &'a mut Link<KeyValue<K, V>>,
+    len: &'a mut usize,
+}
+
+impl<K: Ord, V> BinaryTreeMap<K, V> {
+    /// Creates a new and empty `BinaryTreeMap`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let map = BinaryTreeMap::<u32, ()>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        BinaryTreeMap { head: None, len: 0 }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a key value pair into the map. If the key was already present its value
+    /// is replaced and the old value is returned. The key is not updated.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), Some("a"));
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+            Entry::Occupied(mut entry) => Some(entry.insert(value)),
+        }
+    }
+
+    /// Returns a reference to the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current = self.head.as_deref();
+
+        while let Some(node) = current {
+            match key.cmp(&node.value.key) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return Some(&node.value.value),
+                Ordering::Greater => current = node.right.as_deref(),
+            }
+        }
+
+        None
+    }
+
+    /// Returns a mutable reference to the value stored for `key`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// map.insert("retries", 1);
+    ///
+    /// if let Some(retries) = map.get_mut(&"retries") {
+    ///     *retries += 2;
+    /// }
+    /// assert_eq!(map.get(&"retries"), Some(&3));
+    /// ```
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current = &mut self.head;
+
+        while let Some(node) = current {
+            match key.cmp(&node.value.key) {
+                Ordering::Less => current = &mut node.left,
+                Ordering::Equal => return Some(&mut node.value.value),
+                Ordering::Greater => current = &mut node.right,
+            }
+        }
+
+        None
+    }
+
+    /// Checks if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` from the map and returns its value, if it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let taken = Node::take_by(&mut self.head, |pair| key.cmp(&pair.key))?;
+        self.len -= 1;
+
+        Some(taken.value)
+    }
+
+    /// Returns the entry of `key` for in-place manipulation. The tree is only searched once,
+    /// no matter whether the entry gets read, updated or inserted.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// for word in "a b a c a".split(' ') {
+    ///     *map.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&3));
+    /// assert_eq!(map.get(&"c"), Some(&1));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let len = &mut self.len;
+        let mut link = &mut self.head;
+
+        loop {
+            match link.as_ref().map(|node| key.cmp(&node.value.key)) {
+                None => return Entry::Vacant(VacantEntry { key, link, len }),
+                Some(Ordering::Equal) => return Entry::Occupied(OccupiedEntry { link, len }),
+                Some(Ordering::Less) => link = &mut link.as_mut().unwrap().left,
+                Some(Ordering::Greater) => link = &mut link.as_mut().unwrap().right,
+            }
+        }
+    }
+
+    /// Returns an `Iterator` over the entries of the map, ordered by key.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"a"), (&2, &"b")]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(&self.head);
+        iter
+    }
+
+    /// Returns an `Iterator` over the entries of the map with mutable values, ordered by key.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// map.insert(2, 20);
+    /// map.insert(1, 10);
+    ///
+    /// for (_, value) in map.iter_mut() {
+    ///     *value += 1;
+    /// }
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &11), (&2, &21)]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        let mut iter = IterMut { stack: Vec::new() };
+        iter.push_left(&mut self.head);
+        iter
+    }
+
+    /// Returns an `Iterator` over the entries whose keys lie in `range`, ordered by key.
+    /// Subtrees outside of the range are skipped.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let map = (0..10).map(|key| (key, key * 10)).collect::<BinaryTreeMap<_, _>>();
+    ///
+    /// assert_eq!(
+    ///     map.range(3..6).collect::<Vec<_>>(),
+    ///     vec![(&3, &30), (&4, &40), (&5, &50)]
+    /// );
+    /// assert_eq!(map.range(8..).count(), 2);
+    /// ```
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let mut stack = Vec::new();
+        let mut current = self.head.as_deref();
+
+        // Push the path to the first key in range, nodes before the start are left out
+        while let Some(node) = current {
+            if after_start(range.start_bound(), &node.value.key) {
+                stack.push(node);
+                current = node.left.as_deref();
+            } else {
+                current = node.right.as_deref();
+            }
+        }
+
+        Range {
+            iter: Iter { stack },
+            range,
+        }
+    }
+}
+
+impl<'a, K: Ord, V> Entry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Vacant(entry) => entry.key(),
+            Entry::Occupied(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant and returns a mutable reference to the value.
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        self.or_insert_with(|| default)
+    }
+
+    /// Inserts the result of `default` if the entry is vacant and returns a mutable reference
+    /// to the value. `default` is only called for a vacant entry.
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Vacant(entry) => entry.insert(default()),
+            Entry::Occupied(entry) => entry.into_mut(),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::map::BinaryTreeMap;
+    /// let mut map = BinaryTreeMap::new();
+    /// map.entry("a").and_modify(|value| *value += 1).or_insert(1);
+    /// map.entry("a").and_modify(|value| *value += 1).or_insert(1);
+    ///
+    /// assert_eq!(map.get(&"a"), Some(&2));
+    /// ```
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(entry) = &mut self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K: Ord, V: Default> Entry<'a, K, V> {
+    /// Inserts the default value if the entry is vacant and returns a mutable reference
+    /// to the value.
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+impl<'a, K: Ord, V> VacantEntry<'a, K, V> {
+    /// Returns the key that would be used for inserting.
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Takes the key back out of the entry.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Inserts the value with the key of the entry and returns a mutable reference to it.
+    pub fn insert(self, value: V) -> &'a mut V {
+        *self.len += 1;
+        *self.link = Some(Box::new(Node::new(KeyValue {
+            key: self.key,
+            value,
+        })));
+
+        &mut self.link.as_mut().unwrap().value.value
+    }
+}
+
+impl<'a, K: Ord, V> OccupiedEntry<'a, K, V> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &K {
+        &self.link.as_ref().unwrap().value.key
+    }
+
+    /// Returns a reference to the value of the entry.
+    pub fn get(&self) -> &V {
+        &self.link.as_ref().unwrap().value.value
+    }
+
+    /// Returns a mutable reference to the value of the entry.
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.link.as_mut().unwrap().value.value
+    }
+
+    /// Turns the entry into a mutable reference to its value, bound to the lifetime of the map.
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.link.as_mut().unwrap().value.value
+    }
+
+    /// Replaces the value of the entry and returns the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Removes the entry from the map and returns its key and value.
+    pub fn remove_entry(self) -> (K, V) {
+        *self.len -= 1;
+        let pair = Node::take(self.link).unwrap();
+
+        (pair.key, pair.value)
+    }
+
+    /// Removes the entry from the map and returns its value.
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+}
+
+impl<K: Ord, V> Default for BinaryTreeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> Drop for BinaryTreeMap<K, V> {
+    /// The map doesn't rebalance, so like the tree it frees its nodes iteratively.
+    fn drop(&mut self) {
+        Node::drop_tree(self.head.take());
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for BinaryTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for BinaryTreeMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An in-order Iterator over the entries of a `BinaryTreeMap`.
+/// Instances are created by [`BinaryTreeMap::iter()`].
+pub struct Iter<'a, K, V> {
+    stack: Vec<&'a Node<KeyValue<K, V>>>,
+}
+
+impl<'a, K, V> Iter<'a, K, V> {
+    fn push_left(&mut self, mut link: &'a Link<KeyValue<K, V>>) {
+        while let Some(node) = link {
+            self.stack.push(node);
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(&node.right);
+
+        Some((&node.value.key, &node.value.value))
+    }
+}
+
+/// An in-order Iterator over the entries of a `BinaryTreeMap` with mutable values.
+/// Every node is split up when it is pushed, as the right subtree is still to be visited
+/// after its value got handed out. Instances are created by [`BinaryTreeMap::iter_mut()`].
+pub struct IterMut<'a, K, V> {
+    stack: Vec<SplitNode<'a, K, V>>,
+}
+
+/// The key, the value and the right subtree of a node, borrowed separately.
+type SplitNode<'a, K, V> = (&'a K, &'a mut V, &'a mut Link<KeyValue<K, V>>);
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    fn push_left(&mut self, mut link: &'a mut Link<KeyValue<K, V>>) {
+        while let Some(node) = link {
+            let Node { left, right, value } = &mut **node;
+            self.stack.push((&value.key, &mut value.value, right));
+            link = left;
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value, right) = self.stack.pop()?;
+        self.push_left(right);
+
+        Some((key, value))
+    }
+}
+
+/// An in-order Iterator over the entries of a `BinaryTreeMap` within a range of keys.
+/// Instances are created by [`BinaryTreeMap::range()`].
+pub struct Range<'a, K, V, R> {
+    iter: Iter<'a, K, V>,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for Range<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.iter.next()?;
+
+        if before_end(self.range.end_bound(), key) {
+            Some((key, value))
+        } else {
+            // Every following key is behind the end as well
+            self.iter.stack.clear();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+
+    fn random_pairs(count: u32) -> Vec<(u32, i64)> {
+        let mut rng = thread_rng();
+        let mut keys: Vec<u32> = (0..count).collect();
+        keys.shuffle(&mut rng);
+
+        keys.into_iter()
+            .map(|key| (key, rng.gen_range(-1000, 1000)))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = BinaryTreeMap::new();
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(3, "three"), None);
+        assert_eq!(map.insert(2, "zwei"), Some("two"));
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&2), Some(&"zwei"));
+        assert!(map.contains_key(&1));
+        assert_eq!(map.remove(&2), Some("zwei"));
+        assert_eq!(map.remove(&2), None);
+        assert!(!map.contains_key(&2));
+        assert_eq!(map.len(), 2);
+        assert_eq!(format!("{:?}", map), "{1: \"one\", 3: \"three\"}");
+    }
+
+    #[test]
+    fn test_entry() {
+        let mut map = BinaryTreeMap::new();
+
+        match map.entry(5) {
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.key(), &5);
+                assert_eq!(*entry.insert(50), 50);
+            }
+            Entry::Occupied(_) => panic!("5 is not in the map yet"),
+        }
+
+        match map.entry(5) {
+            Entry::Occupied(mut entry) => {
+                assert_eq!(entry.key(), &5);
+                assert_eq!(entry.get(), &50);
+                assert_eq!(entry.insert(51), 50);
+            }
+            Entry::Vacant(_) => panic!("5 was inserted"),
+        }
+
+        *map.entry(3).or_default() += 30;
+        assert_eq!(map.entry(3).or_insert_with(|| panic!("3 is present")), &30);
+        assert_eq!(map.len(), 2);
+
+        match map.entry(5) {
+            Entry::Occupied(entry) => assert_eq!(entry.remove_entry(), (5, 51)),
+            Entry::Vacant(_) => panic!("5 was inserted"),
+        }
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get(&5), None);
+        assert_eq!(map.get(&3), Some(&30));
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let pairs = random_pairs(500);
+        let mut map = pairs.iter().cloned().collect::<BinaryTreeMap<_, _>>();
+        let mut btree = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+
+        for ((key, value), (btree_key, btree_value)) in map.iter_mut().zip(btree.iter_mut()) {
+            assert_eq!(key, btree_key);
+            *value *= 2;
+            *btree_value *= 2;
+        }
+
+        assert!(map.iter().eq(btree.iter()));
+        assert_eq!(BinaryTreeMap::<u32, ()>::new().iter_mut().next(), None);
+    }
+
+    #[test]
+    fn test_drop_deep_map() {
+        // Sorted keys degenerate the map to a list, dropping it may not recurse
+        let map = (0..20_000)
+            .map(|key| (key, key))
+            .collect::<BinaryTreeMap<_, _>>();
+        assert_eq!(map.len(), 20_000);
+        assert_eq!(map.get(&19_999), Some(&19_999));
+    }
+
+    #[test]
+    fn test_against_btree_map() {
+        let mut rng = thread_rng();
+        let mut map = BinaryTreeMap::new();
+        let mut btree = BTreeMap::new();
+
+        for _ in 0..5000 {
+            let key = rng.gen_range(0, 300);
+            match rng.gen_range(0, 4) {
+                0 => assert_eq!(map.remove(&key), btree.remove(&key)),
+                1 => {
+                    *map.entry(key).or_insert(0) += 1;
+                    *btree.entry(key).or_insert(0) += 1;
+                }
+                _ => assert_eq!(map.insert(key, key * 7), btree.insert(key, key * 7)),
+            }
+
+            assert_eq!(map.get(&key), btree.get(&key));
+        }
+
+        assert_eq!(map.len(), btree.len());
+        assert!(map.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_range_against_btree_map() {
+        let pairs = random_pairs(1000);
+        let map = pairs.iter().cloned().collect::<BinaryTreeMap<_, _>>();
+        let btree = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+        let mut rng = thread_rng();
+
+        for _ in 0..300 {
+            let start = rng.gen_range(0, 1100);
+            let end = rng.gen_range(start, 1100);
+
+            assert!(map.range(start..end).eq(btree.range(start..end)));
+            assert!(map.range(start..=end).eq(btree.range(start..=end)));
+            assert!(map.range(..end).eq(btree.range(..end)));
+            assert!(map.range(start..).eq(btree.range(start..)));
+            // A BTreeMap rejects an empty range excluding both bounds
+            if start < end {
+                let bounds = (Bound::Excluded(start), Bound::Excluded(end));
+                assert!(map.range(bounds).eq(btree.range(bounds)));
+            }
+        }
+
+        assert!(map.range(..).eq(btree.iter()));
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = map.range(7..3).count();
+        assert_eq!(reversed, 0);
+    }
+}
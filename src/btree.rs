@@ -0,0 +1,914 @@
+//! A B+tree map.
+//!
+//! Every node of a B+tree holds many keys next to each other, so a lookup
+//! touches few nodes and scans keys within cache lines instead of chasing one
+//! pointer per key. All entries live in the leaves. The inner nodes only hold
+//! copies of keys that route the search, and the leaves are linked in key order,
+//! so a range scan walks from leaf to leaf without going up again.
+//!
+//! The nodes are stored in one arena and refer to each other by index, which
+//! allows the leaves to point at their successor.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::mem::replace;
+use std::ops::{Bound, RangeBounds};
+
+/// The node order used by [`BPlusTreeMap::new()`].
+pub const DEFAULT_ORDER: usize = 32;
+
+/// An ordered map stored in a B+tree. Inner nodes have at most `order` children and
+/// every node holds at most `order - 1` keys. All nodes except the root are at least
+/// half full.
+pub struct BPlusTreeMap<K, V> {
+    nodes: Vec<Node<K, V>>,
+    /// Indices of nodes that got merged away, reused before the arena grows.
+    free: Vec<usize>,
+    root: usize,
+    order: usize,
+    len: usize,
+}
+
+enum Node<K, V> {
+    /// `children[i]` holds the keys in `keys[i - 1]..keys[i]`.
+    Inner { keys: Vec<K>, children: Vec<usize> },
+    /// `next` is the leaf holding the following keys.
+    Leaf {
+        keys: Vec<K>,
+        values: Vec<V>,
+        next: Option<usize>,
+    },
+}
+
+impl<K, V> Node<K, V> {
+    fn empty_leaf() -> Self {
+        Node::Leaf {
+            keys: Vec::new(),
+            values: Vec::new(),
+            next: None,
+        }
+    }
+
+    fn keys(&self) -> &Vec<K> {
+        match self {
+            Node::Inner { keys, .. } | Node::Leaf { keys, .. } => keys,
+        }
+    }
+}
+
+impl<K: Ord + Clone, V> BPlusTreeMap<K, V> {
+    /// Creates a new and empty `BPlusTreeMap` of the [`DEFAULT_ORDER`].
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let map = BPlusTreeMap::<u32, ()>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        BPlusTreeMap::with_order(DEFAULT_ORDER)
+    }
+
+    /// Creates a new and empty `BPlusTreeMap` whose inner nodes have at most `order` children.
+    ///
+    /// Panics if `order` is less than 3.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let mut map = BPlusTreeMap::with_order(4);
+    /// for key in 0..100 {
+    ///     map.insert(key, key * 2);
+    /// }
+    /// assert_eq!(map.get(&21), Some(&42));
+    /// ```
+    pub fn with_order(order: usize) -> Self {
+        assert!(order >= 3, "the order has to be at least 3, got {}", order);
+
+        BPlusTreeMap {
+            nodes: vec![Node::empty_leaf()],
+            free: Vec::new(),
+            root: 0,
+            order,
+            len: 0,
+        }
+    }
+
+    /// Builds a map from entries sorted by key in `O(n)`, filling every node nearly up.
+    ///
+    /// Panics if the keys are not strictly ascending or if `order` is less than 3.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let map = BPlusTreeMap::from_sorted_iter((0..1000).map(|key| (key, key)), 8);
+    /// assert_eq!(map.len(), 1000);
+    /// assert_eq!(map.range(500..503).count(), 3);
+    /// ```
+    pub fn from_sorted_iter<I: IntoIterator<Item = (K, V)>>(iter: I, order: usize) -> Self {
+        let mut map = BPlusTreeMap::with_order(order);
+        let (keys, values): (Vec<K>, Vec<V>) = iter.into_iter().unzip();
+
+        assert!(
+            keys.windows(2).all(|pair| pair[0] < pair[1]),
+            "the keys have to be strictly ascending"
+        );
+
+        if keys.is_empty() {
+            return map;
+        }
+
+        map.nodes.clear();
+        map.len = keys.len();
+
+        // Every level is a list of (smallest key of the subtree, node index)
+        let mut level = Vec::new();
+        let mut keys = keys.into_iter();
+        let mut values = values.into_iter();
+
+        for size in chunk_sizes(map.len, order - 1) {
+            let leaf_keys: Vec<K> = keys.by_ref().take(size).collect();
+            let leaf_values: Vec<V> = values.by_ref().take(size).collect();
+            let id = map.nodes.len();
+
+            if id > 0 {
+                if let Node::Leaf { next, .. } = &mut map.nodes[id - 1] {
+                    *next = Some(id);
+                }
+            }
+
+            level.push((leaf_keys[0].clone(), id));
+            map.nodes.push(Node::Leaf {
+                keys: leaf_keys,
+                values: leaf_values,
+                next: None,
+            });
+        }
+
+        while level.len() > 1 {
+            let mut upper_level = Vec::new();
+            let mut entries = level.into_iter();
+
+            for size in chunk_sizes(entries.len(), order) {
+                let group: Vec<(K, usize)> = entries.by_ref().take(size).collect();
+                let smallest = group[0].0.clone();
+                let (mut keys, children): (Vec<K>, Vec<usize>) = group.into_iter().unzip();
+                // The first child needs no key in front of it
+                keys.remove(0);
+
+                upper_level.push((smallest, map.nodes.len()));
+                map.nodes.push(Node::Inner { keys, children });
+            }
+
+            level = upper_level;
+        }
+
+        map.root = level[0].1;
+        map
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of levels of the tree. A map with a single leaf has a height of 1.
+    pub fn height(&self) -> usize {
+        let mut height = 1;
+        let mut current = self.root;
+
+        while let Node::Inner { children, .. } = &self.nodes[current] {
+            current = children[0];
+            height += 1;
+        }
+
+        height
+    }
+
+    /// The minimal number of keys in every node except the root.
+    fn min_keys(&self) -> usize {
+        (self.order - 1) / 2
+    }
+
+    /// Returns the leaf that holds `key` if it's present, or would hold it.
+    fn find_leaf(&self, key: &K) -> usize {
+        let mut current = self.root;
+
+        while let Node::Inner { keys, children } = &self.nodes[current] {
+            current = children[child_index(keys, key)];
+        }
+
+        current
+    }
+
+    /// Returns a reference to the value stored for `key`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let mut map = BPlusTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match &self.nodes[self.find_leaf(key)] {
+            Node::Leaf { keys, values, .. } => keys.binary_search(key).ok().map(|i| &values[i]),
+            Node::Inner { .. } => unreachable!("find_leaf always ends in a leaf"),
+        }
+    }
+
+    /// Returns a mutable reference to the value stored for `key`.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let leaf = self.find_leaf(key);
+
+        match &mut self.nodes[leaf] {
+            Node::Leaf { keys, values, .. } => {
+                keys.binary_search(key).ok().map(move |i| &mut values[i])
+            }
+            Node::Inner { .. } => unreachable!("find_leaf always ends in a leaf"),
+        }
+    }
+
+    /// Checks if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Inserts a key value pair into the map. If the key was already present its value
+    /// is replaced and the old value is returned. Full nodes on the way are split, which
+    /// may add a new root.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let mut map = BPlusTreeMap::new();
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), Some("a"));
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let (old_value, split) = self.insert_below(self.root, key, value);
+
+        if let Some((separator, right)) = split {
+            let left = self.root;
+            self.root = self.add_node(Node::Inner {
+                keys: vec![separator],
+                children: vec![left, right],
+            });
+        }
+
+        if old_value.is_none() {
+            self.len += 1;
+        }
+
+        old_value
+    }
+
+    /// Inserts below `id`. Returns the replaced value and, if `id` had to be split, the key
+    /// separating both halves together with the new right half.
+    fn insert_below(&mut self, id: usize, key: K, value: V) -> (Option<V>, Option<(K, usize)>) {
+        let child = match &mut self.nodes[id] {
+            Node::Leaf { keys, values, .. } => {
+                match keys.binary_search(&key) {
+                    Ok(i) => return (Some(replace(&mut values[i], value)), None),
+                    Err(i) => {
+                        keys.insert(i, key);
+                        values.insert(i, value);
+                    }
+                }
+
+                return (None, self.split_if_full(id));
+            }
+            Node::Inner { keys, children } => children[child_index(keys, &key)],
+        };
+
+        let (old_value, split) = self.insert_below(child, key, value);
+
+        if let Some((separator, right)) = split {
+            if let Node::Inner { keys, children } = &mut self.nodes[id] {
+                let i = child_index(keys, &separator);
+                keys.insert(i, separator);
+                children.insert(i + 1, right);
+            }
+
+            return (old_value, self.split_if_full(id));
+        }
+
+        (old_value, None)
+    }
+
+    /// Splits a node holding more than `order - 1` keys into two halves. Returns the key
+    /// separating them and the new right half.
+    fn split_if_full(&mut self, id: usize) -> Option<(K, usize)> {
+        if self.nodes[id].keys().len() < self.order {
+            return None;
+        }
+
+        let right_id = self.next_id();
+
+        let (separator, right) = match &mut self.nodes[id] {
+            Node::Leaf { keys, values, next } => {
+                let mid = keys.len() / 2;
+                let right_keys = keys.split_off(mid);
+                let right_values = values.split_off(mid);
+
+                // Leaves keep all keys, the separator is a copy
+                let separator = right_keys[0].clone();
+                let right_next = next.replace(right_id);
+
+                (
+                    separator,
+                    Node::Leaf {
+                        keys: right_keys,
+                        values: right_values,
+                        next: right_next,
+                    },
+                )
+            }
+            Node::Inner { keys, children } => {
+                let mid = keys.len() / 2;
+                let mut right_keys = keys.split_off(mid);
+                let right_children = children.split_off(mid + 1);
+
+                // Inner nodes hand the separator up
+                let separator = right_keys.remove(0);
+
+                (
+                    separator,
+                    Node::Inner {
+                        keys: right_keys,
+                        children: right_children,
+                    },
+                )
+            }
+        };
+
+        let added = self.add_node(right);
+        debug_assert_eq!(added, right_id);
+
+        Some((separator, right_id))
+    }
+
+    /// The index the next added node will get.
+    fn next_id(&self) -> usize {
+        self.free.last().copied().unwrap_or(self.nodes.len())
+    }
+
+    fn add_node(&mut self, node: Node<K, V>) -> usize {
+        match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    /// Moves a node out of the arena, leaving its slot free.
+    fn remove_node(&mut self, id: usize) -> Node<K, V> {
+        self.free.push(id);
+        replace(&mut self.nodes[id], Node::empty_leaf())
+    }
+
+    /// Removes `key` from the map and returns its value, if it was present. Nodes that
+    /// become less than half full borrow an entry from a sibling or get merged with it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let mut map = BPlusTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.remove_below(self.root, key)?;
+        self.len -= 1;
+
+        // A root without keys has a single child, which takes its place
+        if let Node::Inner { keys, children } = &self.nodes[self.root] {
+            if keys.is_empty() {
+                let child = children[0];
+                self.remove_node(self.root);
+                self.root = child;
+            }
+        }
+
+        Some(value)
+    }
+
+    fn remove_below(&mut self, id: usize, key: &K) -> Option<V> {
+        let (index, child) = match &mut self.nodes[id] {
+            Node::Leaf { keys, values, .. } => {
+                let i = keys.binary_search(key).ok()?;
+                keys.remove(i);
+                return Some(values.remove(i));
+            }
+            Node::Inner { keys, children } => {
+                let index = child_index(keys, key);
+                (index, children[index])
+            }
+        };
+
+        let value = self.remove_below(child, key)?;
+
+        if self.nodes[child].keys().len() < self.min_keys() {
+            self.rebalance_child(id, index);
+        }
+
+        Some(value)
+    }
+
+    /// Refills the child at `index` of the inner node `parent`, which lost its last spare key.
+    fn rebalance_child(&mut self, parent: usize, index: usize) {
+        let children = match &self.nodes[parent] {
+            Node::Inner { children, .. } => children.clone(),
+            Node::Leaf { .. } => unreachable!("only inner nodes have children"),
+        };
+
+        let has_spare = |map: &Self, id: usize| map.nodes[id].keys().len() > map.min_keys();
+
+        if index > 0 && has_spare(self, children[index - 1]) {
+            self.borrow_from_left(parent, index);
+        } else if index + 1 < children.len() && has_spare(self, children[index + 1]) {
+            self.borrow_from_right(parent, index);
+        } else if index > 0 {
+            self.merge_children(parent, index - 1);
+        } else {
+            self.merge_children(parent, index);
+        }
+    }
+
+    /// Moves the last entry of the left sibling to the front of the child at `index`.
+    fn borrow_from_left(&mut self, parent: usize, index: usize) {
+        let (left_id, child_id) = match &self.nodes[parent] {
+            Node::Inner { children, .. } => (children[index - 1], children[index]),
+            Node::Leaf { .. } => unreachable!("only inner nodes have children"),
+        };
+
+        let mut left = self.take_in_place(left_id);
+        let mut child = self.take_in_place(child_id);
+        let separator = self.separator_mut(parent, index - 1);
+
+        match (&mut left, &mut child) {
+            (
+                Node::Leaf {
+                    keys: left_keys,
+                    values: left_values,
+                    ..
+                },
+                Node::Leaf { keys, values, .. },
+            ) => {
+                keys.insert(0, left_keys.pop().unwrap());
+                values.insert(0, left_values.pop().unwrap());
+                *separator = keys[0].clone();
+            }
+            (
+                Node::Inner {
+                    keys: left_keys,
+                    children: left_children,
+                },
+                Node::Inner { keys, children },
+            ) => {
+                // The separator comes down, the last key of the sibling goes up
+                let down = replace(separator, left_keys.pop().unwrap());
+                keys.insert(0, down);
+                children.insert(0, left_children.pop().unwrap());
+            }
+            _ => unreachable!("siblings are on the same level"),
+        }
+
+        self.nodes[left_id] = left;
+        self.nodes[child_id] = child;
+    }
+
+    /// Moves the first entry of the right sibling to the back of the child at `index`.
+    fn borrow_from_right(&mut self, parent: usize, index: usize) {
+        let (child_id, right_id) = match &self.nodes[parent] {
+            Node::Inner { children, .. } => (children[index], children[index + 1]),
+            Node::Leaf { .. } => unreachable!("only inner nodes have children"),
+        };
+
+        let mut child = self.take_in_place(child_id);
+        let mut right = self.take_in_place(right_id);
+        let separator = self.separator_mut(parent, index);
+
+        match (&mut child, &mut right) {
+            (
+                Node::Leaf { keys, values, .. },
+                Node::Leaf {
+                    keys: right_keys,
+                    values: right_values,
+                    ..
+                },
+            ) => {
+                keys.push(right_keys.remove(0));
+                values.push(right_values.remove(0));
+                *separator = right_keys[0].clone();
+            }
+            (
+                Node::Inner { keys, children },
+                Node::Inner {
+                    keys: right_keys,
+                    children: right_children,
+                },
+            ) => {
+                let down = replace(separator, right_keys.remove(0));
+                keys.push(down);
+                children.push(right_children.remove(0));
+            }
+            _ => unreachable!("siblings are on the same level"),
+        }
+
+        self.nodes[child_id] = child;
+        self.nodes[right_id] = right;
+    }
+
+    /// Merges the children at `index` and `index + 1` of `parent` into the left one.
+    fn merge_children(&mut self, parent: usize, index: usize) {
+        let (separator, right_id) = match &mut self.nodes[parent] {
+            Node::Inner { keys, children } => (keys.remove(index), children.remove(index + 1)),
+            Node::Leaf { .. } => unreachable!("only inner nodes have children"),
+        };
+        let left_id = match &self.nodes[parent] {
+            Node::Inner { children, .. } => children[index],
+            Node::Leaf { .. } => unreachable!("only inner nodes have children"),
+        };
+
+        let right = self.remove_node(right_id);
+
+        match (&mut self.nodes[left_id], right) {
+            (
+                Node::Leaf { keys, values, next },
+                Node::Leaf {
+                    keys: right_keys,
+                    values: right_values,
+                    next: right_next,
+                },
+            ) => {
+                keys.extend(right_keys);
+                values.extend(right_values);
+                *next = right_next;
+            }
+            (
+                Node::Inner { keys, children },
+                Node::Inner {
+                    keys: right_keys,
+                    children: right_children,
+                },
+            ) => {
+                keys.push(separator);
+                keys.extend(right_keys);
+                children.extend(right_children);
+            }
+            _ => unreachable!("siblings are on the same level"),
+        }
+    }
+
+    /// Moves a node out of the arena while it is changed together with another one.
+    fn take_in_place(&mut self, id: usize) -> Node<K, V> {
+        replace(&mut self.nodes[id], Node::empty_leaf())
+    }
+
+    fn separator_mut(&mut self, parent: usize, index: usize) -> &mut K {
+        match &mut self.nodes[parent] {
+            Node::Inner { keys, .. } => &mut keys[index],
+            Node::Leaf { .. } => unreachable!("only inner nodes have separators"),
+        }
+    }
+
+    /// Returns an `Iterator` over the entries of the map, ordered by key.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let mut map = BPlusTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// assert_eq!(map.iter().collect::<Vec<_>>(), vec![(&1, &"a"), (&2, &"b")]);
+    /// ```
+    pub fn iter(&self) -> Range<'_, K, V, std::ops::RangeFull> {
+        self.range(..)
+    }
+
+    /// Returns a lazy `Iterator` over the entries whose keys lie in `range`, ordered by key.
+    /// Only the first leaf is searched from the root, the iterator then follows the links
+    /// between the leaves.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::btree::BPlusTreeMap;
+    /// let map = (0..100).map(|key| (key, key * 10)).collect::<BPlusTreeMap<_, _>>();
+    ///
+    /// assert_eq!(
+    ///     map.range(30..33).collect::<Vec<_>>(),
+    ///     vec![(&30, &300), (&31, &310), (&32, &320)]
+    /// );
+    /// assert_eq!(map.range(95..).count(), 5);
+    /// ```
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let mut current = self.root;
+
+        while let Node::Inner { keys, children } = &self.nodes[current] {
+            let index = match range.start_bound() {
+                Bound::Included(start) | Bound::Excluded(start) => child_index(keys, start),
+                Bound::Unbounded => 0,
+            };
+            current = children[index];
+        }
+
+        let index = match range.start_bound() {
+            Bound::Included(start) => self.nodes[current]
+                .keys()
+                .partition_point(|key| key < start),
+            Bound::Excluded(start) => self.nodes[current]
+                .keys()
+                .partition_point(|key| key <= start),
+            Bound::Unbounded => 0,
+        };
+
+        Range {
+            nodes: &self.nodes,
+            leaf: Some(current),
+            index,
+            range,
+        }
+    }
+}
+
+/// The index of the child of an inner node that holds `key`. Keys equal to a separator
+/// belong to the right of it.
+fn child_index<K: Ord>(keys: &[K], key: &K) -> usize {
+    keys.partition_point(|separator| separator.cmp(key) != Ordering::Greater)
+}
+
+/// Splits `count` items into as few groups of at most `capacity` as possible, with sizes
+/// differing by at most one, so no group ends up less than half full.
+fn chunk_sizes(count: usize, capacity: usize) -> impl Iterator<Item = usize> {
+    let groups = count.div_ceil(capacity);
+    let (size, larger) = (count / groups, count % groups);
+
+    (0..groups).map(move |group| if group < larger { size + 1 } else { size })
+}
+
+impl<K: Ord + Clone, V> Default for BPlusTreeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord + Clone, V> FromIterator<(K, V)> for BPlusTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl<K: Ord + Clone + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for BPlusTreeMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// A lazy Iterator over the entries of a `BPlusTreeMap` within a range of keys, walking
+/// along the linked leaves. Instances are created by [`BPlusTreeMap::range()`] and
+/// [`BPlusTreeMap::iter()`].
+pub struct Range<'a, K, V, R> {
+    nodes: &'a [Node<K, V>],
+    leaf: Option<usize>,
+    index: usize,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for Range<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (keys, values, next) = match &self.nodes[self.leaf?] {
+                Node::Leaf { keys, values, next } => (keys, values, next),
+                Node::Inner { .. } => unreachable!("only leaves are linked"),
+            };
+
+            if self.index < keys.len() {
+                let key = &keys[self.index];
+                let in_range = match self.range.end_bound() {
+                    Bound::Included(end) => key <= end,
+                    Bound::Excluded(end) => key < end,
+                    Bound::Unbounded => true,
+                };
+
+                if !in_range {
+                    self.leaf = None;
+                    return None;
+                }
+
+                self.index += 1;
+                return Some((key, &values[self.index - 1]));
+            }
+
+            self.leaf = *next;
+            self.index = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::seq::SliceRandom;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeMap;
+
+    /// Checks the key counts, the order of all keys, that all leaves are on the same level
+    /// and that the leaf links visit every leaf in order. Returns the number of entries.
+    fn check<K: Ord + Clone + std::fmt::Debug, V>(map: &BPlusTreeMap<K, V>) -> usize {
+        let mut leaves = Vec::new();
+        check_node(map, map.root, None, None, 1, &mut leaves, true);
+
+        for pair in leaves.windows(2) {
+            match &map.nodes[pair[0]] {
+                Node::Leaf { next, .. } => assert_eq!(*next, Some(pair[1])),
+                Node::Inner { .. } => panic!("leaves contain an inner node"),
+            }
+        }
+
+        let count = leaves
+            .iter()
+            .map(|leaf| map.nodes[*leaf].keys().len())
+            .sum();
+        assert_eq!(count, map.len());
+        count
+    }
+
+    fn check_node<K: Ord + Clone + std::fmt::Debug, V>(
+        map: &BPlusTreeMap<K, V>,
+        id: usize,
+        lower: Option<&K>,
+        upper: Option<&K>,
+        depth: usize,
+        leaves: &mut Vec<usize>,
+        is_root: bool,
+    ) {
+        let keys = map.nodes[id].keys();
+
+        assert!(keys.len() < map.order);
+        if !is_root {
+            assert!(keys.len() >= map.min_keys());
+        }
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(keys
+            .iter()
+            .all(|key| lower.is_none_or(|lower| key >= lower)));
+        assert!(keys.iter().all(|key| upper.is_none_or(|upper| key < upper)));
+
+        match &map.nodes[id] {
+            Node::Leaf { values, .. } => {
+                assert_eq!(keys.len(), values.len());
+                assert_eq!(depth, map.height());
+                leaves.push(id);
+            }
+            Node::Inner { keys, children } => {
+                assert_eq!(children.len(), keys.len() + 1);
+
+                for (i, child) in children.iter().enumerate() {
+                    let lower = if i == 0 { lower } else { Some(&keys[i - 1]) };
+                    let upper = keys.get(i).or(upper);
+                    check_node(map, *child, lower, upper, depth + 1, leaves, false);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = BPlusTreeMap::with_order(3);
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(2, "zwei"), Some("two"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&"zwei"));
+        *map.get_mut(&1).unwrap() = "eins";
+        assert_eq!(map.remove(&1), Some("eins"));
+        assert_eq!(map.remove(&1), None);
+        assert!(!map.contains_key(&1));
+        assert_eq!(format!("{:?}", map), "{2: \"zwei\"}");
+    }
+
+    #[test]
+    fn test_against_btree_map() {
+        for order in [3, 4, 5, 8, 33].iter() {
+            let mut rng = thread_rng();
+            let mut map = BPlusTreeMap::with_order(*order);
+            let mut btree = BTreeMap::new();
+
+            for _ in 0..5000 {
+                let key = rng.gen_range(0, 500);
+                if rng.gen_range(0, 3) == 0 {
+                    assert_eq!(map.remove(&key), btree.remove(&key));
+                } else {
+                    assert_eq!(map.insert(key, key * 3), btree.insert(key, key * 3));
+                }
+
+                assert_eq!(map.get(&key), btree.get(&key));
+            }
+
+            assert_eq!(check(&map), btree.len());
+            assert!(map.iter().eq(btree.iter()));
+
+            // Emptying the map shrinks it back to a single leaf
+            let mut keys: Vec<_> = btree.keys().copied().collect();
+            keys.shuffle(&mut rng);
+            for key in keys {
+                assert_eq!(map.remove(&key), btree.remove(&key));
+            }
+            assert_eq!(check(&map), 0);
+            assert_eq!(map.height(), 1);
+        }
+    }
+
+    #[test]
+    fn test_nodes_are_reused() {
+        let mut map = BPlusTreeMap::with_order(4);
+        for round in 0..10 {
+            for key in 0..1000 {
+                map.insert(key, round);
+            }
+            for key in 0..1000 {
+                map.remove(&key);
+            }
+        }
+
+        // Merged nodes are kept for later, the arena doesn't grow with every round
+        assert!(map.nodes.len() < 1000);
+    }
+
+    #[test]
+    fn test_from_sorted_iter() {
+        for order in [3, 4, 7, 32].iter() {
+            for count in [0, 1, 2, 3, 10, 99, 1000].iter() {
+                let odd = (0..*count).map(|key| key * 2 + 1);
+                let mut map =
+                    BPlusTreeMap::from_sorted_iter(odd.clone().map(|key| (key, ())), *order);
+                assert_eq!(check(&map), *count);
+                assert!(map.iter().map(|(key, _)| *key).eq(odd));
+
+                // The loaded tree has to stay usable
+                map.insert(0, ());
+                map.insert(*count, ());
+                map.remove(&(count / 2 * 2 + 1));
+                check(&map);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the keys have to be strictly ascending")]
+    fn test_from_unsorted_iter() {
+        BPlusTreeMap::from_sorted_iter(vec![(2, ()), (1, ())], 4);
+    }
+
+    #[test]
+    fn test_range_against_btree_map() {
+        let mut keys: Vec<u32> = (0..2000).map(|key| key * 2).collect();
+        keys.shuffle(&mut thread_rng());
+
+        let map = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<BPlusTreeMap<_, _>>();
+        let btree = keys
+            .iter()
+            .map(|key| (*key, ()))
+            .collect::<BTreeMap<_, _>>();
+        let mut rng = thread_rng();
+
+        for _ in 0..300 {
+            let start = rng.gen_range(0, 4100);
+            let end = rng.gen_range(start, 4100);
+
+            assert!(map.range(start..end).eq(btree.range(start..end)));
+            assert!(map.range(start..=end).eq(btree.range(start..=end)));
+            assert!(map.range(..end).eq(btree.range(..end)));
+            assert!(map.range(start..).eq(btree.range(start..)));
+
+            if start < end {
+                let bounds = (Bound::Excluded(start), Bound::Excluded(end));
+                assert!(map.range(bounds).eq(btree.range(bounds)));
+            }
+        }
+
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = map.range(7..3).count();
+        assert_eq!(reversed, 0);
+    }
+}
@@ -0,0 +1,1084 @@
+//! A doubly linked list with cursors.
+//!
+//! Every node knows its predecessor and successor, so a cursor that already points at a
+//! node can insert or remove next to it in O(1) without walking from the head.
+//!
+//! The nodes are allocated one by one and only ever reached through raw pointers. The list
+//! owns all of them and frees them again when they are removed or the list is dropped.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use std::fmt;
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ptr::NonNull;
+
+/// A doubly linked list build from Nodes, holding its first and last node and its length.
+pub struct DList<T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    /// The list owns the nodes as if it held them in boxes.
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+/// A Link between Nodes.
+type Link<T> = Option<NonNull<Node<T>>>;
+
+/// A Node in a doubly linked list which holds a pointer to the previous and next Node as
+/// well as a value.
+struct Node<T> {
+    prev: Link<T>,
+    next: Link<T>,
+    value: T,
+}
+
+// The list owns its nodes like a `Box` would, so it may cross threads whenever `T` may.
+unsafe impl<T: Send> Send for DList<T> {}
+unsafe impl<T: Sync> Sync for DList<T> {}
+
+/// A double-ended Iterator over the elements of a `DList`.
+/// Instances are created by [`DList::iter()`].
+pub struct Iter<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a Node<T>>,
+}
+
+/// A double-ended Iterator over mutable references to the elements of a `DList`.
+/// Instances are created by [`DList::iter_mut()`].
+pub struct IterMut<'a, T> {
+    head: Link<T>,
+    tail: Link<T>,
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+/// An owning double-ended Iterator over the elements of a `DList`.
+/// Instances are created by [`DList::into_iter()`].
+pub struct IntoIter<T> {
+    list: DList<T>,
+}
+
+/// A cursor pointing at an element of a `DList`, or at the "ghost" position between the
+/// last and the first element. Moving past either end lands on the ghost, moving on from
+/// the ghost wraps around to the other end.
+/// Instances are created by [`DList::cursor_front()`] and [`DList::cursor_back()`].
+pub struct Cursor<'a, T> {
+    list: &'a DList<T>,
+    current: Link<T>,
+    /// The index of the current element, `list.len` on the ghost.
+    index: usize,
+}
+
+/// A cursor like [`Cursor`] which can also change the list around its position.
+/// Instances are created by [`DList::cursor_front_mut()`] and [`DList::cursor_back_mut()`].
+pub struct CursorMut<'a, T> {
+    list: &'a mut DList<T>,
+    current: Link<T>,
+    /// The index of the current element, `list.len` on the ghost.
+    index: usize,
+}
+
+impl<T> DList<T> {
+    /// Creates a new and empty `DList`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let list = DList::<()>::new();
+    /// assert!(list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        DList {
+            head: None,
+            tail: None,
+            len: 0,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all elements from the list.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+
+    /// Returns a reference to the first element.
+    pub fn front(&self) -> Option<&T> {
+        // SAFETY: the list owns the node and `&self` keeps it alive and unchanged
+        self.head.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the first element.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: the list owns the node and `&mut self` rules out any other access
+        self.head.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns a reference to the last element.
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: as in `front`
+        self.tail.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the last element.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `front_mut`
+        self.tail.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Adds an element to the front of the list in O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list = DList::new();
+    /// list.push_front(2);
+    /// list.push_front(1);
+    ///
+    /// assert_eq!(list.front(), Some(&1));
+    /// assert_eq!(list.back(), Some(&2));
+    /// ```
+    pub fn push_front(&mut self, value: T) {
+        self.link_between(None, self.head, value);
+    }
+
+    /// Adds an element to the back of the list in O(1).
+    pub fn push_back(&mut self, value: T) {
+        self.link_between(self.tail, None, value);
+    }
+
+    /// Removes the first element and returns it in O(1).
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.map(|node| self.unlink(node))
+    }
+
+    /// Removes the last element and returns it in O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 2].into_iter().collect();
+    ///
+    /// assert_eq!(list.pop_back(), Some(2));
+    /// assert_eq!(list.pop_back(), Some(1));
+    /// assert_eq!(list.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.map(|node| self.unlink(node))
+    }
+
+    /// Moves all elements of `other` to the back of this list in O(1), leaving `other` empty.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 2].into_iter().collect();
+    /// let mut other: DList<_> = vec![3, 4].into_iter().collect();
+    /// list.append(&mut other);
+    ///
+    /// assert!(other.is_empty());
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
+    pub fn append(&mut self, other: &mut DList<T>) {
+        self.splice_between(self.tail, None, mem::take(other));
+    }
+
+    /// Splits the list at index `at` and returns everything from there on. This walks from
+    /// the closer end of the list to `at`.
+    ///
+    /// Panics if `at > len`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 2, 3, 4].into_iter().collect();
+    /// let back = list.split_off(1);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1]);
+    /// assert_eq!(back.iter().collect::<Vec<_>>(), vec![&2, &3, &4]);
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> DList<T> {
+        assert!(
+            at <= self.len,
+            "cannot split off at {}, the length is {}",
+            at,
+            self.len
+        );
+
+        let first_of_back = if at == self.len {
+            None
+        } else if at <= self.len / 2 {
+            let mut cursor = self.cursor_front();
+            for _ in 0..at {
+                cursor.move_next();
+            }
+            cursor.current
+        } else {
+            let mut cursor = self.cursor_back();
+            for _ in at..self.len - 1 {
+                cursor.move_prev();
+            }
+            cursor.current
+        };
+
+        self.split_at_node(first_of_back, self.len - at)
+    }
+
+    /// Returns a double-ended `Iterator` over the elements of the list.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let list: DList<_> = vec![1, 2, 3].into_iter().collect();
+    ///
+    /// assert_eq!(list.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a double-ended `Iterator` over mutable references to the elements.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
+            tail: self.tail,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a cursor pointing at the first element, or at the ghost if the list is empty.
+    pub fn cursor_front(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            current: self.head,
+            index: 0,
+        }
+    }
+
+    /// Returns a cursor pointing at the last element, or at the ghost if the list is empty.
+    pub fn cursor_back(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self,
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+        }
+    }
+
+    /// Returns a mutable cursor pointing at the first element, or at the ghost if the list
+    /// is empty.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 3].into_iter().collect();
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.insert_after(2);
+    /// cursor.move_next();
+    /// *cursor.current().unwrap() *= 10;
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &20, &3]);
+    /// ```
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.head,
+            index: 0,
+            list: self,
+        }
+    }
+
+    /// Returns a mutable cursor pointing at the last element, or at the ghost if the list
+    /// is empty.
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, T> {
+        CursorMut {
+            current: self.tail,
+            index: self.len.saturating_sub(1),
+            list: self,
+        }
+    }
+
+    /// Allocates a node for `value` and links it between `prev` and `next`, which have to be
+    /// neighbours in this list. `None` stands for the start or the end of the list.
+    fn link_between(&mut self, prev: Link<T>, next: Link<T>, value: T) -> NonNull<Node<T>> {
+        let node = Box::new(Node { prev, next, value });
+        let node = NonNull::from(Box::leak(node));
+
+        // SAFETY: `prev` and `next` are nodes owned by this list
+        unsafe {
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(node),
+                None => self.head = Some(node),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(node),
+                None => self.tail = Some(node),
+            }
+        }
+
+        self.len += 1;
+        node
+    }
+
+    /// Unlinks a node of this list, frees it and returns its value.
+    fn unlink(&mut self, node: NonNull<Node<T>>) -> T {
+        // SAFETY: the node belongs to this list, so it came from `Box::leak` and nothing
+        // else points to it once its neighbours are relinked
+        let node = unsafe { Box::from_raw(node.as_ptr()) };
+
+        unsafe {
+            match node.prev {
+                Some(prev) => (*prev.as_ptr()).next = node.next,
+                None => self.head = node.next,
+            }
+            match node.next {
+                Some(next) => (*next.as_ptr()).prev = node.prev,
+                None => self.tail = node.prev,
+            }
+        }
+
+        self.len -= 1;
+        node.value
+    }
+
+    /// Detaches `first` and all nodes after it, `len` of them, into a new list.
+    fn split_at_node(&mut self, first: Link<T>, len: usize) -> DList<T> {
+        let first = match first {
+            Some(first) => first,
+            None => return DList::new(),
+        };
+
+        // SAFETY: `first` and its predecessor are nodes owned by this list
+        let prev = unsafe { (*first.as_ptr()).prev.take() };
+        match prev {
+            Some(prev) => unsafe { (*prev.as_ptr()).next = None },
+            None => self.head = None,
+        }
+
+        let back = DList {
+            head: Some(first),
+            tail: self.tail,
+            len,
+            marker: PhantomData,
+        };
+
+        self.tail = prev;
+        self.len -= len;
+        back
+    }
+
+    /// Links all nodes of `other` between `prev` and `next`, which have to be neighbours in
+    /// this list.
+    fn splice_between(&mut self, prev: Link<T>, next: Link<T>, mut other: DList<T>) {
+        let (first, last) = match (other.head.take(), other.tail.take()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return,
+        };
+
+        // SAFETY: `first` and `last` now belong to this list, `prev` and `next` already do
+        unsafe {
+            (*first.as_ptr()).prev = prev;
+            (*last.as_ptr()).next = next;
+
+            match prev {
+                Some(prev) => (*prev.as_ptr()).next = Some(first),
+                None => self.head = Some(first),
+            }
+            match next {
+                Some(next) => (*next.as_ptr()).prev = Some(last),
+                None => self.tail = Some(last),
+            }
+        }
+
+        self.len += mem::replace(&mut other.len, 0);
+    }
+}
+
+impl<T: PartialEq> DList<T> {
+    /// Checks if the list contains an element.
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|element| element == value)
+    }
+}
+
+impl<'a, T> Cursor<'a, T> {
+    /// Returns the index of the current element, or `None` on the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Returns the current element, or `None` on the ghost.
+    pub fn current(&self) -> Option<&'a T> {
+        // SAFETY: the cursor borrows the list, so the node stays alive and unchanged
+        self.current.map(|node| unsafe { &(*node.as_ptr()).value })
+    }
+
+    /// Moves to the next element. From the last element it moves to the ghost, from the
+    /// ghost to the first element.
+    pub fn move_next(&mut self) {
+        match self.current {
+            // SAFETY: the current node belongs to the borrowed list
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).next };
+                self.index += 1;
+            }
+            None => {
+                self.current = self.list.head;
+                self.index = 0;
+            }
+        }
+    }
+
+    /// Moves to the previous element. From the first element it moves to the ghost, from
+    /// the ghost to the last element.
+    pub fn move_prev(&mut self) {
+        match self.current {
+            // SAFETY: the current node belongs to the borrowed list
+            Some(node) => {
+                self.current = unsafe { (*node.as_ptr()).prev };
+                self.index = self.index.checked_sub(1).unwrap_or(self.list.len);
+            }
+            None => {
+                self.current = self.list.tail;
+                self.index = self.list.len.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Returns the element the cursor would move to with [`Cursor::move_next()`].
+    pub fn peek_next(&self) -> Option<&'a T> {
+        let mut next = self.clone();
+        next.move_next();
+        next.current()
+    }
+
+    /// Returns the element the cursor would move to with [`Cursor::move_prev()`].
+    pub fn peek_prev(&self) -> Option<&'a T> {
+        let mut prev = self.clone();
+        prev.move_prev();
+        prev.current()
+    }
+}
+
+impl<'a, T> Clone for Cursor<'a, T> {
+    fn clone(&self) -> Self {
+        Cursor {
+            list: self.list,
+            current: self.current,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Returns the index of the current element, or `None` on the ghost.
+    pub fn index(&self) -> Option<usize> {
+        self.current.map(|_| self.index)
+    }
+
+    /// Returns the current element, or `None` on the ghost.
+    pub fn current(&mut self) -> Option<&mut T> {
+        // SAFETY: the cursor holds the only borrow of the list
+        self.current
+            .map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Moves to the next element, see [`Cursor::move_next()`].
+    pub fn move_next(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_next();
+        let (current, index) = (cursor.current, cursor.index);
+
+        self.current = current;
+        self.index = index;
+    }
+
+    /// Moves to the previous element, see [`Cursor::move_prev()`].
+    pub fn move_prev(&mut self) {
+        let mut cursor = self.as_cursor();
+        cursor.move_prev();
+        let (current, index) = (cursor.current, cursor.index);
+
+        self.current = current;
+        self.index = index;
+    }
+
+    /// Returns the element after the current one, see [`Cursor::peek_next()`].
+    pub fn peek_next(&mut self) -> Option<&mut T> {
+        let next = match self.current {
+            // SAFETY: the current node belongs to the borrowed list
+            Some(node) => unsafe { (*node.as_ptr()).next },
+            None => self.list.head,
+        };
+
+        // SAFETY: the cursor holds the only borrow of the list
+        next.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns the element before the current one, see [`Cursor::peek_prev()`].
+    pub fn peek_prev(&mut self) -> Option<&mut T> {
+        let prev = match self.current {
+            // SAFETY: the current node belongs to the borrowed list
+            Some(node) => unsafe { (*node.as_ptr()).prev },
+            None => self.list.tail,
+        };
+
+        // SAFETY: the cursor holds the only borrow of the list
+        prev.map(|node| unsafe { &mut (*node.as_ptr()).value })
+    }
+
+    /// Returns a read-only cursor at the same position, borrowing this one.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        Cursor {
+            list: self.list,
+            current: self.current,
+            index: self.index,
+        }
+    }
+
+    /// The neighbours of the current position, the ghost sits between the last and the first
+    /// element.
+    fn neighbours(&self) -> (Link<T>, Link<T>) {
+        match self.current {
+            // SAFETY: the current node belongs to the borrowed list
+            Some(node) => unsafe { ((*node.as_ptr()).prev, (*node.as_ptr()).next) },
+            None => (self.list.tail, self.list.head),
+        }
+    }
+
+    /// Inserts an element before the current one in O(1). On the ghost the element becomes
+    /// the last one.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![2].into_iter().collect();
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.insert_before(1);
+    /// cursor.move_next();
+    /// cursor.insert_before(3);
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn insert_before(&mut self, value: T) {
+        let (prev, _) = self.neighbours();
+        let next = self.current;
+        self.list.link_between(prev, next, value);
+        self.index += 1;
+    }
+
+    /// Inserts an element after the current one in O(1). On the ghost the element becomes
+    /// the first one.
+    pub fn insert_after(&mut self, value: T) {
+        let (_, next) = self.neighbours();
+        let prev = self.current;
+        self.list.link_between(prev, next, value);
+
+        if self.current.is_none() {
+            self.index += 1;
+        }
+    }
+
+    /// Removes the current element in O(1) and returns it. The cursor moves on to the next
+    /// element, or to the ghost after removing the last one. On the ghost nothing happens.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 2, 3].into_iter().collect();
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.move_next();
+    ///
+    /// assert_eq!(cursor.remove_current(), Some(2));
+    /// assert_eq!(cursor.current(), Some(&mut 3));
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &3]);
+    /// ```
+    pub fn remove_current(&mut self) -> Option<T> {
+        let node = self.current?;
+        let (_, next) = self.neighbours();
+
+        self.current = next;
+        let value = self.list.unlink(node);
+
+        if self.current.is_none() {
+            self.index = self.list.len;
+        }
+
+        Some(value)
+    }
+
+    /// Splits the list before the current element and returns the front part. On the ghost
+    /// the whole list is returned.
+    pub fn split_before(&mut self) -> DList<T> {
+        let back_len = self.list.len - self.index;
+        let back = self.list.split_at_node(self.current, back_len);
+        self.index = 0;
+
+        match self.current {
+            Some(_) => mem::replace(self.list, back),
+            None => mem::take(self.list),
+        }
+    }
+
+    /// Splits the list after the current element and returns the back part. On the ghost
+    /// the whole list is returned.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 2, 3, 4].into_iter().collect();
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.move_next();
+    /// let back = cursor.split_after();
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// assert_eq!(back.iter().collect::<Vec<_>>(), vec![&3, &4]);
+    /// ```
+    pub fn split_after(&mut self) -> DList<T> {
+        match self.current {
+            Some(_) => {
+                let (_, next) = self.neighbours();
+                let back_len = self.list.len - self.index - 1;
+                self.list.split_at_node(next, back_len)
+            }
+            None => {
+                self.index = 0;
+                mem::take(self.list)
+            }
+        }
+    }
+
+    /// Moves all elements of `other` after the current element in O(1). On the ghost they
+    /// are put in front of the first element.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::dlist::DList;
+    /// let mut list: DList<_> = vec![1, 4].into_iter().collect();
+    /// let mut cursor = list.cursor_front_mut();
+    /// cursor.splice_after(vec![2, 3].into_iter().collect());
+    ///
+    /// assert_eq!(list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
+    pub fn splice_after(&mut self, other: DList<T>) {
+        let (_, next) = self.neighbours();
+        let prev = self.current;
+
+        if self.current.is_none() {
+            self.index += other.len;
+        }
+
+        self.list.splice_between(prev, next, other);
+    }
+}
+
+impl<T> Drop for DList<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Default for DList<T> {
+    fn default() -> Self {
+        DList::new()
+    }
+}
+
+impl<T> FromIterator<T> for DList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = DList::new();
+
+        for value in iter {
+            list.push_back(value);
+        }
+
+        list
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: PartialEq> PartialEq for DList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for DList<T> {}
+
+impl<T> IntoIterator for DList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the list into an iterator over the lists values.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a DList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|node| {
+            // SAFETY: the iterator borrows the list, so the node stays alive and unchanged
+            let node = unsafe { &*node.as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            &node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|node| {
+            // SAFETY: as in `next`
+            let node = unsafe { &*node.as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            &node.value
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.head.map(|node| {
+            // SAFETY: the iterator borrows the list mutably and the length check makes sure
+            // no node is handed out twice, from the front and from the back
+            let node = unsafe { &mut *node.as_ptr() };
+            self.len -= 1;
+            self.head = node.next;
+            &mut node.value
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.tail.map(|node| {
+            // SAFETY: as in `next`
+            let node = unsafe { &mut *node.as_ptr() };
+            self.len -= 1;
+            self.tail = node.prev;
+            &mut node.value
+        })
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.list.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.list.len, Some(self.list.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.list.pop_back()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use std::collections::VecDeque;
+
+    /// Checks that both directions of the links agree with each other and the length.
+    fn check<T: PartialEq + fmt::Debug>(list: &DList<T>) {
+        let forward: Vec<_> = list.iter().collect();
+        let mut backward: Vec<_> = list.iter().rev().collect();
+        backward.reverse();
+
+        assert_eq!(forward.len(), list.len());
+        assert_eq!(forward, backward);
+        assert_eq!(list.front(), forward.first().copied());
+        assert_eq!(list.back(), forward.last().copied());
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let mut sut = DList::new();
+        sut.push_back(2);
+        sut.push_front(1);
+        sut.push_back(3);
+        check(&sut);
+        assert_eq!(sut.len(), 3);
+
+        *sut.front_mut().unwrap() = 0;
+        *sut.back_mut().unwrap() = 4;
+        assert_eq!(sut.pop_front(), Some(0));
+        assert_eq!(sut.pop_back(), Some(4));
+        assert_eq!(sut.pop_back(), Some(2));
+        assert_eq!(sut.pop_front(), None);
+        assert!(sut.is_empty());
+        check(&sut);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let mut sut: DList<_> = (0..6).collect();
+        for value in sut.iter_mut() {
+            *value *= 2;
+        }
+
+        let mut iter = sut.iter();
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&10));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&8));
+        assert_eq!(iter.next_back(), Some(&6));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let mut iter_mut = sut.iter_mut();
+        *iter_mut.next_back().unwrap() = 11;
+        assert_eq!(iter_mut.count(), 5);
+
+        let into: Vec<_> = sut.into_iter().rev().collect();
+        assert_eq!(into, vec![11, 8, 6, 4, 2, 0]);
+    }
+
+    #[test]
+    fn test_append_and_split_off() {
+        let mut sut: DList<_> = (0..5).collect();
+        let mut other: DList<_> = (5..8).collect();
+        sut.append(&mut other);
+        check(&sut);
+        check(&other);
+        assert!(sut.iter().copied().eq(0..8));
+
+        sut.append(&mut DList::new());
+        let mut empty = DList::new();
+        empty.append(&mut sut);
+        assert!(sut.is_empty());
+        assert!(empty.iter().copied().eq(0..8));
+
+        for at in 0..=8 {
+            let mut front: DList<_> = (0..8).collect();
+            let back = front.split_off(at);
+            check(&front);
+            check(&back);
+            assert!(front.iter().copied().eq(0..at));
+            assert!(back.iter().copied().eq(at..8));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot split off at 4, the length is 3")]
+    fn test_split_off_out_of_range() {
+        let mut sut: DList<_> = (0..3).collect();
+        sut.split_off(4);
+    }
+
+    #[test]
+    fn test_cursor_moves() {
+        let sut: DList<_> = (0..3).collect();
+        let mut cursor = sut.cursor_front();
+        assert_eq!((cursor.index(), cursor.current()), (Some(0), Some(&0)));
+        assert_eq!(cursor.peek_prev(), None);
+
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (Some(2), Some(&2)));
+        assert_eq!(cursor.peek_next(), None);
+
+        cursor.move_next();
+        assert_eq!((cursor.index(), cursor.current()), (None, None));
+        assert_eq!(cursor.peek_next(), Some(&0));
+        assert_eq!(cursor.peek_prev(), Some(&2));
+
+        cursor.move_prev();
+        assert_eq!(cursor.index(), Some(2));
+
+        let mut cursor = sut.cursor_back();
+        cursor.move_prev();
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+        cursor.move_prev();
+        assert_eq!(cursor.current(), Some(&2));
+
+        let empty = DList::<u8>::new();
+        let mut cursor = empty.cursor_back();
+        cursor.move_next();
+        cursor.move_prev();
+        assert_eq!(cursor.current(), None);
+    }
+
+    #[test]
+    fn test_cursor_split_and_splice() {
+        let mut sut: DList<_> = (0..6).collect();
+        let mut cursor = sut.cursor_front_mut();
+        cursor.move_next();
+        cursor.move_next();
+
+        let front = cursor.split_before();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 2));
+        let back = cursor.split_after();
+        assert_eq!(cursor.index(), Some(0));
+        assert!(front.iter().copied().eq(0..2));
+        assert!(back.iter().copied().eq(3..6));
+        check(&front);
+        check(&back);
+
+        cursor.splice_after(back);
+        assert_eq!(cursor.index(), Some(0));
+        cursor.move_prev();
+        cursor.splice_after(front);
+        assert_eq!(cursor.index(), None);
+        cursor.move_next();
+        assert_eq!(cursor.index(), Some(0));
+        assert_eq!(cursor.current(), Some(&mut 0));
+        check(&sut);
+        assert_eq!(
+            sut.iter().copied().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4, 5]
+        );
+
+        let mut cursor = sut.cursor_back_mut();
+        cursor.move_next();
+        let all = cursor.split_after();
+        assert_eq!(all.len(), 6);
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn test_cursor_against_vec_deque() {
+        let mut rng = thread_rng();
+        let mut sut = DList::new();
+        let mut model = VecDeque::new();
+        // The model position, `model.len()` stands for the ghost
+        let mut position = 0;
+        let steps = if cfg!(miri) { 300 } else { 3000 };
+
+        for value in 0..steps {
+            let mut cursor = sut.cursor_front_mut();
+            for _ in 0..position {
+                cursor.move_next();
+            }
+
+            match rng.gen_range(0, 7) {
+                0 => {
+                    cursor.insert_before(value);
+                    model.insert(position, value);
+                    position += 1;
+                }
+                1 => {
+                    cursor.insert_after(value);
+                    if position == model.len() {
+                        model.push_front(value);
+                        position += 1;
+                    } else {
+                        model.insert(position + 1, value);
+                    }
+                }
+                2 => {
+                    let expected = if position < model.len() {
+                        model.remove(position)
+                    } else {
+                        None
+                    };
+                    assert_eq!(cursor.remove_current(), expected);
+                }
+                3 => {
+                    cursor.move_next();
+                    position = (position + 1) % (model.len() + 1);
+                }
+                4 => {
+                    let at = rng.gen_range(0, model.len() + 1);
+                    let mut back = sut.split_off(at);
+                    let model_back = model.split_off(at);
+                    assert!(back.iter().eq(model_back.iter()));
+                    sut.append(&mut back);
+                    model.extend(model_back);
+                    continue;
+                }
+                5 if position < model.len() => {
+                    let back = cursor.split_after();
+                    let model_back = model.split_off(position + 1);
+                    assert!(back.iter().eq(model_back.iter()));
+                    cursor.splice_after(back);
+                    model.extend(model_back);
+                }
+                _ => {
+                    assert_eq!(cursor.index(), Some(position).filter(|p| *p < model.len()));
+                    assert_eq!(cursor.current().copied(), model.get(position).copied());
+                }
+            }
+
+            assert_eq!(cursor.index(), Some(position).filter(|p| *p < model.len()));
+        }
+
+        check(&sut);
+        assert!(sut.iter().eq(model.iter()));
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let count = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let sut: DList<_> = (0..count).collect();
+        assert_eq!(sut.len(), count);
+    }
+}
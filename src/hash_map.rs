@@ -39,7 +39,7 @@ where
     ///   buckets should be redistributed.
     fn resize(&mut self) {
         let mut target_size: usize = INITIAL_SIZE;
-        if self.buckets.len() != 0 {
+        if !self.buckets.is_empty() {
             target_size = self.buckets.len() * 2;
         }
 
@@ -79,7 +79,7 @@ where
         let hash = hasher.finish();
 
         // TODO check if the cast is necessary
-        return (hash % self.buckets.capacity() as u64) as usize;
+        (hash % self.buckets.capacity() as u64) as usize
     }
 
     /// Inserts a tuple into the HashMap.
@@ -149,13 +149,10 @@ where
 
         let bucket = self.get_bucket(&key);
 
-        return match &self.buckets[bucket] {
-            Some(vec) => vec
-                .into_iter()
-                .find(|k| k.0 == key)
-                .map_or(None, |tuple| Some(&tuple.1)),
+        match &self.buckets[bucket] {
+            Some(vec) => vec.iter().find(|k| k.0 == key).map(|tuple| &tuple.1),
             None => None,
-        };
+        }
     }
 
     /// Put this here to test my credentials and to create
@@ -166,14 +163,20 @@ where
         }
 
         let bucket = self.get_bucket(&key);
-        return match self.buckets[bucket].take() {
-            Some(vec) => vec
-                .into_iter()
-                .find(|k| k.0 == key)
-                .take()
-                .map_or(None, |tuple| Some(tuple.1)),
+        match self.buckets[bucket].take() {
+            Some(vec) => vec.into_iter().find(|k| k.0 == key).map(|tuple| tuple.1),
             None => None,
-        };
+        }
+    }
+}
+
+impl<K, V> Default for HashMap<K, V>
+where
+    K: Hash + Ord,
+    V: Ord,
+{
+    fn default() -> Self {
+        HashMap::new()
     }
 }
 
@@ -0,0 +1,84 @@
+//! Pieces shared by the ordered maps and sets.
+//!
+//! The maps store a `KeyValue` in the nodes of their tree, which orders the
+//! pairs by key, and check their ranges with `after_start` and `before_end`.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use std::cmp::Ordering;
+use std::ops::Bound;
+
+/// A key value pair stored in a node. Pairs are ordered by their key only.
+#[derive(Clone)]
+pub struct KeyValue<K, V> {
+    pub key: K,
+    pub value: V,
+}
+
+impl<K: Ord, V> PartialEq for KeyValue<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: Ord, V> Eq for KeyValue<K, V> {}
+
+impl<K: Ord, V> PartialOrd for KeyValue<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<K: Ord, V> Ord for KeyValue<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Checks if `key` is not in front of the start bound of a range.
+pub fn after_start<K: Ord>(start: Bound<&K>, key: &K) -> bool {
+    match start {
+        Bound::Included(start) => key >= start,
+        Bound::Excluded(start) => key > start,
+        Bound::Unbounded => true,
+    }
+}
+
+/// Checks if `key` is not behind the end bound of a range.
+pub fn before_end<K: Ord>(end: Bound<&K>, key: &K) -> bool {
+    match end {
+        Bound::Included(end) => key <= end,
+        Bound::Excluded(end) => key < end,
+        Bound::Unbounded => true,
+    }
+}
+
+/// A value which is compared by its number only, so the sets can tell a replaced value
+/// apart from the one they kept.
+#[cfg(test)]
+#[derive(Debug)]
+pub struct Keyed(pub u32, pub &'static str);
+
+#[cfg(test)]
+impl PartialEq for Keyed {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+#[cfg(test)]
+impl Eq for Keyed {}
+
+#[cfg(test)]
+impl PartialOrd for Keyed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(test)]
+impl Ord for Keyed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
+    }
+}
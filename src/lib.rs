@@ -1,7 +1,18 @@
 pub mod binary_tree;
+pub mod btree;
+pub mod dlist;
 pub mod hash_map;
+mod key_value;
 pub mod linked_list;
+pub mod metrics;
+pub mod queue;
+pub mod rb_tree;
+pub mod splay;
+pub mod treap;
+pub mod unrolled_list;
 
 pub mod avl_tree;
 
 pub mod stack;
+
+pub mod visualize;
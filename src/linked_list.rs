@@ -1,24 +1,46 @@
-//! A safe linked list.
+//! A singly linked list.
 //!
 //! The `LinkedList` allows inserting, removing and iterating it's elements.
 //!
+//! Besides the head, the list keeps a pointer to its last node, so pushing to the back
+//! takes O(1). That node is also linked from its predecessor, and a `Box` can't have a
+//! second owner, so the nodes are allocated one by one and only ever reached through raw
+//! pointers. The list owns all of them and frees them again when they are removed or the
+//! list is dropped.
+//!
 //! NOTE: This was written for a learning purpose.
 
+pub mod algorithms;
+
+use std::cmp::Ordering;
 use std::convert::From;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+use std::ptr::NonNull;
 
 /// A linked list build from Nodes. This struct represents a linked list
 /// with a head and it's length.
-#[derive(Debug, Eq, PartialEq)]
 pub struct LinkedList<T> {
     head: Link<T>,
     len: usize,
+    /// The last node, so pushing to the back takes O(1).
+    tail: Link<T>,
+    /// The list owns the nodes as if it held them in boxes.
+    marker: PhantomData<Box<Node<T>>>,
 }
 
-/// A Link between Nodes.
-type Link<T> = Option<Box<Node<T>>>;
+// The list owns its nodes like a `Box` would, so it may cross threads whenever `T` may.
+unsafe impl<T: Send> Send for LinkedList<T> {}
+unsafe impl<T: Sync> Sync for LinkedList<T> {}
+
+/// A Link between Nodes. The nodes are allocated one by one with `Box::leak` and freed
+/// again with `Box::from_raw` once the list lets go of them.
+type Link<T> = Option<NonNull<Node<T>>>;
 
 /// A Node in a linked list which holds a reference to the next Node as well as a value.
-#[derive(Debug, Eq, PartialEq)]
 struct Node<T> {
     next: Link<T>,
     value: T,
@@ -28,21 +50,27 @@ struct Node<T> {
 /// Instances are created by [`LinkedList::iter()`]. See its
 /// documentation for more.
 pub struct Iter<'a, T: 'a> {
-    head: &'a Link<T>,
+    head: Option<&'a Node<T>>,
+    len: usize,
+}
+
+/// An Iterator over mutable references to the elements of a linked list.
+/// Instances are created by [`LinkedList::iter_mut()`]. See its
+/// documentation for more.
+pub struct IterMut<'a, T: 'a> {
+    head: Link<T>,
     len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
 }
 
 /// An owning Iteraror the elements of a linked list.
 /// Instances are created by [`LinkedList::into_iter()`]. See its
 /// documentation for more.
-pub struct IntoIter<T: Eq> {
+pub struct IntoIter<T> {
     list: LinkedList<T>,
 }
 
-impl<T> LinkedList<T>
-where
-    T: Eq + Ord,
-{
+impl<T> LinkedList<T> {
     /// Creates a new and empty `LinkedList`.
     /// # Example
     /// ```rust
@@ -52,7 +80,12 @@ where
     ///```
     #[inline]
     pub fn new() -> Self {
-        LinkedList { head: None, len: 0 }
+        LinkedList {
+            head: None,
+            len: 0,
+            tail: None,
+            marker: PhantomData,
+        }
     }
 
     #[inline]
@@ -60,7 +93,14 @@ where
         self.len == 0
     }
 
-    /// Appends a new element to the list.
+    /// Returns the number of elements in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Appends a new element to the list. Kept for compatibility, it does the same
+    /// as [`LinkedList::push_back()`] and always returns `true`.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::linked_list::LinkedList;
@@ -72,28 +112,121 @@ where
     /// assert!(linked_list.contains(2));
     /// ```
     pub fn append(&mut self, val: T) -> bool {
-        match &mut self.head {
-            Some(first) => {
-                self.len += 1;
-                first.append(val)
-            }
-            None => {
-                self.head = Some(Box::new(Node::new(val)));
-                self.len += 1;
-                true
-            }
-        }
+        self.push_back(val);
+        true
     }
 
-    /// Checks if a `LinkedList` contains a given element.
-    pub fn contains(&self, val: T) -> bool {
-        match &self.head {
-            Some(first) => first.contains(val),
-            None => false,
+    /// Adds an element to the front of the list in O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::new();
+    /// linked_list.push_front(2);
+    /// linked_list.push_front(1);
+    ///
+    /// assert_eq!(linked_list.front(), Some(&1));
+    /// assert_eq!(linked_list.back(), Some(&2));
+    /// ```
+    pub fn push_front(&mut self, val: T) {
+        self.push_front_node(Box::new(Node::new(val)));
+    }
+
+    /// Adds an element to the back of the list in O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::new();
+    /// linked_list.push_back(1);
+    /// linked_list.push_back(2);
+    ///
+    /// assert_eq!(linked_list.front(), Some(&1));
+    /// assert_eq!(linked_list.back(), Some(&2));
+    /// ```
+    pub fn push_back(&mut self, val: T) {
+        self.push_back_node(Box::new(Node::new(val)));
+    }
+
+    /// Removes the last element and returns it. A node doesn't know its predecessor,
+    /// so this walks the whole list and takes O(n).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::new();
+    /// linked_list.push_back(1);
+    /// linked_list.push_back(2);
+    ///
+    /// assert_eq!(linked_list.pop_back(), Some(2));
+    /// assert_eq!(linked_list.pop_back(), Some(1));
+    /// assert_eq!(linked_list.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len <= 1 {
+            return self.pop_front();
+        }
+
+        let mut new_tail = self.head.unwrap();
+        for _ in 0..self.len - 2 {
+            // SAFETY: the list has more than `len - 2` nodes behind the head
+            new_tail = unsafe { (*new_tail.as_ptr()).next.unwrap() };
         }
+
+        // SAFETY: the old tail came from `Box::leak` and the new tail lets go of it here
+        let old_tail = unsafe { Box::from_raw((*new_tail.as_ptr()).next.take().unwrap().as_ptr()) };
+        self.tail = Some(new_tail);
+        self.len -= 1;
+
+        Some(old_tail.value)
     }
 
-    /// Removes an element at the given index from the list.
+    /// Returns a reference to the first element.
+    pub fn front(&self) -> Option<&T> {
+        self.head_node().map(|head| &head.value)
+    }
+
+    /// Returns a mutable reference to the first element.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::new();
+    /// linked_list.push_back(1);
+    /// *linked_list.front_mut().unwrap() = 5;
+    ///
+    /// assert_eq!(linked_list.front(), Some(&5));
+    /// ```
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: `&mut self` rules out any other reference into the list
+        self.head.map(|head| unsafe { &mut (*head.as_ptr()).value })
+    }
+
+    /// Returns a reference to the last element in O(1).
+    pub fn back(&self) -> Option<&T> {
+        // SAFETY: the tail is the last node, which lives as long as `self`
+        self.tail.map(|tail| unsafe { &(*tail.as_ptr()).value })
+    }
+
+    /// Returns a mutable reference to the last element in O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::new();
+    /// linked_list.push_back(1);
+    /// linked_list.push_back(2);
+    /// *linked_list.back_mut().unwrap() = 5;
+    ///
+    /// assert_eq!(linked_list.back(), Some(&5));
+    /// ```
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        // SAFETY: as in `back`, and `&mut self` rules out any other reference into the list
+        self.tail.map(|tail| unsafe { &mut (*tail.as_ptr()).value })
+    }
+
+    /// Returns a reference to the first node.
+    fn head_node(&self) -> Option<&Node<T>> {
+        // SAFETY: the node is owned by the list and lives as long as `self`
+        self.head.map(|head| unsafe { &*head.as_ptr() })
+    }
+
+    /// Removes the element at `index` and returns it, or `None` if `index` is out of range.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::linked_list::LinkedList;
@@ -104,142 +237,386 @@ where
     /// assert!(linked_list.contains(1));
     /// assert!(linked_list.contains(2));
     ///
-    /// linked_list.remove(0);
+    /// assert_eq!(linked_list.remove(0), Some(1));
+    /// assert_eq!(linked_list.remove(1), None);
     ///
     /// assert!(!linked_list.contains(1));
     /// ```
-    pub fn remove(&mut self, index: usize) -> bool {
+    pub fn remove(&mut self, index: usize) -> Option<T> {
         if index >= self.len {
-            false
+            return None;
         } else if index == 0 {
-            let mut old_head = self.head.take().unwrap();
-            if let Some(new) = old_head.next.take() {
-                self.head = Some(new);
-            }
-            self.len -= 1;
-            true
+            return self.pop_front();
+        }
+
+        let before = self.node(index - 1);
+        Some(self.unlink_after(before))
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if `index` is out of range.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(linked_list.get(1), Some(&2));
+    /// assert_eq!(linked_list.get(3), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        } else if index + 1 == self.len {
+            return self.back();
+        }
+
+        self.iter().nth(index)
+    }
+
+    /// Returns a mutable reference to the element at `index`, or `None` if `index` is out
+    /// of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        } else if index + 1 == self.len {
+            return self.back_mut();
+        }
+
+        // SAFETY: `&mut self` rules out any other reference into the list
+        Some(unsafe { &mut (*self.node(index).as_ptr()).value })
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it to the back.
+    ///
+    /// Panics if `index > len`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 3]);
+    /// linked_list.insert(1, 2);
+    /// linked_list.insert(3, 4);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4]);
+    /// ```
+    pub fn insert(&mut self, index: usize, val: T) {
+        assert!(
+            index <= self.len,
+            "insertion index {} is out of range for a list of length {}",
+            index,
+            self.len
+        );
+
+        if index == 0 {
+            self.push_front(val);
+        } else if index == self.len {
+            self.push_back(val);
         } else {
-            match &mut self.head {
-                Some(val) => {
-                    self.len -= 1;
-                    val.remove(index, 0)
-                }
-                None => false,
-            }
+            let before = self.node(index - 1);
+            self.link_after(before, val);
         }
     }
 
-    pub fn is_sorted(&self) -> bool {
-        match &self.head {
-            Some(head) => head.is_sorted(),
-            None => false,
+    /// Swaps the elements at `i` and `j` in one pass.
+    ///
+    /// Panics if `i` or `j` is out of range.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 3]);
+    /// linked_list.swap(0, 2);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let (low, high) = (i.min(j), i.max(j));
+        assert!(
+            high < self.len,
+            "swap index {} is out of range for a list of length {}",
+            high,
+            self.len
+        );
+
+        if low == high {
+            return;
         }
+
+        let first = self.node(low);
+        let mut other = first;
+        for _ in low..high {
+            // SAFETY: the index is in range, so every node up to it has a successor
+            other = unsafe { (*other.as_ptr()).next.unwrap() };
+        }
+
+        // SAFETY: both are different nodes of this list and `&mut self` rules out any other
+        // reference into it
+        unsafe { std::mem::swap(&mut (*first.as_ptr()).value, &mut (*other.as_ptr()).value) };
     }
 
-    /// Why Merge-Sort?
+    /// Shortens the list to its first `len` elements. Nothing happens if the list is not
+    /// longer than that.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 3]);
+    /// linked_list.truncate(1);
     ///
-    /// Quick sort works well for sorting in-place.
-    /// In particular, most of the operations can be defined in terms of swapping pairs of elements
-    /// in an array. To do that, however, you normally "walk" through the array with two pointers
-    /// (or indexes, etc.) One starts at the beginning of the array and the other at the end.
-    /// Both then work their way toward the middle (and you're done with a particular partition step
-    /// when they meet). That's expensive with files, because files are oriented primarily toward reading
-    /// in one direction, from beginning to end. Starting from the end and seeking backwards is usually
-    /// relatively expensive.
+    /// assert_eq!(linked_list.len(), 1);
+    /// assert_eq!(linked_list.back(), Some(&1));
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len {
+            drop(self.split_at_index(len));
+        }
+    }
 
-    /// At least in its simplest incarnation, merge sort is pretty much the opposite.
-    /// The easy way to implement it only requires looking through the data in one direction,
-    /// but involves breaking the data into two separate pieces, sorting the pieces,
-    /// then merging them back together.
+    /// Detaches the nodes from `at` on into a list of their own, `at` has to be at most
+    /// the length.
+    fn split_at_index(&mut self, at: usize) -> LinkedList<T> {
+        if at == 0 {
+            return std::mem::take(self);
+        }
 
-    /// With a linked list, it's easy to take (for example) alternating elements in one linked list,
-    /// and manipulate the links to create two linked lists from those same elements instead.
-    /// With an array, rearranging elements so alternating elements go into separate arrays is easy
-    /// if you're willing to create a copy as big as the original data, but otherwise rather more
-    /// non-trivial.
+        let new_tail = self.node(at - 1);
+        // SAFETY: the new tail is a node of this list
+        let head = unsafe { (*new_tail.as_ptr()).next.take() };
+        let back = LinkedList {
+            head,
+            len: self.len - at,
+            tail: head.and(self.tail),
+            marker: PhantomData,
+        };
 
-    /// Likewise, merging with arrays is easy if you merge elements from the source arrays
-    /// into a new array with the data in order -- but to do it in place without creating a whole
-    /// new copy of the data is a whole different story. With a linked list, merging elements together
-    /// from two source lists into a single target list is trivial -- again, you just manipulate links,
-    /// without copying elements.
+        self.tail = Some(new_tail);
+        self.len = at;
 
-    /// As for using Quicksort to produce the sorted runs for an external merge sort,
-    /// it does work, but it's (decidedly) sub-optimal as a rule. To optimize a merge-sort,
-    /// you normally want to maximize the lengths of each sorted "run" as you produce it.
-    /// If you simply read in the data that will fit in memory, Quicksort it and write it out,
-    /// each run will be restricted to (a little less than) the size of the available memory.
+        back
+    }
 
-    /// You can do quite a bit better than that as a rule though.
-    /// You start by reading in a block of data, but instead of using a Quicksort on it, you build a heap.
-    /// Then, as you write each item out from the heap into the sorted "run" file, you read another item
-    /// in from your input file. If it's larger than the item you just wrote to disk, you insert it into
-    /// your existing heap, and repeat.
+    /// Returns the node at `index`, which has to be in range.
+    fn node(&self, index: usize) -> NonNull<Node<T>> {
+        let mut node = self.head.unwrap();
+        for _ in 0..index {
+            // SAFETY: the index is in range, so every node up to it has a successor
+            node = unsafe { (*node.as_ptr()).next.unwrap() };
+        }
 
-    /// Items that are smaller (i.e., belong before items that have already been written) you keep
-    /// separate, and build into a second heap. When (and only when) your first heap is empty,
-    /// and the second heap has taken over all the memory, you quit writing items to the existing "run" file,
-    /// and start on a new one.
+        node
+    }
 
-    /// Exactly how effective this will be depends on the initial order of the data.
-    /// In the worst case (input sorted in inverse order) it does no good at all. In the best case
-    /// (input already sorted) it lets you "sort" the data in a single run through the input.
-    /// In an average case (input in random order) it lets you approximately double the length of
-    /// each sorted run, which will typically improve speed by around 20-25%
-    /// (though the percentage varies depending on how much larger your data is than the available memory).
-    #[inline]
-    pub fn sort(&mut self) {
-        if self.head.is_none() {
-            return;
+    /// Allocates a node for `val` and links it behind `before`, a node of this list.
+    fn link_after(&mut self, before: NonNull<Node<T>>, val: T) {
+        // SAFETY: `before` is a node of this list
+        let next = unsafe { &mut (*before.as_ptr()).next };
+        let node = Box::new(Node {
+            next: *next,
+            value: val,
+        });
+        let node = NonNull::from(Box::leak(node));
+        *next = Some(node);
+
+        if self.tail == Some(before) {
+            self.tail = Some(node);
         }
+        self.len += 1;
+    }
 
-        let (mut front, mut back) = self.split();
+    /// Unlinks the node behind `before`, which has to exist, frees it and returns its value.
+    fn unlink_after(&mut self, before: NonNull<Node<T>>) -> T {
+        // SAFETY: `before` is a node of this list and has a successor, which came from
+        // `Box::leak` and nothing else points to once `before` skips it
+        let node = unsafe {
+            let next = &mut (*before.as_ptr()).next;
+            let node = Box::from_raw(next.unwrap().as_ptr());
+            *next = node.next;
+            node
+        };
+
+        if node.next.is_none() {
+            self.tail = Some(before);
+        }
+        self.len -= 1;
 
-        if front.len > 1 {
-            front.sort();
+        node.value
+    }
+
+    /// Sorts the list with a comparator function. The sort is a stable, iterative bottom-up
+    /// merge sort which only relinks the nodes, see [`LinkedList::sort()`].
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![2.5, -1.0, 0.5]);
+    /// linked_list.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&-1.0, &0.5, &2.5]);
+    /// ```
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        // `runs[i]` is either empty or holds 2^i sorted elements, which came before all
+        // elements in the lower runs. A list of 2^64 nodes doesn't fit into memory.
+        let mut runs: [LinkedList<T>; 64] = std::array::from_fn(|_| LinkedList::new());
+
+        while let Some(node) = self.pop_front_node() {
+            let mut carry = LinkedList::new();
+            carry.push_back_node(node);
+
+            // Like adding one to a binary number, full runs merge and carry over
+            for run in runs.iter_mut() {
+                if run.is_empty() {
+                    *run = carry;
+                    break;
+                }
+                carry = LinkedList::merge_by(run, &mut carry, &mut compare);
+            }
         }
 
-        if back.len > 1 {
-            back.sort();
+        for run in runs.iter_mut() {
+            let mut later = std::mem::take(self);
+            *self = LinkedList::merge_by(run, &mut later, &mut compare);
         }
+    }
 
-        self.head = Some(Box::new(Node::from(LinkedList::merge(
-            &mut front, &mut back,
-        ))));
+    /// Sorts the list by the key `f` extracts from every element. The sort is stable, so
+    /// elements with equal keys keep their order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec!["ccc", "a", "bb", "d"]);
+    /// linked_list.sort_by_key(|word| word.len());
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&"a", &"d", &"bb", &"ccc"]);
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
     }
 
-    #[inline]
-    fn split(&mut self) -> (LinkedList<T>, LinkedList<T>) {
-        let back = self
-            .head
-            .as_mut()
-            .unwrap()
-            .get_back(self.len / 2, 0)
-            .unwrap();
-        let front = self.head.take().unwrap();
+    /// Checks if `in_order` holds for every pair of neighbouring elements. Like
+    /// [`LinkedList::is_sorted()`] an empty list doesn't count as sorted.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from(vec![3.0, 2.0, 1.0]);
+    ///
+    /// assert!(linked_list.is_sorted_by(|a, b| a >= b));
+    /// assert!(!linked_list.is_sorted_by(|a, b| a <= b));
+    /// ```
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, in_order: F) -> bool {
+        match self.head_node() {
+            Some(head) => head.is_sorted_by(in_order),
+            None => false,
+        }
+    }
+
+    /// Removes consecutive elements for which `same` returns `true`, keeping the first of
+    /// them. `same` gets the later element first, like [`Vec::dedup_by()`].
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same: F) {
+        let mut before = match self.head {
+            Some(head) => head,
+            None => return,
+        };
+
+        // SAFETY: `before` and its successor are different nodes of this list and `&mut self`
+        // rules out any other reference into it
+        while let Some(next) = unsafe { (*before.as_ptr()).next } {
+            if unsafe { same(&mut (*next.as_ptr()).value, &mut (*before.as_ptr()).value) } {
+                self.unlink_after(before);
+            } else {
+                before = next;
+            }
+        }
+    }
 
-        (LinkedList::from(*front), LinkedList::from(*back))
+    /// Removes consecutive elements with the same key, keeping the first of them.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![10, 11, 20, 12, 13]);
+    /// linked_list.dedup_by_key(|value| *value / 10);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&10, &20, &12]);
+    /// ```
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
     }
 
+    /// Merges two lists sorted by `compare` into one by relinking their nodes, leaving both
+    /// empty. On ties the node of `front` comes first, which keeps the sort stable.
     #[inline]
-    fn merge(front: &mut LinkedList<T>, back: &mut LinkedList<T>) -> Self {
-        let mut result: Node<T>;
+    fn merge_by<F>(front: &mut LinkedList<T>, back: &mut LinkedList<T>, compare: &mut F) -> Self
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        let mut result = LinkedList::new();
 
-        if front.head.is_none() {
-            return LinkedList::from(*back.head.take().unwrap());
-        } else if back.head.is_none() {
-            return LinkedList::from(*front.head.take().unwrap());
+        while let (Some(first), Some(second)) = (front.front(), back.front()) {
+            let node = if compare(first, second) != Ordering::Greater {
+                front.pop_front_node()
+            } else {
+                back.pop_front_node()
+            };
+
+            result.push_back_node(node.unwrap());
         }
 
-        if front.head.as_ref().unwrap().value <= back.head.as_ref().unwrap().value {
-            result = Node::new(front.pop_front().unwrap());
-        } else {
-            result = Node::new(back.pop_front().unwrap());
+        // At most one of them has elements left, all larger than the merged ones
+        result.push_back_list(front);
+        result.push_back_list(back);
+
+        result
+    }
+
+    /// Detaches the first node.
+    fn pop_front_node(&mut self) -> Option<Box<Node<T>>> {
+        // SAFETY: the head came from `Box::leak` and the list lets go of it right here
+        let mut old_head = unsafe { Box::from_raw(self.head?.as_ptr()) };
+        self.head = old_head.next.take();
+        self.len -= 1;
+
+        if self.head.is_none() {
+            self.tail = None;
+        }
+
+        Some(old_head)
+    }
+
+    /// Links a single node in front of the first one.
+    fn push_front_node(&mut self, mut node: Box<Node<T>>) {
+        node.next = self.head;
+        let node = NonNull::from(Box::leak(node));
+
+        if self.tail.is_none() {
+            self.tail = Some(node);
         }
+        self.head = Some(node);
+        self.len += 1;
+    }
 
-        result.set_next(Node::from(LinkedList::merge(front, back)));
+    /// Links a single node behind the last one.
+    fn push_back_node(&mut self, mut node: Box<Node<T>>) {
+        node.next = None;
+        let node = Some(NonNull::from(Box::leak(node)));
 
-        LinkedList::from(result)
+        match self.tail {
+            // SAFETY: the tail is the last node of this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = node },
+            None => self.head = node,
+        }
+        self.tail = node;
+        self.len += 1;
+    }
+
+    /// Moves all nodes of `other` behind the last node, leaving `other` empty.
+    fn push_back_list(&mut self, other: &mut LinkedList<T>) {
+        if other.is_empty() {
+            return;
+        }
+
+        match self.tail {
+            // SAFETY: the tail is the last node of this list
+            Some(tail) => unsafe { (*tail.as_ptr()).next = other.head },
+            None => self.head = other.head,
+        }
+        self.tail = other.tail.take();
+        self.len += other.len;
+        other.head = None;
+        other.len = 0;
     }
 
     /// Removes the head and returns it as an Option.
@@ -253,17 +630,7 @@ where
     /// assert_eq!(linked_list.pop_front(), Some(1))
     /// ```
     pub fn pop_front(&mut self) -> Option<T> {
-        if self.len == 0 {
-            None
-        } else {
-            let mut old_head = self.head.take().unwrap();
-            let res = Some(old_head.value);
-            if let Some(next) = old_head.next.take() {
-                self.head = Some(next);
-            }
-            self.len -= 1;
-            res
-        }
+        self.pop_front_node().map(|node| node.value)
     }
 
     /// Returns an `Iterator` over the elements of a list.
@@ -279,12 +646,31 @@ where
     /// ```
     pub fn iter<'a>(&'a self) -> Iter<'a, T> {
         Iter {
-            head: &self.head,
+            head: self.head_node(),
+            len: self.len,
+        }
+    }
+
+    /// Returns an `Iterator` over mutable references to the elements of a list.
+    /// # Example
+    /// ```
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 3]);
+    /// for elem in linked_list.iter_mut() {
+    ///     *elem *= 10;
+    /// }
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&10, &20, &30]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            head: self.head,
             len: self.len,
+            marker: PhantomData,
         }
     }
 
-    /// Reverses the linked list.
+    /// Reverses the list in place in O(n), relinking the nodes without allocating.
     /// # Example
     /// ```rust
     /// use data_structure_with_colin::linked_list::LinkedList;
@@ -294,72 +680,394 @@ where
     /// linked_list.append(3);
     /// linked_list.append(4);
     ///
-    /// let reversed = linked_list.reverse();
+    /// linked_list.reverse();
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+    /// ```
+    pub fn reverse(&mut self) {
+        let mut reversed = None;
+        let mut current = self.head;
+
+        while let Some(node) = current {
+            // SAFETY: every node of this list gets visited once, `&mut self` rules out any
+            // other reference into it
+            unsafe {
+                current = (*node.as_ptr()).next;
+                (*node.as_ptr()).next = reversed;
+            }
+            reversed = Some(node);
+        }
+
+        self.tail = self.head;
+        self.head = reversed;
+    }
+
+    /// Moves the first `k` elements to the back of the list, relinking the nodes. `k` is
+    /// taken modulo the length, so rotating an empty list does nothing.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+    /// linked_list.rotate_left(2);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &1, &2]);
     /// ```
-    pub fn reverse(&mut self) -> Self {
+    pub fn rotate_left(&mut self, k: usize) {
         if self.is_empty() {
-            return LinkedList::new();
+            return;
+        }
+
+        let k = k % self.len;
+        if k == 0 {
+            return;
+        }
+
+        let mut new_tail = self.head.unwrap();
+        for _ in 1..k {
+            // SAFETY: `k` is less than the length, so every node up to the new tail has a
+            // successor
+            new_tail = unsafe { (*new_tail.as_ptr()).next.unwrap() };
+        }
+
+        // SAFETY: `k` is less than the length, so the new tail and the old tail are
+        // different nodes of this list
+        unsafe {
+            let new_head = (*new_tail.as_ptr()).next.take();
+            (*self.tail.unwrap().as_ptr()).next = self.head;
+            self.head = new_head;
+        }
+        self.tail = Some(new_tail);
+    }
+
+    /// Moves the last `k` elements to the front of the list, relinking the nodes. `k` is
+    /// taken modulo the length, so rotating an empty list does nothing.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 3, 4, 5]);
+    /// linked_list.rotate_right(2);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&4, &5, &1, &2, &3]);
+    /// ```
+    pub fn rotate_right(&mut self, k: usize) {
+        if !self.is_empty() {
+            self.rotate_left(self.len - k % self.len);
+        }
+    }
+}
+
+impl<T: Clone> LinkedList<T> {
+    /// Returns a reversed copy of the list and leaves the list itself untouched.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from(vec![1, 2, 3]);
+    /// let reversed = linked_list.reversed();
+    ///
+    /// assert_eq!(reversed.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn reversed(&self) -> Self {
+        let mut reversed = LinkedList::new();
+
+        for value in self.iter() {
+            reversed.push_front(value.clone());
+        }
+
+        reversed
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+    /// Checks if a `LinkedList` contains a given element.
+    pub fn contains(&self, val: T) -> bool {
+        match self.head_node() {
+            Some(first) => first.contains(val),
+            None => false,
+        }
+    }
+
+    /// Removes consecutive equal elements, so a sorted list keeps every element once.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 1, 2, 2, 2, 1]);
+    /// linked_list.dedup();
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&1, &2, &1]);
+    /// ```
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Returns the index of the first element equal to `val`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from(vec![1, 2, 1]);
+    ///
+    /// assert_eq!(linked_list.position(&1), Some(0));
+    /// assert_eq!(linked_list.position(&3), None);
+    /// ```
+    pub fn position(&self, val: &T) -> Option<usize> {
+        self.iter().position(|element| element == val)
+    }
+
+    /// Removes the first element equal to `val` and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 1]);
+    ///
+    /// assert_eq!(linked_list.remove_first(&1), Some(1));
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&2, &1]);
+    /// ```
+    pub fn remove_first(&mut self, val: &T) -> Option<T> {
+        if self.front()? == val {
+            return self.pop_front();
+        }
+
+        let mut before = self.head?;
+        // SAFETY: `before` and its successor are nodes of this list
+        while let Some(next) = unsafe { (*before.as_ptr()).next } {
+            if unsafe { (*next.as_ptr()).value == *val } {
+                return Some(self.unlink_after(before));
+            }
+            before = next;
+        }
+
+        None
+    }
+
+    /// Removes all elements equal to `val` and returns how many there were.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 2, 1, 1]);
+    ///
+    /// assert_eq!(linked_list.remove_all(&1), 3);
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&2]);
+    /// ```
+    pub fn remove_all(&mut self, val: &T) -> usize {
+        let len_before = self.len;
+
+        while self.front() == Some(val) {
+            self.pop_front();
         }
 
-        let mut cur = self.head.take().unwrap();
+        let mut before = match self.head {
+            Some(head) => head,
+            None => return len_before,
+        };
+
+        // SAFETY: `before` and its successor are nodes of this list
+        while let Some(next) = unsafe { (*before.as_ptr()).next } {
+            if unsafe { (*next.as_ptr()).value == *val } {
+                self.unlink_after(before);
+            } else {
+                before = next;
+            }
+        }
+
+        len_before - self.len
+    }
+}
 
-        if cur.next.is_none() {
-            return LinkedList::from(*cur);
+impl<T: Ord> LinkedList<T> {
+    /// Checks if the elements are in ascending order. An empty list doesn't count as sorted.
+    pub fn is_sorted(&self) -> bool {
+        match self.head_node() {
+            Some(head) => head.is_sorted_by(|a, b| a <= b),
+            None => false,
         }
+    }
 
-        let mut next = cur.next.take().unwrap();
+    /// Why Merge-Sort?
+    ///
+    /// Quick sort works well for sorting in-place.
+    /// In particular, most of the operations can be defined in terms of swapping pairs of elements
+    /// in an array. To do that, however, you normally "walk" through the array with two pointers
+    /// (or indexes, etc.) One starts at the beginning of the array and the other at the end.
+    /// Both then work their way toward the middle (and you're done with a particular partition step
+    /// when they meet). That's expensive with files, because files are oriented primarily toward reading
+    /// in one direction, from beginning to end. Starting from the end and seeking backwards is usually
+    /// relatively expensive.
+    ///
+    /// At least in its simplest incarnation, merge sort is pretty much the opposite.
+    /// The easy way to implement it only requires looking through the data in one direction,
+    /// but involves breaking the data into two separate pieces, sorting the pieces,
+    /// then merging them back together.
+    ///
+    /// With a linked list, it's easy to take (for example) alternating elements in one linked list,
+    /// and manipulate the links to create two linked lists from those same elements instead.
+    /// With an array, rearranging elements so alternating elements go into separate arrays is easy
+    /// if you're willing to create a copy as big as the original data, but otherwise rather more
+    /// non-trivial.
+    ///
+    /// Likewise, merging with arrays is easy if you merge elements from the source arrays
+    /// into a new array with the data in order -- but to do it in place without creating a whole
+    /// new copy of the data is a whole different story. With a linked list, merging elements together
+    /// from two source lists into a single target list is trivial -- again, you just manipulate links,
+    /// without copying elements.
+    ///
+    /// As for using Quicksort to produce the sorted runs for an external merge sort,
+    /// it does work, but it's (decidedly) sub-optimal as a rule. To optimize a merge-sort,
+    /// you normally want to maximize the lengths of each sorted "run" as you produce it.
+    /// If you simply read in the data that will fit in memory, Quicksort it and write it out,
+    /// each run will be restricted to (a little less than) the size of the available memory.
+    ///
+    /// You can do quite a bit better than that as a rule though.
+    /// You start by reading in a block of data, but instead of using a Quicksort on it, you build a heap.
+    /// Then, as you write each item out from the heap into the sorted "run" file, you read another item
+    /// in from your input file. If it's larger than the item you just wrote to disk, you insert it into
+    /// your existing heap, and repeat.
+    ///
+    /// Items that are smaller (i.e., belong before items that have already been written) you keep
+    /// separate, and build into a second heap. When (and only when) your first heap is empty,
+    /// and the second heap has taken over all the memory, you quit writing items to the existing "run" file,
+    /// and start on a new one.
+    ///
+    /// Exactly how effective this will be depends on the initial order of the data.
+    /// In the worst case (input sorted in inverse order) it does no good at all. In the best case
+    /// (input already sorted) it lets you "sort" the data in a single run through the input.
+    /// In an average case (input in random order) it lets you approximately double the length of
+    /// each sorted run, which will typically improve speed by around 20-25%
+    /// (though the percentage varies depending on how much larger your data is than the available memory).
+    #[inline]
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
 
-        // return in case the list does not have more than two elements
-        if next.next.is_none() {
-            next.next = Some(cur);
-            return LinkedList::from(*next);
+    /// Merges two sorted lists into one sorted list by relinking their nodes. On ties the
+    /// elements of `a` come first.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let merged = LinkedList::merge_sorted(
+    ///     LinkedList::from(vec![1, 4, 5]),
+    ///     LinkedList::from(vec![2, 3, 6]),
+    /// );
+    ///
+    /// assert_eq!(merged.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+    /// ```
+    pub fn merge_sorted(mut a: Self, mut b: Self) -> Self {
+        LinkedList::merge_by(&mut a, &mut b, &mut T::cmp)
+    }
+
+    /// Inserts an element into a sorted list, behind all elements not greater than it,
+    /// so the list stays sorted. Inserting behind the last element takes O(1).
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from(vec![1, 3, 5]);
+    /// linked_list.insert_sorted(4);
+    /// linked_list.insert_sorted(0);
+    /// linked_list.insert_sorted(6);
+    ///
+    /// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&0, &1, &3, &4, &5, &6]);
+    /// ```
+    pub fn insert_sorted(&mut self, val: T) {
+        match (self.front(), self.back()) {
+            (Some(front), _) if val < *front => return self.push_front(val),
+            (_, Some(back)) if *back <= val => return self.push_back(val),
+            (None, None) => return self.push_front(val),
+            _ => {}
+        }
+
+        // The last element is greater, so the new node never becomes the tail
+        let mut before = self.head.unwrap();
+        // SAFETY: `before` and its successor are nodes of this list
+        while let Some(next) = unsafe { (*before.as_ptr()).next } {
+            if unsafe { (*next.as_ptr()).value > val } {
+                break;
+            }
+            before = next;
         }
 
-        while next.next.is_some() {
-            let look_ahead = next.next.take().unwrap();
-            next.next = Some(cur);
-            cur = next;
-            next = look_ahead;
-        }
+        self.link_after(before, val);
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    /// Creates an empty `LinkedList<T>`.
+    #[inline]
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the list into an iterator over the lists values.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { list: self }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    /// Returns the next element of a list iterator.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.head.map(|head| {
+            self.len -= 1;
+            self.head = head.next_node();
+            &head.value
+        })
+    }
+
+    /// Returns the length of an iterator.
+    fn count(self) -> usize {
+        self.len
+    }
+}
 
-        next.next = Some(cur);
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
 
-        LinkedList::from(*next)
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl<T: Eq + Ord> IntoIterator for LinkedList<T> {
-    type Item = T;
-    type IntoIter = IntoIter<T>;
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
 
-    /// Consumes the list into an iterator over the lists values.
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter { list: self }
+        self.iter_mut()
     }
 }
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
 
-    /// Returns the next element of a list iterator.
+    /// Returns the next element of a mutable list iterator.
     fn next(&mut self) -> Option<Self::Item> {
         if self.len == 0 {
             return None;
         }
-        self.head.as_ref().map(|head| {
+
+        self.head.map(|head| {
+            // SAFETY: the iterator borrows the list mutably and hands out every node once
+            let head = unsafe { &mut *head.as_ptr() };
             self.len -= 1;
-            self.head = &head.next;
-            &head.value
+            self.head = head.next;
+            &mut head.value
         })
     }
 
-    /// Returns the length of an iterator.
-    fn count(self) -> usize {
-        self.len
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
     }
 }
 
-impl<'a, T: Eq + Ord> Iterator for IntoIter<T> {
+impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
     /// Returns the next element of a IntoIter.
@@ -368,120 +1076,70 @@ impl<'a, T: Eq + Ord> Iterator for IntoIter<T> {
     }
 }
 
-impl<T> Node<T>
-where
-    T: Eq + Ord,
-{
-    #[inline]
-    fn set_next(&mut self, new: Node<T>) {
-        self.next = Some(Box::new(new));
-    }
-
-    #[inline]
-    fn get_back(&mut self, index: usize, mut cur: usize) -> Option<Box<Node<T>>> {
-        if cur + 1 == index {
-            self.next.take()
-        } else {
-            cur += 1;
-            self.next.as_mut().unwrap().get_back(index, cur)
-        }
-    }
+impl<T> Node<T> {
+    fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut in_order: F) -> bool {
+        let mut look = self;
 
-    fn is_sorted(&self) -> bool {
-        let look = self;
-        match &look.next {
-            Some(val) => {
-                if look.value <= val.value {
-                    return val.is_sorted();
-                } else {
-                    return false;
-                }
+        while let Some(next) = look.next_node() {
+            if !in_order(&look.value, &next.value) {
+                return false;
             }
-            None => true,
+            look = next;
         }
+
+        true
     }
 
-    fn new(value: T) -> Self
-    where
-        T: Ord,
-    {
+    fn new(value: T) -> Self {
         Node { next: None, value }
     }
 
-    #[inline]
-    fn append(&mut self, val: T) -> bool {
-        match &mut self.next {
-            Some(iter) => iter.append(val),
-            None => {
-                self.set_next(Node::new(val));
-                true
-            }
-        }
+    /// Returns a reference to the next node, which lives as long as the list does.
+    fn next_node(&self) -> Option<&Node<T>> {
+        // SAFETY: a node is only reachable through its list, which the borrow of `self`
+        // keeps alive
+        self.next.map(|next| unsafe { &*next.as_ptr() })
     }
+}
 
+impl<T: PartialEq> Node<T> {
     #[inline]
     fn contains(&self, val: T) -> bool {
-        if val == self.value {
-            true
-        } else {
-            match &self.next {
-                Some(iter) => iter.contains(val),
-                None => false,
-            }
-        }
-    }
+        let mut current = Some(self);
 
-    #[inline]
-    fn remove(&mut self, index: usize, mut cur: usize) -> bool {
-        if cur + 1 == index {
-            let mut garbage = self.next.take().unwrap();
-            match garbage.next.take() {
-                None => true,
-                Some(new_link) => {
-                    self.set_next(*new_link);
-                    true
-                }
+        while let Some(node) = current {
+            if node.value == val {
+                return true;
             }
-        } else {
-            cur += 1;
-            self.next.as_mut().unwrap().remove(index, cur)
-        }
-    }
-
-    fn get_length(&self) -> usize {
-        let mut count = 1;
-        let mut walk = Some(self);
-
-        while walk.unwrap().next.is_some() {
-            walk = walk.unwrap().next.as_deref();
-            count += 1;
+            current = node.next_node();
         }
 
-        count
+        false
     }
 }
 
 /// Macro for creating a list with given elements. Works like the Vec![] Macro.
 /// # Example
 /// ```rust
-/// use data_structure_with_colin::linked_list::LinkedList;
+/// use data_structure_with_colin::list;
 /// let linked_list = list![1, 2, 3];
 ///
 /// assert!(linked_list.contains(1));
 /// assert!(linked_list.contains(2));
 /// assert!(linked_list.contains(3));
 /// ```
+#[macro_export]
 macro_rules! list {
     () => {
-        LinkedList::new();
+        $crate::linked_list::LinkedList::new()
     };
     ($elem:expr) => {{
-        let mut res = LinkedList::new();
+        let mut res = $crate::linked_list::LinkedList::new();
         res.append($elem);
         res
     }};
-    ($($elem:expr),+) => {{
-        let mut res = LinkedList::new();
+    ($($elem:expr),+ $(,)?) => {{
+        let mut res = $crate::linked_list::LinkedList::new();
         $(res.append($elem);)+
         res
     }};
@@ -498,10 +1156,7 @@ macro_rules! list {
 /// assert!(linked_list.contains(2));
 /// assert!(linked_list.contains(3));
 ///```
-impl<T> From<Vec<T>> for LinkedList<T>
-where
-    T: Eq + Ord,
-{
+impl<T> From<Vec<T>> for LinkedList<T> {
     fn from(list: Vec<T>) -> Self {
         let mut result = list![];
         for elem in list {
@@ -511,25 +1166,161 @@ where
     }
 }
 
-impl<T> From<LinkedList<T>> for Node<T>
-where
-    T: Eq + Ord,
-{
+/// Creates a `LinkedList` from an array.
+/// # Example
+/// ```rust
+/// use data_structure_with_colin::linked_list::LinkedList;
+/// let linked_list = LinkedList::from([1, 2, 3]);
+///
+/// assert_eq!(linked_list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+///```
+impl<T, const N: usize> From<[T; N]> for LinkedList<T> {
+    fn from(array: [T; N]) -> Self {
+        LinkedList::from_iter(array)
+    }
+}
+
+/// Collects the elements of a `LinkedList` into a `Vec`, front to back.
+/// # Example
+/// ```rust
+/// use data_structure_with_colin::linked_list::LinkedList;
+/// let v: Vec<_> = LinkedList::from([1, 2, 3]).into();
+///
+/// assert_eq!(v, vec![1, 2, 3]);
+///```
+impl<T> From<LinkedList<T>> for Vec<T> {
     fn from(list: LinkedList<T>) -> Self {
-        *list.head.unwrap()
+        let mut result = Vec::with_capacity(list.len);
+        result.extend(list);
+        result
     }
 }
 
-impl<T> From<Node<T>> for LinkedList<T>
-where
-    T: Eq + Ord,
-{
-    fn from(node: Node<T>) -> Self {
-        let length = node.get_length();
-        LinkedList {
-            head: Some(Box::new(node)),
-            len: length,
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        while self.pop_front_node().is_some() {}
+    }
+}
+
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+
+    /// Returns the element at `index`.
+    ///
+    /// Panics if `index` is out of range.
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index {} is out of range for a list of length {}",
+                index, self.len
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for LinkedList<T> {
+    /// Returns the element at `index` mutably.
+    ///
+    /// Panics if `index` is out of range.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!(
+                "index {} is out of range for a list of length {}",
+                index, len
+            ),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+/// Compares lists lexicographically, like slices.
+impl<T: PartialOrd> PartialOrd for LinkedList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for LinkedList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // The length keeps nested lists like [[1], []] and [[], [1]] apart
+        state.write_usize(self.len);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for LinkedList<T> {
+    /// Appends the elements to the back of the list, in O(1) each.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Formats the list as `[a -> b -> c]`.
+/// # Example
+/// ```rust
+/// use data_structure_with_colin::linked_list::LinkedList;
+/// let linked_list = LinkedList::from([1, 2, 3]);
+///
+/// assert_eq!(linked_list.to_string(), "[1 -> 2 -> 3]");
+/// assert_eq!(LinkedList::<u8>::new().to_string(), "[]");
+///```
+impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (index, value) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", value)?;
         }
+        write!(f, "]")
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
     }
 }
 
@@ -540,14 +1331,14 @@ mod test {
     fn test_new() {
         let sut = LinkedList::<bool>::new();
         assert_eq!(sut.head, None);
-        assert_eq!(sut.is_empty(), true);
+        assert!(sut.is_empty());
     }
 
     #[test]
     fn test_append() {
         let mut sut = LinkedList::<i32>::new();
         assert_eq!(&sut.append(1), &true);
-        assert_eq!(sut.head.unwrap().value, (1 as i32));
+        assert_eq!(sut.front(), Some(&1_i32));
     }
 
     #[test]
@@ -560,37 +1351,41 @@ mod test {
         }
 
         for i in 0..10 {
-            assert_eq!(sut.contains(i), true);
+            assert!(sut.contains(i));
         }
-        assert_eq!(sut.contains(342), true);
-        assert_eq!(sut.contains(3453), true);
+        assert!(sut.contains(342));
+        assert!(sut.contains(3453));
     }
 
     #[test]
     fn test_empty_contains() {
         let sut = LinkedList::<i32>::new();
-        assert_eq!(sut.contains(42), false);
+        assert!(!sut.contains(42));
     }
 
     #[test]
     fn test_contains() {
         let mut sut = LinkedList::<i32>::new();
-        assert_eq!(sut.contains(42), false);
+        assert!(!sut.contains(42));
         sut.append(42);
-        assert_eq!(sut.contains(42), true);
+        assert!(sut.contains(42));
     }
 
     #[test]
     fn test_to_list() {
         let vector = vec![1, 2, 3];
         let sut = LinkedList::from(vector);
-        assert_eq!(sut.head.unwrap().value, 1);
-        let vector = vec![1, 2, 3];
-        let sut = LinkedList::from(vector);
-        assert_eq!(sut.head.unwrap().next.unwrap().value, 2);
-        let vector = vec![1, 2, 3];
-        let sut = LinkedList::from(vector);
-        assert_eq!(sut.head.unwrap().next.unwrap().next.unwrap().value, 3);
+        let head = sut.head_node().unwrap();
+        assert_eq!(head.value, 1);
+        assert_eq!(head.next_node().unwrap().value, 2);
+        assert_eq!(head.next_node().unwrap().next_node().unwrap().value, 3);
+        assert!(head
+            .next_node()
+            .unwrap()
+            .next_node()
+            .unwrap()
+            .next
+            .is_none());
     }
 
     #[test]
@@ -598,17 +1393,17 @@ mod test {
         let sut: LinkedList<u32> = list![];
         assert_eq!(sut.head, None);
         let sut = list![2];
-        assert_eq!(sut.head.unwrap().value, 2);
+        assert_eq!(sut.head_node().unwrap().value, 2);
         let sut = list![1, 2, 3];
-        assert_eq!(sut.contains(1), true);
-        assert_eq!(sut.contains(2), true);
-        assert_eq!(sut.contains(3), true);
+        assert!(sut.contains(1));
+        assert!(sut.contains(2));
+        assert!(sut.contains(3));
     }
 
     #[test]
     fn test_remove_simple() {
         let mut sut: LinkedList<u32> = list![];
-        assert_ne!(true, sut.remove(0));
+        assert_eq!(sut.remove(0), None);
         sut.append(45);
         assert!(sut.contains(45));
         sut.remove(0);
@@ -663,7 +1458,7 @@ mod test {
         assert!(sut.contains(45));
         sut.remove(0);
         assert!(!sut.contains(45));
-        let val = sut.head.unwrap().value;
+        let val = sut.head_node().unwrap().value;
         assert_eq!(val, 56);
         assert_eq!(sut.len, 5);
         println!("{}", val);
@@ -671,13 +1466,33 @@ mod test {
 
     #[test]
     fn test_get_length() {
-        let mut sut: LinkedList<u32> = list![1, 2, 4, 5, 6];
+        let sut: LinkedList<u32> = list![1, 2, 4, 5, 6];
 
-        let head = sut.head.take().unwrap();
+        let mut count = 1;
+        let mut node = sut.head_node().unwrap();
+        while let Some(next) = node.next_node() {
+            node = next;
+            count += 1;
+        }
 
-        let test = LinkedList::from(*head);
+        assert_eq!(sut.len, count);
+        assert_eq!(sut.tail, Some(NonNull::from(node)));
+    }
 
-        assert_eq!(test.len, 5)
+    #[test]
+    fn test_tail_after_pop_front() {
+        let mut sut = LinkedList::new();
+        sut.push_back(1);
+        sut.push_back(2);
+        assert_eq!(sut.pop_front(), Some(1));
+
+        // The tail still points to the node of 2, which didn't move
+        sut.push_back(3);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&2, &3]);
+        assert_eq!(sut.back(), Some(&3));
+        assert_eq!(sut.pop_front(), Some(2));
+        assert_eq!(sut.pop_front(), Some(3));
+        assert_eq!(sut.tail, None);
     }
 
     #[test]
@@ -745,10 +1560,10 @@ mod test {
         let mut sut = list![1, 2, 3, 4];
         let should = list![4, 3, 2, 1];
 
-        let reversed = sut.reverse();
+        sut.reverse();
 
         let mut iter_should = should.iter();
-        let mut iter_reversed = reversed.iter();
+        let iter_reversed = sut.iter();
 
         for item in iter_reversed {
             let should = iter_should.next().unwrap();
@@ -761,11 +1576,556 @@ mod test {
         let mut sut = list![1, 2];
         let should = list![2, 1];
 
-        let reversed = sut.reverse();
+        sut.reverse();
 
         let mut iter_should = should.iter();
-        let mut iter_reversed = reversed.iter();
+        let mut iter_reversed = sut.iter();
 
         assert_eq!(iter_should.next(), iter_reversed.next())
     }
+
+    #[test]
+    fn test_push_both_ends() {
+        let mut sut = LinkedList::new();
+        assert_eq!(sut.front(), None);
+        assert_eq!(sut.back(), None);
+
+        sut.push_back(2);
+        sut.push_front(1);
+        sut.push_back(3);
+        assert_eq!(sut.front(), Some(&1));
+        assert_eq!(sut.back(), Some(&3));
+        assert_eq!(sut.len, 3);
+
+        *sut.front_mut().unwrap() = 0;
+        *sut.back_mut().unwrap() = 4;
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&0, &2, &4]);
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut sut = list![1, 2, 3];
+        assert_eq!(sut.pop_back(), Some(3));
+        assert_eq!(sut.back(), Some(&2));
+
+        // The tail has to follow, pushing continues behind the new last element
+        sut.push_back(5);
+        assert_eq!(sut.pop_back(), Some(5));
+        assert_eq!(sut.pop_back(), Some(2));
+        assert_eq!(sut.pop_back(), Some(1));
+        assert_eq!(sut.pop_back(), None);
+        assert_eq!(sut.back(), None);
+
+        sut.push_back(7);
+        assert_eq!(sut.front(), Some(&7));
+        assert_eq!(sut.back(), Some(&7));
+    }
+
+    #[test]
+    fn test_tail_after_changes() {
+        let mut sut = list![4, 3, 5, 1];
+        sut.remove(3);
+        assert_eq!(sut.back(), Some(&5));
+        sut.push_back(2);
+        sut.sort();
+        assert_eq!(sut.back(), Some(&5));
+        sut.push_back(6);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&2, &3, &4, &5, &6]);
+
+        sut.reverse();
+        assert_eq!(sut.back(), Some(&2));
+        sut.push_back(1);
+        assert_eq!(sut.back(), Some(&1));
+        assert_eq!(sut, list![6, 5, 4, 3, 2, 1]);
+
+        while sut.pop_front().is_some() {}
+        sut.push_back(9);
+        assert_eq!(sut.back(), Some(&9));
+    }
+
+    #[test]
+    fn test_push_back_many() {
+        let count = if cfg!(miri) { 1_000 } else { 100_000 };
+        let mut sut = LinkedList::new();
+        for i in 0..count {
+            sut.push_back(i);
+        }
+
+        assert_eq!(sut.len, count);
+        assert_eq!(sut.back(), Some(&(count - 1)));
+        assert!(sut.iter().copied().eq(0..count));
+    }
+
+    #[test]
+    fn test_ten_million_elements() {
+        // Every operation has to run without recursing once per node
+        const COUNT: u32 = if cfg!(miri) { 1_000 } else { 10_000_000 };
+        let mut sut = LinkedList::new();
+        for i in (0..COUNT).rev() {
+            sut.push_back(i);
+        }
+        assert!(sut.contains(0));
+        assert!(!sut.is_sorted());
+
+        sut.sort();
+        assert!(sut.is_sorted());
+        assert_eq!(sut.front(), Some(&0));
+        assert_eq!(sut.back(), Some(&(COUNT - 1)));
+
+        sut.reverse();
+        assert_eq!(sut.len, COUNT as usize);
+        assert_eq!(sut.front(), Some(&(COUNT - 1)));
+        assert_eq!(sut.back(), Some(&0));
+
+        drop(sut);
+    }
+
+    #[test]
+    fn test_remove_and_drop_long_list() {
+        let count = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let mut sut = LinkedList::from((0..count).collect::<Vec<_>>());
+        assert_eq!(sut.remove(count - 1), Some(count - 1));
+        assert_eq!(sut.remove(count / 2), Some(count / 2));
+        assert_eq!(sut.remove(count - 2), None);
+        assert_eq!(sut.back(), Some(&(count - 2)));
+        assert!(!sut.contains(count / 2));
+        assert_eq!(sut.iter().count(), count - 2);
+    }
+
+    #[test]
+    fn test_unordered_elements() {
+        let mut floats = LinkedList::new();
+        floats.push_back(1.5);
+        floats.push_front(f64::NAN);
+        assert!(floats.contains(1.5));
+        assert!(!floats.contains(f64::NAN));
+        assert_eq!(floats.len(), 2);
+
+        let mut closures: LinkedList<Box<dyn Fn(i32) -> i32>> = LinkedList::new();
+        closures.push_back(Box::new(|x| x + 1));
+        closures.push_back(Box::new(|x| x * 2));
+        closures.reverse();
+        assert_eq!(
+            closures.iter().map(|f| f(3)).collect::<Vec<_>>(),
+            vec![6, 4]
+        );
+        assert!(closures.remove(0).is_some());
+        assert_eq!(
+            closures.into_iter().map(|f| f(3)).collect::<Vec<_>>(),
+            vec![4]
+        );
+    }
+
+    #[test]
+    fn test_sort_by() {
+        #[derive(Debug, PartialEq)]
+        struct Task {
+            priority: u8,
+            name: &'static str,
+        }
+        let task = |priority, name| Task { priority, name };
+
+        let mut sut =
+            LinkedList::from(vec![task(2, "b"), task(1, "c"), task(2, "a"), task(1, "d")]);
+        assert!(!sut.is_sorted_by(|a, b| a.priority <= b.priority));
+
+        // Equal priorities keep their order
+        sut.sort_by(|a, b| a.priority.cmp(&b.priority));
+        assert!(sut.is_sorted_by(|a, b| a.priority <= b.priority));
+        let names: Vec<_> = sut.iter().map(|task| task.name).collect();
+        assert_eq!(names, vec!["c", "d", "b", "a"]);
+
+        sut.sort_by_key(|task| task.name);
+        let names: Vec<_> = sut.iter().map(|task| task.name).collect();
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+        assert_eq!(sut.back(), Some(&task(1, "d")));
+    }
+
+    #[test]
+    fn test_sort_by_against_vec() {
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+        let values: Vec<(u8, usize)> = (0..2000).map(|i| (rng.gen_range(0, 10), i)).collect();
+        let mut sut = LinkedList::from(values.clone());
+        let mut expected = values;
+
+        sut.sort_by_key(|pair| pair.0);
+        expected.sort_by_key(|pair| pair.0);
+        assert!(sut.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_reverse_in_place() {
+        let mut sut: LinkedList<u32> = list![];
+        sut.reverse();
+        assert!(sut.is_empty());
+        assert_eq!(sut.len(), 0);
+        assert_eq!(sut.iter().next(), None);
+
+        sut.push_back(1);
+        sut.reverse();
+        assert_eq!(sut.len(), 1);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&1]);
+
+        let mut sut = list![1, 2, 3];
+        sut.reverse();
+        assert!(!sut.is_empty());
+        assert_eq!(sut.len(), 3);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+        assert_eq!(sut.back(), Some(&1));
+
+        // The tail has to point to the new last node
+        sut.push_back(0);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&3, &2, &1, &0]);
+        assert_eq!(sut.len(), 4);
+    }
+
+    #[test]
+    fn test_reversed() {
+        let sut = list![1, 2, 3];
+        let mut reversed = sut.reversed();
+
+        assert_eq!(sut.len(), 3);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+        assert_eq!(reversed.len(), 3);
+        assert_eq!(reversed.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+
+        reversed.push_back(0);
+        assert_eq!(reversed.back(), Some(&0));
+        assert!(LinkedList::<u8>::new().reversed().is_empty());
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut empty: LinkedList<u32> = list![];
+        empty.rotate_left(3);
+        empty.rotate_right(3);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+
+        for len in 1..7 {
+            for k in 0..15 {
+                let values: Vec<u32> = (0..len).collect();
+                let mut sut = LinkedList::from(values.clone());
+                let mut expected = values.clone();
+
+                sut.rotate_left(k);
+                expected.rotate_left(k % len as usize);
+                assert_eq!(sut.len(), len as usize);
+                assert!(!sut.is_empty());
+                assert!(sut.iter().eq(expected.iter()));
+                assert_eq!(sut.back(), expected.last());
+
+                sut.rotate_right(k);
+                assert_eq!(sut.len(), len as usize);
+                assert!(sut.iter().eq(values.iter()));
+                assert_eq!(sut.back(), values.last());
+
+                sut.push_back(len);
+                assert_eq!(sut.iter().last(), Some(&len));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reorder_after_pop_front() {
+        let mut sut = list![0, 1, 2, 3];
+        sut.pop_front();
+        sut.rotate_left(1);
+        sut.push_back(4);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&2, &3, &1, &4]);
+
+        sut.pop_front();
+        sut.reverse();
+        sut.push_back(5);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&4, &1, &3, &5]);
+        assert_eq!(sut.back(), Some(&5));
+
+        sut.rotate_right(3);
+        assert_eq!(sut.pop_back(), Some(4));
+        assert_eq!(sut.back(), Some(&5));
+    }
+
+    #[test]
+    fn test_get_and_index() {
+        let mut sut = list![1, 2, 3];
+        assert_eq!(sut.get(0), Some(&1));
+        assert_eq!(sut.get(2), Some(&3));
+        assert_eq!(sut.get(3), None);
+        assert_eq!(sut.get_mut(3), None);
+
+        *sut.get_mut(1).unwrap() = 20;
+        *sut.get_mut(2).unwrap() = 30;
+        sut[0] = 10;
+        assert_eq!((sut[0], sut[1], sut[2]), (10, 20, 30));
+        assert_eq!(sut.back(), Some(&30));
+        assert!(LinkedList::<u8>::new().get(0).is_none());
+        assert_eq!(sut.get(usize::MAX), None);
+        assert_eq!(sut.get_mut(usize::MAX), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of range for a list of length 3")]
+    fn test_index_out_of_range() {
+        let sut = list![1, 2, 3];
+        let _ = sut[3];
+    }
+
+    #[test]
+    fn test_insert_and_remove_at() {
+        let mut sut = LinkedList::new();
+        sut.insert(0, 2);
+        sut.insert(0, 0);
+        sut.insert(1, 1);
+        sut.insert(3, 4);
+        sut.insert(3, 3);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3, &4]);
+        assert_eq!(sut.len(), 5);
+        assert_eq!(sut.back(), Some(&4));
+
+        assert_eq!(sut.remove(4), Some(4));
+        assert_eq!(sut.back(), Some(&3));
+        assert_eq!(sut.remove(1), Some(1));
+        assert_eq!(sut.remove(3), None);
+        sut.push_back(5);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&0, &2, &3, &5]);
+        assert_eq!(sut.len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index 3 is out of range for a list of length 1")]
+    fn test_insert_out_of_range() {
+        let mut sut = list![1];
+        sut.insert(3, 2);
+    }
+
+    #[test]
+    fn test_swap_and_truncate() {
+        let mut sut = list![0, 1, 2, 3, 4];
+        sut.swap(4, 1);
+        sut.swap(2, 2);
+        sut.swap(0, 1);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&4, &0, &2, &3, &1]);
+        assert_eq!(sut.back(), Some(&1));
+
+        sut.truncate(10);
+        assert_eq!(sut.len(), 5);
+        sut.truncate(2);
+        assert_eq!(sut.len(), 2);
+        assert_eq!(sut.back(), Some(&0));
+        sut.push_back(7);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&4, &0, &7]);
+
+        sut.truncate(0);
+        assert!(sut.is_empty());
+        assert_eq!(sut.back(), None);
+        sut.push_back(1);
+        assert_eq!(sut.front(), Some(&1));
+    }
+
+    #[test]
+    #[should_panic(expected = "swap index 2 is out of range for a list of length 2")]
+    fn test_swap_out_of_range() {
+        let mut sut = list![1, 2];
+        sut.swap(0, 2);
+    }
+
+    #[test]
+    fn test_remove_by_value() {
+        let mut sut = list![1, 2, 1, 3, 1];
+        assert_eq!(sut.position(&3), Some(3));
+        assert_eq!(sut.position(&4), None);
+
+        assert_eq!(sut.remove_first(&3), Some(3));
+        assert_eq!(sut.remove_first(&4), None);
+        assert_eq!(sut.remove_first(&1), Some(1));
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&2, &1, &1]);
+
+        assert_eq!(sut.remove_first(&1), Some(1));
+        assert_eq!(sut.remove_first(&1), Some(1));
+        assert_eq!(sut.back(), Some(&2));
+        assert_eq!(sut.len(), 1);
+
+        let mut sut = list![1, 1, 2, 1, 3, 1, 1];
+        assert_eq!(sut.remove_all(&1), 5);
+        assert_eq!(sut.len(), 2);
+        assert_eq!(sut.back(), Some(&3));
+        assert_eq!(sut.remove_all(&4), 0);
+        assert_eq!(sut.remove_all(&2), 1);
+        assert_eq!(sut.remove_all(&3), 1);
+        assert!(sut.is_empty());
+        assert_eq!(sut.back(), None);
+        sut.push_back(9);
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&9]);
+    }
+
+    #[test]
+    fn test_sort_is_stable_against_vec() {
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+        for len in [0, 1, 2, 3, 7, 64, 65, 1000].iter() {
+            let values: Vec<(u8, usize)> = (0..*len).map(|i| (rng.gen_range(0, 5), i)).collect();
+            let mut sut = LinkedList::from(values.clone());
+            let mut expected = values;
+
+            sut.sort_by_key(|pair| pair.0);
+            expected.sort_by_key(|pair| pair.0);
+            assert!(sut.iter().eq(expected.iter()));
+            assert_eq!(sut.len(), *len);
+            assert_eq!(sut.back(), expected.last());
+        }
+    }
+
+    #[test]
+    fn test_merge_sorted() {
+        let merged = LinkedList::merge_sorted(list![(1, 'a'), (3, 'a')], list![(1, 'b'), (2, 'b')]);
+        assert_eq!(
+            merged.iter().collect::<Vec<_>>(),
+            vec![&(1, 'a'), &(1, 'b'), &(2, 'b'), &(3, 'a')]
+        );
+        assert_eq!(merged.len(), 4);
+        assert_eq!(merged.back(), Some(&(3, 'a')));
+
+        let merged = LinkedList::merge_sorted(list![], list![1, 2]);
+        assert_eq!(merged.iter().collect::<Vec<_>>(), vec![&1, &2]);
+        let merged = LinkedList::merge_sorted(list![1, 2], list![]);
+        assert_eq!(merged.back(), Some(&2));
+        assert!(LinkedList::<u8>::merge_sorted(list![], list![]).is_empty());
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut sut = list![1, 1, 1, 2, 3, 3, 1, 1];
+        sut.dedup();
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &1]);
+        assert_eq!(sut.len(), 4);
+        assert_eq!(sut.back(), Some(&1));
+        sut.push_back(5);
+        assert_eq!(sut.back(), Some(&5));
+
+        let mut sut = list!["a", "B", "b", "A", "c"];
+        sut.dedup_by_key(|word| word.to_lowercase());
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&"a", &"B", &"A", &"c"]);
+
+        let mut empty: LinkedList<u8> = list![];
+        empty.dedup();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_insert_sorted() {
+        use rand::{thread_rng, Rng};
+
+        let mut rng = thread_rng();
+        let mut sut = LinkedList::new();
+        let mut expected = Vec::new();
+
+        for _ in 0..500 {
+            let value = rng.gen_range(0, 100);
+            sut.insert_sorted(value);
+            expected.push(value);
+        }
+        expected.sort();
+
+        assert!(sut.iter().eq(expected.iter()));
+        assert_eq!(sut.len(), 500);
+        assert_eq!(sut.back(), expected.last());
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut sut = list![1, 2, 3];
+        let mut iter = sut.iter_mut();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        *iter.next().unwrap() = 10;
+        assert_eq!(iter.size_hint(), (2, Some(2)));
+
+        for value in &mut sut {
+            *value += 1;
+        }
+        assert_eq!(sut.iter().collect::<Vec<_>>(), vec![&11, &3, &4]);
+        assert_eq!(sut.back(), Some(&4));
+        assert_eq!(LinkedList::<u8>::new().iter_mut().next(), None);
+    }
+
+    #[test]
+    fn test_collect_and_extend() {
+        let mut sut: LinkedList<_> = (1..=3).collect();
+        sut.extend(vec![4, 5]);
+        sut.extend(&[6, 7]);
+        assert_eq!(sut.len(), 7);
+        assert_eq!(sut.back(), Some(&7));
+        sut.push_back(8);
+        assert_eq!(Vec::from(sut), (1..=8).collect::<Vec<_>>());
+
+        let mut empty = LinkedList::new();
+        empty.extend(Vec::<u8>::new());
+        assert!(empty.is_empty());
+        assert_eq!(empty.back(), None);
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let mut sut = list![String::from("a"), String::from("b")];
+        let clone = sut.clone();
+        sut.push_back(String::from("c"));
+        sut[0].push('!');
+
+        assert_eq!(clone, list![String::from("a"), String::from("b")]);
+        assert_eq!(clone.back(), Some(&String::from("b")));
+        assert_eq!(sut.len(), 3);
+    }
+
+    #[test]
+    fn test_ord_is_lexicographic() {
+        assert!(list![1, 2] < list![1, 3]);
+        assert!(list![1, 2] < list![1, 2, 0]);
+        assert!(list![2] > list![1, 9, 9]);
+        assert!(LinkedList::<u8>::new() < list![0]);
+        assert_eq!(list![1, 2].cmp(&list![1, 2]), Ordering::Equal);
+        assert_eq!(list![1.0, f64::NAN].partial_cmp(&list![1.0, 2.0]), None);
+
+        let mut lists = vec![list![2], list![1, 2], list![], list![1]];
+        lists.sort();
+        assert_eq!(lists, vec![list![], list![1], list![1, 2], list![2]]);
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        assert_eq!(hash(&list![1, 2, 3]), hash(&LinkedList::from([1, 2, 3])));
+        assert_ne!(hash(&list![1, 2, 3]), hash(&list![3, 2, 1]));
+        assert_ne!(
+            hash(&list![list![1], list![]]),
+            hash(&list![list![], list![1]])
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(list![1].to_string(), "[1]");
+        assert_eq!(list!["a", "b", "c"].to_string(), "[a -> b -> c]");
+        assert_eq!(format!("{}", LinkedList::<u8>::default()), "[]");
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(format!("{:?}", list![1, 2, 3]), "[1, 2, 3]");
+        assert_eq!(format!("{:?}", list!["a"]), "[\"a\"]");
+        assert_eq!(format!("{:?}", LinkedList::<u8>::new()), "[]");
+
+        // Formatting a long list may not recurse once per node
+        let count = if cfg!(miri) { 1_000 } else { 100_000 };
+        let long: LinkedList<_> = (0..count).collect();
+        assert!(format!("{:?}", long).ends_with(&format!("{}]", count - 1)));
+    }
 }
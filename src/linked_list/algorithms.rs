@@ -0,0 +1,415 @@
+//! Classic two-pointer and relinking algorithms on the singly linked list.
+//!
+//! Every algorithm walks the list at most twice and needs O(1) extra space. Instead of
+//! copying elements into a buffer, they move pointers along the chain or relink the
+//! existing nodes, so no node gets allocated or freed.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use super::{Iter, LinkedList, Node};
+
+/// An Iterator over consecutive chunks of a linked list, each being an [`Iter`] over at
+/// most `size` elements. Instances are created by [`LinkedList::chunks()`].
+pub struct Chunks<'a, T: 'a> {
+    head: Option<&'a Node<T>>,
+    len: usize,
+    size: usize,
+}
+
+impl<T> LinkedList<T> {
+    /// Returns the middle element, found with a slow pointer moving one node and a fast
+    /// pointer moving two nodes at a time. For an even length it is the second of the two
+    /// middle elements, the one at `len / 2`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// assert_eq!(LinkedList::from([1, 2, 3]).middle(), Some(&2));
+    /// assert_eq!(LinkedList::from([1, 2, 3, 4]).middle(), Some(&3));
+    /// assert_eq!(LinkedList::<u8>::new().middle(), None);
+    /// ```
+    pub fn middle(&self) -> Option<&T> {
+        let mut slow = self.head_node()?;
+        let mut fast = slow;
+
+        while let Some(next) = fast.next_node() {
+            slow = slow.next_node().unwrap();
+            match next.next_node() {
+                Some(after_next) => fast = after_next,
+                None => break,
+            }
+        }
+
+        Some(&slow.value)
+    }
+
+    /// Returns the element `k` places before the last one, so `nth_from_end(0)` is the
+    /// last element. A leading pointer starts `k` nodes ahead of a trailing one, when it
+    /// reaches the last node the trailing one points at the result.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(linked_list.nth_from_end(0), Some(&4));
+    /// assert_eq!(linked_list.nth_from_end(3), Some(&1));
+    /// assert_eq!(linked_list.nth_from_end(4), None);
+    /// ```
+    pub fn nth_from_end(&self, k: usize) -> Option<&T> {
+        let mut lead = self.head_node()?;
+        for _ in 0..k {
+            lead = lead.next_node()?;
+        }
+
+        let mut trail = self.head_node().unwrap();
+        while let Some(next) = lead.next_node() {
+            lead = next;
+            trail = trail.next_node().unwrap();
+        }
+
+        Some(&trail.value)
+    }
+
+    /// Interleaves the nodes of `other` with the nodes of this list, starting with this
+    /// one. Once one of the lists runs out, the rest of the other follows as it is.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from([1, 2, 3]);
+    /// linked_list.interleave(LinkedList::from([10, 20, 30, 40, 50]));
+    ///
+    /// assert_eq!(
+    ///     linked_list.iter().collect::<Vec<_>>(),
+    ///     vec![&1, &10, &2, &20, &3, &30, &40, &50]
+    /// );
+    /// ```
+    pub fn interleave(&mut self, mut other: LinkedList<T>) {
+        let mut result = LinkedList::new();
+
+        while let Some(node) = self.pop_front_node() {
+            result.push_back_node(node);
+            match other.pop_front_node() {
+                Some(node) => result.push_back_node(node),
+                None => break,
+            }
+        }
+
+        // At most one of them has nodes left
+        result.push_back_list(self);
+        result.push_back_list(&mut other);
+        *self = result;
+    }
+
+    /// Returns an `Iterator` over chunks of `size` elements, the last chunk may be shorter.
+    /// Each chunk is an [`Iter`] into the list itself, nothing gets copied.
+    ///
+    /// Panics if `size` is zero.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let linked_list = LinkedList::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<Vec<_>> = linked_list.chunks(2).map(|chunk| chunk.collect()).collect();
+    ///
+    /// assert_eq!(chunks, vec![vec![&1, &2], vec![&3, &4], vec![&5]]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Chunks {
+            head: self.head_node(),
+            len: self.len,
+            size,
+        }
+    }
+
+    /// Reverses every group of `k` consecutive elements in place, the last group may be
+    /// shorter and gets reversed as well. The nodes are relinked in a single pass.
+    ///
+    /// Panics if `k` is zero.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// linked_list.reverse_in_groups(3);
+    ///
+    /// assert_eq!(
+    ///     linked_list.iter().collect::<Vec<_>>(),
+    ///     vec![&3, &2, &1, &6, &5, &4, &8, &7]
+    /// );
+    /// ```
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        assert!(k != 0, "group size must be non-zero");
+
+        let mut result = LinkedList::new();
+
+        while !self.is_empty() {
+            // Pushing to the front reverses the group, its first node becomes its tail
+            let mut group = LinkedList::new();
+            while let Some(node) = self.pop_front_node() {
+                group.push_front_node(node);
+                if group.len == k {
+                    break;
+                }
+            }
+
+            result.push_back_list(&mut group);
+        }
+
+        *self = result;
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+    /// Checks if the list reads the same forwards and backwards. To get along without a
+    /// buffer, the second half gets reversed for the comparison and restored afterwards,
+    /// which is why this needs the list mutably.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// assert!(LinkedList::from([1, 2, 3, 2, 1]).is_palindrome());
+    /// assert!(LinkedList::from(['a', 'b', 'b', 'a']).is_palindrome());
+    /// assert!(!LinkedList::from([1, 2, 3]).is_palindrome());
+    /// ```
+    pub fn is_palindrome(&mut self) -> bool {
+        let half = self.len / 2;
+        if half == 0 {
+            return true;
+        }
+
+        // With an odd length the middle element stays with the front half, the comparison
+        // stops right before it
+        let mut back = self.split_at_index(self.len - half);
+        back.reverse();
+        let palindrome = self.iter().zip(back.iter()).all(|(a, b)| a == b);
+        back.reverse();
+        self.push_back_list(&mut back);
+
+        palindrome
+    }
+}
+
+impl<T: PartialOrd> LinkedList<T> {
+    /// Relinks the nodes so that all elements less than `pivot` come before all other
+    /// elements. The partition is stable, both parts keep their order. Returns the number
+    /// of elements less than `pivot`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::linked_list::LinkedList;
+    /// let mut linked_list = LinkedList::from([3, 5, 8, 5, 10, 2, 1]);
+    ///
+    /// assert_eq!(linked_list.partition(&5), 3);
+    /// assert_eq!(
+    ///     linked_list.iter().collect::<Vec<_>>(),
+    ///     vec![&3, &2, &1, &5, &8, &5, &10]
+    /// );
+    /// ```
+    pub fn partition(&mut self, pivot: &T) -> usize {
+        let mut less = LinkedList::new();
+        let mut rest = LinkedList::new();
+
+        while let Some(node) = self.pop_front_node() {
+            if node.value < *pivot {
+                less.push_back_node(node);
+            } else {
+                rest.push_back_node(node);
+            }
+        }
+
+        let count = less.len;
+        less.push_back_list(&mut rest);
+        *self = less;
+
+        count
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    /// Returns the next chunk, skipping its nodes to find the start of the following one.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let len = self.len.min(self.size);
+        let chunk = Iter {
+            head: self.head,
+            len,
+        };
+
+        for _ in 0..len {
+            self.head = self.head.unwrap().next_node();
+        }
+        self.len -= len;
+
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.len.div_ceil(self.size);
+        (count, Some(count))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Chunks<'a, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    const ROUNDS: usize = if cfg!(miri) { 20 } else { 500 };
+
+    /// Returns random values with few distinct ones, so duplicates and palindromes happen.
+    fn random_values<R: Rng>(rng: &mut R) -> Vec<u8> {
+        let len = rng.gen_range(0, 40);
+        (0..len).map(|_| rng.gen_range(0, 3)).collect()
+    }
+
+    /// Checks elements, length and tail of a list against the expected values.
+    fn assert_list_eq(list: &mut LinkedList<u8>, expected: &[u8]) {
+        assert!(list.iter().eq(expected.iter()));
+        assert_eq!(list.len(), expected.len());
+        assert_eq!(list.back(), expected.last());
+
+        list.push_back(42);
+        assert_eq!(list.back(), Some(&42));
+        list.pop_back();
+    }
+
+    #[test]
+    fn test_middle_matches_index() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..ROUNDS {
+            let values = random_values(&mut rng);
+            let list = LinkedList::from(values.clone());
+            assert_eq!(list.middle(), values.get(values.len() / 2));
+        }
+    }
+
+    #[test]
+    fn test_nth_from_end_matches_index() {
+        let mut rng = StdRng::seed_from_u64(2);
+
+        for _ in 0..ROUNDS {
+            let values = random_values(&mut rng);
+            let list = LinkedList::from(values.clone());
+
+            for k in 0..=values.len() + 1 {
+                let expected = values.len().checked_sub(k + 1).map(|i| &values[i]);
+                assert_eq!(list.nth_from_end(k), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_palindrome_matches_reversed_vec() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for round in 0..ROUNDS {
+            let mut values = random_values(&mut rng);
+            if round % 2 == 0 {
+                // Mirror the values, every second round with a middle element
+                let mirrored: Vec<u8> = values.iter().rev().copied().collect();
+                if round % 4 == 0 {
+                    values.push(7);
+                }
+                values.extend(mirrored);
+            }
+
+            let mut list = LinkedList::from(values.clone());
+            let expected = values.iter().eq(values.iter().rev());
+            assert_eq!(list.is_palindrome(), expected);
+            assert_list_eq(&mut list, &values);
+        }
+    }
+
+    #[test]
+    fn test_partition_is_stable() {
+        let mut rng = StdRng::seed_from_u64(4);
+
+        for _ in 0..ROUNDS {
+            let values = random_values(&mut rng);
+            let pivot = rng.gen_range(0, 4);
+            let (mut expected, rest): (Vec<u8>, Vec<u8>) =
+                values.iter().partition(|value| **value < pivot);
+            let count = expected.len();
+            expected.extend(rest);
+
+            let mut list = LinkedList::from(values);
+            assert_eq!(list.partition(&pivot), count);
+            assert_list_eq(&mut list, &expected);
+        }
+    }
+
+    #[test]
+    fn test_interleave_matches_vec() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for _ in 0..ROUNDS {
+            let first = random_values(&mut rng);
+            let second = random_values(&mut rng);
+
+            let mut expected = Vec::new();
+            for i in 0..first.len().max(second.len()) {
+                expected.extend(first.get(i));
+                expected.extend(second.get(i));
+            }
+
+            let mut list = LinkedList::from(first);
+            list.interleave(LinkedList::from(second));
+            assert_list_eq(&mut list, &expected);
+        }
+    }
+
+    #[test]
+    fn test_chunks_match_slice_chunks() {
+        let mut rng = StdRng::seed_from_u64(6);
+
+        for _ in 0..ROUNDS {
+            let values = random_values(&mut rng);
+            let size = rng.gen_range(1, 10);
+            let list = LinkedList::from(values.clone());
+
+            let chunks = list.chunks(size);
+            assert_eq!(chunks.len(), values.chunks(size).len());
+            for (chunk, expected) in chunks.zip(values.chunks(size)) {
+                assert_eq!(chunk.count(), expected.len());
+            }
+            for (chunk, expected) in list.chunks(size).zip(values.chunks(size)) {
+                assert!(chunk.eq(expected.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_reverse_in_groups_matches_slice_chunks() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..ROUNDS {
+            let values = random_values(&mut rng);
+            let k = rng.gen_range(1, 10);
+            let expected: Vec<u8> = values
+                .chunks(k)
+                .flat_map(|chunk| chunk.iter().rev().copied())
+                .collect();
+
+            let mut list = LinkedList::from(values);
+            list.reverse_in_groups(k);
+            assert_list_eq(&mut list, &expected);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn test_chunks_of_zero_panics() {
+        LinkedList::from([1]).chunks(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "group size must be non-zero")]
+    fn test_reverse_in_groups_of_zero_panics() {
+        LinkedList::from([1]).reverse_in_groups(0);
+    }
+}
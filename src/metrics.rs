@@ -0,0 +1,68 @@
+//! Instrumentation counters for the tree structures.
+//!
+//! Every instrumented structure owns a `Metrics` instance and bumps its
+//! counters while it works. The counters are only recorded with the `metrics`
+//! cargo feature enabled. Without it `Metrics` is a zero sized type whose
+//! methods do nothing, so it costs neither memory nor time.
+//!
+//! NOTE: This was written for a learning purpose.
+
+/// Counts the work done by a single structure instance.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Metrics {
+    #[cfg(feature = "metrics")]
+    comparisons: u64,
+    #[cfg(feature = "metrics")]
+    rotations: u64,
+    #[cfg(feature = "metrics")]
+    visited_nodes: u64,
+}
+
+impl Metrics {
+    #[inline]
+    pub(crate) fn count_comparison(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.comparisons += 1;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn count_rotations(&mut self, _rotations: usize) {
+        #[cfg(feature = "metrics")]
+        {
+            self.rotations += _rotations as u64;
+        }
+    }
+
+    #[inline]
+    pub(crate) fn count_visited_node(&mut self) {
+        #[cfg(feature = "metrics")]
+        {
+            self.visited_nodes += 1;
+        }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl Metrics {
+    /// Number of comparisons between two values.
+    pub fn comparisons(&self) -> u64 {
+        self.comparisons
+    }
+
+    /// Number of single rotations done while rebalancing. A double rotation counts twice.
+    pub fn rotations(&self) -> u64 {
+        self.rotations
+    }
+
+    /// Number of nodes visited while searching for a position.
+    pub fn visited_nodes(&self) -> u64 {
+        self.visited_nodes
+    }
+
+    /// Sets all counters back to zero.
+    pub fn reset(&mut self) {
+        *self = Metrics::default();
+    }
+}
@@ -0,0 +1,171 @@
+//! A safe queue.
+//!
+//! The `Queue` allows inserting at the back and removing from the front in
+//! amortized constant time. It is build from two stacks: elements are pushed
+//! onto the back stack and, once the front stack runs empty, moved over to it,
+//! which reverses them into FIFO order.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use super::stack::Stack;
+use std::iter::FromIterator;
+
+/// A FIFO queue build from two stacks.
+pub struct Queue<T> {
+    front: Stack<T>,
+    back: Stack<T>,
+}
+
+/// An owning Iterator of the queues elements.
+/// Instances are created by [`Queue::into_iter()`].
+pub struct IntoIter<T: Eq> {
+    queue: Queue<T>,
+}
+
+impl<T: Eq> Queue<T> {
+    /// Creates a new empty queue.
+    /// ```rust
+    /// use data_structure_with_colin::queue::Queue;
+    /// let queue = Queue::<()>::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Queue {
+            front: Stack::new(),
+            back: Stack::new(),
+        }
+    }
+
+    /// Inserts an element at the back of the queue.
+    /// ```rust
+    /// use data_structure_with_colin::queue::Queue;
+    /// let mut queue = Queue::new();
+    /// queue.enqueue(1);
+    /// assert!(!queue.is_empty());
+    /// ```
+    pub fn enqueue(&mut self, elem: T) {
+        self.back.push(elem);
+    }
+
+    /// Removes the element at the front of the queue, which is the one that got inserted first.
+    /// ```rust
+    /// use data_structure_with_colin::queue::Queue;
+    /// let mut queue = Queue::new();
+    /// queue.enqueue(1);
+    /// queue.enqueue(2);
+    /// assert_eq!(queue.dequeue(), Some(1));
+    /// assert_eq!(queue.dequeue(), Some(2));
+    /// assert_eq!(queue.dequeue(), None);
+    /// ```
+    pub fn dequeue(&mut self) -> Option<T> {
+        if self.front.is_empty() {
+            while let Some(elem) = self.back.pop() {
+                self.front.push(elem);
+            }
+        }
+
+        self.front.pop()
+    }
+
+    /// Returns the number of elements in the queue.
+    /// ```rust
+    /// use data_structure_with_colin::queue::Queue;
+    /// let mut queue = Queue::new();
+    /// queue.enqueue(1);
+    /// queue.enqueue(2);
+    /// assert_eq!(queue.size(), 2);
+    /// ```
+    pub fn size(&self) -> usize {
+        (self.front.size() + self.back.size()) as usize
+    }
+
+    /// Checks if the queue is empty.
+    pub fn is_empty(&self) -> bool {
+        self.front.is_empty() && self.back.is_empty()
+    }
+}
+
+impl<T: Eq> Default for Queue<T> {
+    /// Creates an empty `Queue<T>`.
+    #[inline]
+    fn default() -> Self {
+        Queue::new()
+    }
+}
+
+impl<T: Eq> FromIterator<T> for Queue<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut result = Queue::new();
+        for elem in iter {
+            result.enqueue(elem);
+        }
+        result
+    }
+}
+
+impl<T: Eq> IntoIterator for Queue<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the queue into an iterator over its values, in FIFO order.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { queue: self }
+    }
+}
+
+impl<T: Eq> Iterator for IntoIter<T> {
+    type Item = T;
+
+    /// Returns the next element of a IntoIter.
+    fn next(&mut self) -> Option<Self::Item> {
+        self.queue.dequeue()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_queue() {
+        let sut = Queue::<()>::new();
+        assert!(sut.is_empty());
+        assert_eq!(sut.size(), 0);
+    }
+
+    #[test]
+    fn test_fifo_order() {
+        let mut sut = Queue::new();
+        sut.enqueue(1);
+        sut.enqueue(2);
+        assert_eq!(sut.dequeue(), Some(1));
+        sut.enqueue(3);
+        sut.enqueue(4);
+        assert_eq!(sut.dequeue(), Some(2));
+        assert_eq!(sut.dequeue(), Some(3));
+        sut.enqueue(5);
+        assert_eq!(sut.size(), 2);
+        assert_eq!(sut.dequeue(), Some(4));
+        assert_eq!(sut.dequeue(), Some(5));
+        assert_eq!(sut.dequeue(), None);
+        assert!(sut.is_empty());
+    }
+
+    #[test]
+    fn test_drop_long_queue() {
+        let count = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let mut sut: Queue<usize> = (0..count).collect();
+        // All elements move over to the front stack, half of them come back to the back one
+        for _ in 0..count / 2 {
+            let front = sut.dequeue().unwrap();
+            sut.enqueue(front);
+        }
+        assert_eq!(sut.size(), count);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let sut: Queue<_> = (1..=4).collect();
+        assert_eq!(sut.into_iter().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,322 @@
+use crate::key_value::{after_start, before_end, KeyValue};
+use crate::metrics::Metrics;
+use crate::rb_tree::tree::*;
+use std::cmp::Ordering;
+use std::iter::FromIterator;
+use std::mem::replace;
+use std::ops::RangeBounds;
+
+/// An ordered map that keeps its entries in a red-black tree.
+#[derive(Clone)]
+pub struct RBTreeMap<K: Ord, V> {
+    root: RBTree<KeyValue<K, V>>,
+    len: usize,
+    metrics: Metrics,
+}
+
+impl<K: Ord, V> RBTreeMap<K, V> {
+    /// Creates a new and empty `RBTreeMap`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeMap;
+    /// let map = RBTreeMap::<u32, ()>::new();
+    /// assert!(map.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        RBTreeMap {
+            root: None,
+            len: 0,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Inserts a key value pair into the map. If the key was already present its value
+    /// is replaced and the old value is returned. The key is not updated.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeMap;
+    /// let mut map = RBTreeMap::new();
+    /// assert_eq!(map.insert(1, "a"), None);
+    /// assert_eq!(map.insert(1, "b"), Some("a"));
+    /// assert_eq!(map.get(&1), Some(&"b"));
+    /// ```
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let rejected = insert(
+            &mut self.root,
+            KeyValue { key, value },
+            false,
+            &mut self.metrics,
+        );
+
+        match rejected {
+            None => {
+                self.len += 1;
+                None
+            }
+            // The stored key stays, only the value is swapped
+            Some(KeyValue { key, value }) => Some(replace(self.get_mut(&key).unwrap(), value)),
+        }
+    }
+
+    /// Returns a reference to the value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let mut current_tree = &self.root;
+
+        while let Some(current_node) = current_tree {
+            match key.cmp(&current_node.value.key) {
+                Ordering::Less => current_tree = &current_node.left,
+                Ordering::Equal => return Some(&current_node.value.value),
+                Ordering::Greater => current_tree = &current_node.right,
+            }
+        }
+
+        None
+    }
+
+    /// Returns a mutable reference to the value stored for `key`.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let mut current_tree = &mut self.root;
+
+        while let Some(current_node) = current_tree {
+            match key.cmp(&current_node.value.key) {
+                Ordering::Less => current_tree = &mut current_node.left,
+                Ordering::Equal => return Some(&mut current_node.value.value),
+                Ordering::Greater => current_tree = &mut current_node.right,
+            }
+        }
+
+        None
+    }
+
+    /// Checks if the map contains a value for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes `key` from the map and returns its value, if it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeMap;
+    /// let mut map = RBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.remove(&1), Some("a"));
+    /// assert_eq!(map.remove(&1), None);
+    /// ```
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let taken = take_by(
+            &mut self.root,
+            |entry: &KeyValue<K, V>| key.cmp(&entry.key),
+            &mut self.metrics,
+        )?;
+        self.len -= 1;
+
+        Some(taken.value)
+    }
+
+    /// Checks the red-black invariants, see [`RBTreeSet::check_invariants`].
+    ///
+    /// [`RBTreeSet::check_invariants`]: crate::rb_tree::RBTreeSet::check_invariants
+    pub fn check_invariants(&self) -> Result<usize, Violation> {
+        check(&self.root)
+    }
+
+    /// Returns an `Iterator` over the entries of the map, ordered by key.
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            inner: InorderIter::new(&self.root),
+        }
+    }
+
+    /// Returns an `Iterator` over the entries whose keys lie in `range`, ordered by key.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeMap;
+    /// let map = (0..10).map(|key| (key, key * 10)).collect::<RBTreeMap<_, _>>();
+    ///
+    /// assert_eq!(
+    ///     map.range(3..6).collect::<Vec<_>>(),
+    ///     vec![(&3, &30), (&4, &40), (&5, &50)]
+    /// );
+    /// ```
+    pub fn range<R: RangeBounds<K>>(&self, range: R) -> Range<'_, K, V, R> {
+        let inner = InorderIter::starting_at(&self.root, |entry: &KeyValue<K, V>| {
+            after_start(range.start_bound(), &entry.key)
+        });
+
+        Range { inner, range }
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<K: Ord, V> RBTreeMap<K, V> {
+    /// Returns the comparisons made by `insert` and `remove` and the rotations done
+    /// while rebalancing since creation or the last reset.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets all metrics back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+}
+
+impl<K: Ord, V> Default for RBTreeMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for RBTreeMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+
+        for (key, value) in iter {
+            map.insert(key, value);
+        }
+
+        map
+    }
+}
+
+impl<K: Ord + std::fmt::Debug, V: std::fmt::Debug> std::fmt::Debug for RBTreeMap<K, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An in-order Iterator over the entries of an `RBTreeMap`.
+/// Instances are created by [`RBTreeMap::iter()`].
+pub struct Iter<'a, K, V> {
+    inner: InorderIter<'a, KeyValue<K, V>>,
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.inner.next()?;
+        Some((&entry.key, &entry.value))
+    }
+}
+
+/// An in-order Iterator over the entries of an `RBTreeMap` within a range of keys.
+/// Instances are created by [`RBTreeMap::range()`].
+pub struct Range<'a, K, V, R> {
+    inner: InorderIter<'a, KeyValue<K, V>>,
+    range: R,
+}
+
+impl<'a, K: Ord, V, R: RangeBounds<K>> Iterator for Range<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.inner.next()?;
+
+        if before_end(self.range.end_bound(), &entry.key) {
+            Some((&entry.key, &entry.value))
+        } else {
+            self.inner.finish();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::key_value::Keyed;
+    use rand::seq::SliceRandom;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeMap;
+
+    fn random_pairs(count: u32) -> Vec<(u32, i64)> {
+        let mut rng = thread_rng();
+        let mut keys: Vec<u32> = (0..count).collect();
+        keys.shuffle(&mut rng);
+
+        keys.into_iter()
+            .map(|key| (key, rng.gen_range(-1000, 1000)))
+            .collect()
+    }
+
+    #[test]
+    fn test_insert_get_remove() {
+        let mut map = RBTreeMap::new();
+        assert_eq!(map.insert(2, "two"), None);
+        assert_eq!(map.insert(1, "one"), None);
+        assert_eq!(map.insert(2, "zwei"), Some("two"));
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&2), Some(&"zwei"));
+        *map.get_mut(&1).unwrap() = "eins";
+        assert_eq!(map.get(&1), Some(&"eins"));
+        assert_eq!(map.remove(&1), Some("eins"));
+        assert_eq!(map.remove(&1), None);
+        assert!(!map.contains_key(&1));
+        assert_eq!(map.len(), 1);
+        assert_eq!(format!("{:?}", map), "{2: \"zwei\"}");
+    }
+
+    #[test]
+    fn test_insert_keeps_key() {
+        let mut map = RBTreeMap::new();
+        assert_eq!(map.insert(Keyed(1, "old"), "a"), None);
+        assert_eq!(map.insert(Keyed(1, "new"), "b"), Some("a"));
+
+        let (key, value) = map.iter().next().unwrap();
+        assert_eq!((key.1, *value), ("old", "b"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_against_btree_map() {
+        let mut rng = thread_rng();
+        let mut map = RBTreeMap::new();
+        let mut btree = BTreeMap::new();
+
+        for _ in 0..5000 {
+            let key = rng.gen_range(0, 300);
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(map.remove(&key), btree.remove(&key));
+            } else {
+                assert_eq!(map.insert(key, key * 3), btree.insert(key, key * 3));
+            }
+
+            assert_eq!(map.get(&key), btree.get(&key));
+        }
+
+        assert!(map.check_invariants().is_ok());
+        assert_eq!(map.len(), btree.len());
+        assert!(map.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_range_against_btree_map() {
+        let pairs = random_pairs(1000);
+        let map = pairs.iter().cloned().collect::<RBTreeMap<_, _>>();
+        let btree = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+        let mut rng = thread_rng();
+
+        for _ in 0..300 {
+            let start = rng.gen_range(0, 1100);
+            let end = rng.gen_range(start, 1100);
+
+            assert!(map.range(start..end).eq(btree.range(start..end)));
+            assert!(map.range(start..=end).eq(btree.range(start..=end)));
+            assert!(map.range(..end).eq(btree.range(..end)));
+            assert!(map.range(start..).eq(btree.range(start..)));
+        }
+
+        assert!(map.range(..).eq(btree.iter()));
+    }
+}
@@ -0,0 +1,7 @@
+pub mod map;
+pub mod set;
+mod tree;
+
+pub use map::RBTreeMap;
+pub use set::RBTreeSet;
+pub use tree::Violation;
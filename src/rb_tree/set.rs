@@ -0,0 +1,471 @@
+use crate::key_value::{after_start, before_end};
+use crate::metrics::Metrics;
+use crate::rb_tree::tree::*;
+use crate::visualize::{DisplayNode, Visualize};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::iter::FromIterator;
+use std::ops::RangeBounds;
+
+/// An ordered set that keeps its values in a red-black tree. Compared to the
+/// [`AVLTreeSet`](crate::avl_tree::set::AVLTreeSet) the tree is balanced less strictly,
+/// but needs at most two rotations per insert and three per removal.
+#[derive(Debug, Clone)]
+pub struct RBTreeSet<T: Ord> {
+    root: RBTree<T>,
+    len: usize,
+    metrics: Metrics,
+}
+
+impl<T: Ord> RBTreeSet<T> {
+    /// Creates a new and empty `RBTreeSet`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let set = RBTreeSet::<u32>::new();
+    /// assert!(set.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            root: None,
+            len: 0,
+            metrics: Metrics::default(),
+        }
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds a value to the set and returns whether it was not present before. An equal
+    /// value that is already present is kept.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let mut set = RBTreeSet::new();
+    /// assert!(set.insert(1));
+    /// assert!(!set.insert(1));
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        let added = insert(&mut self.root, value, false, &mut self.metrics).is_none();
+
+        if added {
+            self.len += 1;
+        }
+
+        added
+    }
+
+    /// Adds a value to the set, replacing the equal value that may already be present.
+    /// Returns the replaced value.
+    pub fn replace(&mut self, value: T) -> Option<T> {
+        let replaced = insert(&mut self.root, value, true, &mut self.metrics);
+
+        if replaced.is_none() {
+            self.len += 1;
+        }
+
+        replaced
+    }
+
+    /// Removes a value from the set and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let mut set = RBTreeSet::new();
+    /// set.insert(1);
+    /// assert_eq!(set.take(&1), Some(1));
+    /// assert_eq!(set.take(&1), None);
+    /// ```
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let taken = take_by(
+            &mut self.root,
+            |node_value| value.cmp(node_value),
+            &mut self.metrics,
+        )?;
+        self.len -= 1;
+
+        Some(taken)
+    }
+
+    /// Removes a value from the set and returns whether it was present.
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Checks if the set contains a value.
+    pub fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    /// Returns a reference to the value in the set that is equal to `value`.
+    pub fn get(&self, value: &T) -> Option<&T> {
+        let mut current_tree = &self.root;
+
+        while let Some(current_node) = current_tree {
+            match value.cmp(&current_node.value) {
+                Ordering::Less => current_tree = &current_node.left,
+                Ordering::Equal => return Some(&current_node.value),
+                Ordering::Greater => current_tree = &current_node.right,
+            }
+        }
+
+        None
+    }
+
+    /// Removes all values from the set.
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Checks that no red node has a red child and that every path from the root passes the
+    /// same number of black nodes. Returns that number, counting the empty trees below the
+    /// leaves as black.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let set = (0..1000).collect::<RBTreeSet<_>>();
+    /// assert!(set.check_invariants().is_ok());
+    /// ```
+    pub fn check_invariants(&self) -> Result<usize, Violation> {
+        check(&self.root)
+    }
+
+    /// Returns an `Iterator` over the values of the set in ascending order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let set = vec![3, 1, 2].into_iter().collect::<RBTreeSet<_>>();
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            inner: InorderIter::new(&self.root),
+        }
+    }
+
+    /// Returns an `Iterator` over the values of the set that lie in `range`, in ascending order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::rb_tree::RBTreeSet;
+    /// let set = (0..10).collect::<RBTreeSet<_>>();
+    /// assert_eq!(set.range(3..6).collect::<Vec<_>>(), vec![&3, &4, &5]);
+    /// ```
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Range<'_, T, R> {
+        let inner =
+            InorderIter::starting_at(&self.root, |value| after_start(range.start_bound(), value));
+
+        Range { inner, range }
+    }
+}
+
+/// Two sets are equal if their trees have the same shape, colors and values, no matter
+/// what their metrics recorded.
+impl<T: Ord> PartialEq for RBTreeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<T: Ord> RBTreeSet<T> {
+    /// Returns the comparisons made by `insert` and `take` and the rotations done
+    /// while rebalancing since creation or the last reset.
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+
+    /// Sets all metrics back to zero.
+    pub fn reset_metrics(&mut self) {
+        self.metrics.reset();
+    }
+}
+
+impl<T: Ord> FromIterator<T> for RBTreeSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for i in iter {
+            set.insert(i);
+        }
+
+        set
+    }
+}
+
+impl<T: Ord> Default for RBTreeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Display> Visualize for RBTreeSet<T> {
+    fn display_tree(&self) -> Option<DisplayNode> {
+        self.root.as_deref().map(display_node)
+    }
+}
+
+/// Labels every node with its value and color, like `5:B`.
+fn display_node<T: Display>(root: &RBNode<T>) -> DisplayNode {
+    DisplayNode::from_tree(
+        root,
+        |node| {
+            let color = match node.color {
+                Color::Red => "R",
+                Color::Black => "B",
+            };
+
+            format!("{}:{}", node.value, color)
+        },
+        |node| (node.left.as_deref(), node.right.as_deref()),
+    )
+}
+
+/// An in-order Iterator over the values of an `RBTreeSet`.
+/// Instances are created by [`RBTreeSet::iter()`].
+pub struct Iter<'a, T> {
+    inner: InorderIter<'a, T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// An in-order Iterator over the values of an `RBTreeSet` within a range.
+/// Instances are created by [`RBTreeSet::range()`].
+pub struct Range<'a, T, R> {
+    inner: InorderIter<'a, T>,
+    range: R,
+}
+
+impl<'a, T: Ord, R: RangeBounds<T>> Iterator for Range<'a, T, R> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.inner.next()?;
+
+        if before_end(self.range.end_bound(), value) {
+            Some(value)
+        } else {
+            self.inner.finish();
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::key_value::Keyed;
+    use rand::seq::SliceRandom;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_rb_insert_and_remove_basic() {
+        let mut tree = RBTreeSet::new();
+        tree.insert(50);
+        tree.insert(70);
+        tree.insert(90);
+        assert!(!tree.insert(90));
+        assert!(tree.contains(&50));
+        assert!(tree.contains(&70));
+        assert!(tree.contains(&90));
+        tree.remove(&70);
+        assert!(!tree.contains(&70));
+        assert_eq!(tree.get(&50).unwrap(), &50);
+        assert_eq!(tree.check_invariants(), Ok(2));
+    }
+
+    #[test]
+    fn test_insert_randomly() {
+        let rb = (1..50_u8).collect::<RBTreeSet<_>>();
+        let btree = (1..50_u8).collect::<BTreeSet<_>>();
+
+        assert!(rb.check_invariants().is_ok());
+        assert!(rb.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_delete_somehow_randomly() {
+        let mut rb = (1..100_u16).collect::<RBTreeSet<_>>();
+        let mut btree = (1..100_u16).collect::<BTreeSet<_>>();
+
+        for value in [45, 12, 36, 73, 75, 80].iter() {
+            assert_eq!(rb.remove(value), btree.remove(value));
+            assert!(rb.check_invariants().is_ok());
+        }
+
+        assert!(rb.contains(&90));
+        assert_eq!(rb.get(&90).unwrap(), &90);
+        assert!(rb.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn truly_random_insert() {
+        let mut vec: Vec<u32> = (0..10000).collect();
+        vec.shuffle(&mut thread_rng());
+        let rb = vec.iter().collect::<RBTreeSet<_>>();
+        let btree = vec.iter().collect::<BTreeSet<_>>();
+
+        assert!(rb.check_invariants().is_ok());
+        assert!(rb.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn random_remove() {
+        let mut vec: Vec<u32> = (0..100000).collect();
+        vec.shuffle(&mut thread_rng());
+        let mut rb = vec.iter().collect::<RBTreeSet<_>>();
+        let mut btree = vec.iter().collect::<BTreeSet<_>>();
+
+        let mut remove: Vec<u32> = (0..10000).collect();
+        remove.shuffle(&mut thread_rng());
+        for item in remove.iter() {
+            rb.remove(&item);
+            btree.remove(&item);
+        }
+
+        assert!(rb.check_invariants().is_ok());
+        assert_eq!(rb.len(), btree.len());
+        assert!(rb.iter().eq(btree.iter()));
+    }
+
+    #[test]
+    fn test_invariants_after_every_operation() {
+        let mut rng = thread_rng();
+        let mut rb = RBTreeSet::new();
+        let mut btree = BTreeSet::new();
+
+        for _ in 0..3000 {
+            let value = rng.gen_range(0, 300);
+            if rng.gen_range(0, 3) == 0 {
+                assert_eq!(rb.take(&value), btree.take(&value));
+            } else {
+                assert_eq!(rb.insert(value), btree.insert(value));
+            }
+
+            assert!(rb.check_invariants().is_ok());
+        }
+
+        while let Some(value) = btree.pop_first() {
+            assert_eq!(rb.take(&value), Some(value));
+            assert!(rb.check_invariants().is_ok());
+        }
+        assert!(rb.is_empty());
+    }
+
+    #[test]
+    fn test_sorted_insert_height() {
+        let rb = (0..(1 << 16)).collect::<RBTreeSet<u32>>();
+        let height = rb.display_tree().unwrap().height;
+
+        // The longest path is at most twice the shortest one
+        assert!(height <= 2 * 17);
+        assert!(rb.check_invariants().is_ok());
+    }
+
+    #[test]
+    fn test_range() {
+        let mut vec: Vec<u32> = (0..1000).collect();
+        vec.shuffle(&mut thread_rng());
+        let rb = vec.iter().copied().collect::<RBTreeSet<_>>();
+        let btree = vec.iter().copied().collect::<BTreeSet<_>>();
+        let mut rng = thread_rng();
+
+        for _ in 0..300 {
+            let start = rng.gen_range(0, 1100);
+            let end = rng.gen_range(start, 1100);
+
+            assert!(rb.range(start..end).eq(btree.range(start..end)));
+            assert!(rb.range(start..=end).eq(btree.range(start..=end)));
+            assert!(rb.range(..end).eq(btree.range(..end)));
+            assert!(rb.range(start..).eq(btree.range(start..)));
+        }
+    }
+
+    #[test]
+    fn test_check_invariants_detects_violations() {
+        let mut rb = (0..10).collect::<RBTreeSet<_>>();
+
+        rb.root.as_mut().unwrap().color = Color::Red;
+        assert_eq!(rb.check_invariants(), Err(Violation::RedRoot));
+        rb.root.as_mut().unwrap().color = Color::Black;
+
+        // Any other node changes the black height of its paths when recolored
+        let left = rb.root.as_mut().unwrap().left.as_mut().unwrap();
+        left.color = match left.color {
+            Color::Red => Color::Black,
+            Color::Black => Color::Red,
+        };
+        assert!(rb.check_invariants().is_err());
+
+        let mut rb = (0..10).collect::<RBTreeSet<_>>();
+        rb.root.as_mut().unwrap().value = 100;
+        assert_eq!(rb.check_invariants(), Err(Violation::Unordered));
+
+        // The last node of the left subtree stays greater than its parent, but not smaller
+        // than the root
+        let mut rb = (0..10).collect::<RBTreeSet<_>>();
+        let root = rb.root.as_mut().unwrap();
+        let mut node = root.left.as_mut().unwrap();
+        while node.right.is_some() {
+            node = node.right.as_mut().unwrap();
+        }
+        node.value = root.value + 1;
+        assert_eq!(rb.check_invariants(), Err(Violation::Unordered));
+    }
+
+    #[test]
+    fn test_replace() {
+        let mut rb = RBTreeSet::new();
+        assert!(rb.replace(Keyed(1, "old")).is_none());
+        assert!(!rb.insert(Keyed(1, "ignored")));
+        assert_eq!(rb.get(&Keyed(1, "")).unwrap().1, "old");
+
+        let replaced = rb.replace(Keyed(1, "new")).unwrap();
+        assert_eq!(replaced.1, "old");
+        assert_eq!(rb.get(&Keyed(1, "")).unwrap().1, "new");
+        assert_eq!(rb.len(), 1);
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_metrics() {
+        let mut rb = RBTreeSet::new();
+        rb.insert(1);
+        rb.insert(2);
+        assert_eq!(rb.metrics().comparisons(), 1);
+        assert_eq!(rb.metrics().rotations(), 0);
+
+        // Black uncle on the outside, a single rotation
+        rb.insert(3);
+        assert_eq!(rb.metrics().comparisons(), 3);
+        assert_eq!(rb.metrics().rotations(), 1);
+
+        rb.reset_metrics();
+
+        // Red uncle, recoloring only
+        rb.insert(5);
+        assert_eq!(rb.metrics().rotations(), 0);
+
+        // Black uncle on the inside, a double rotation
+        rb.insert(4);
+        assert_eq!(rb.metrics().rotations(), 2);
+
+        // Comparing sets ignores the metrics
+        assert_eq!(rb, (1..=5).collect::<RBTreeSet<_>>());
+    }
+}
@@ -0,0 +1,409 @@
+use crate::metrics::Metrics;
+use std::cmp::Ordering;
+use std::fmt;
+use std::mem::replace;
+
+/// The color of a node. Every path from a node down to an empty tree passes the same number
+/// of black nodes and no red node has a red child, which keeps the longest path at most
+/// twice as long as the shortest one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Black,
+}
+
+/// A node of a red-black tree.
+#[derive(Debug, PartialEq, Clone)]
+pub struct RBNode<T> {
+    pub value: T,
+    pub left: RBTree<T>,
+    pub right: RBTree<T>,
+    pub color: Color,
+}
+
+pub type RBTree<T> = Option<Box<RBNode<T>>>;
+
+/// A broken invariant found by [`RBTreeSet::check_invariants`](super::RBTreeSet::check_invariants)
+/// or [`RBTreeMap::check_invariants`](super::RBTreeMap::check_invariants).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// The root is red.
+    RedRoot,
+    /// A red node has a red child.
+    RedRed,
+    /// Two paths from the same node pass a different number of black nodes.
+    BlackHeight,
+    /// A value in a left subtree is not smaller or one in a right subtree is not greater than
+    /// the value above it.
+    Unordered,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Violation::RedRoot => "the root is red",
+            Violation::RedRed => "a red node has a red child",
+            Violation::BlackHeight => "the black height differs between paths",
+            Violation::Unordered => "the values are not ordered",
+        };
+
+        f.write_str(description)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl<T> RBNode<T> {
+    /// Creates a new red leaf.
+    pub fn new(value: T) -> Self {
+        RBNode {
+            value,
+            left: None,
+            right: None,
+            color: Color::Red,
+        }
+    }
+
+    fn child(&self, side: Side) -> &RBTree<T> {
+        match side {
+            Side::Left => &self.left,
+            Side::Right => &self.right,
+        }
+    }
+
+    fn child_mut(&mut self, side: Side) -> &mut RBTree<T> {
+        match side {
+            Side::Left => &mut self.left,
+            Side::Right => &mut self.right,
+        }
+    }
+}
+
+fn is_red<T>(tree: &RBTree<T>) -> bool {
+    tree.as_ref().is_some_and(|node| node.color == Color::Red)
+}
+
+fn set_color<T>(tree: &mut RBTree<T>, color: Color) {
+    if let Some(node) = tree {
+        node.color = color;
+    }
+}
+
+/// Rotates the root of `tree` down towards `side`, its child on the other side takes its place.
+fn rotate<T>(tree: &mut RBTree<T>, side: Side, metrics: &mut Metrics) {
+    let other = side.opposite();
+    let mut node = tree.take().unwrap();
+    let mut child = node.child_mut(other).take().unwrap();
+
+    *node.child_mut(other) = child.child_mut(side).take();
+    *child.child_mut(side) = Some(node);
+    *tree = Some(child);
+
+    metrics.count_rotations(1);
+}
+
+/// Inserts `value` into the tree. If an equal value is already present, it is replaced when
+/// `replace_equal` is set and returned, otherwise `value` is handed back. Returns `None` if the
+/// value was added.
+pub fn insert<T: Ord>(
+    root: &mut RBTree<T>,
+    value: T,
+    replace_equal: bool,
+    metrics: &mut Metrics,
+) -> Option<T> {
+    let rejected = insert_below(root, value, replace_equal, metrics);
+    set_color(root, Color::Black);
+    rejected
+}
+
+fn insert_below<T: Ord>(
+    tree: &mut RBTree<T>,
+    value: T,
+    replace_equal: bool,
+    metrics: &mut Metrics,
+) -> Option<T> {
+    let node = match tree {
+        Some(node) => node,
+        None => {
+            *tree = Some(Box::new(RBNode::new(value)));
+            return None;
+        }
+    };
+
+    metrics.count_comparison();
+
+    let rejected = match value.cmp(&node.value) {
+        Ordering::Less => insert_below(&mut node.left, value, replace_equal, metrics),
+        Ordering::Greater => insert_below(&mut node.right, value, replace_equal, metrics),
+        Ordering::Equal if replace_equal => return Some(replace(&mut node.value, value)),
+        Ordering::Equal => return Some(value),
+    };
+
+    if rejected.is_none() {
+        fix_insert(tree, metrics);
+    }
+
+    rejected
+}
+
+/// Repairs a red child with a red grandchild below the root of `tree`. A red uncle is
+/// solved by recoloring, which may move the violation up by two levels. Otherwise at most
+/// two rotations solve it for good.
+fn fix_insert<T>(tree: &mut RBTree<T>, metrics: &mut Metrics) {
+    let node = tree.as_mut().unwrap();
+
+    let violation = [Side::Left, Side::Right].iter().copied().find(|side| {
+        node.child(*side).as_ref().is_some_and(|child| {
+            child.color == Color::Red && (is_red(&child.left) || is_red(&child.right))
+        })
+    });
+
+    let side = match violation {
+        Some(side) => side,
+        None => return,
+    };
+    let other = side.opposite();
+
+    if is_red(node.child(other)) {
+        node.color = Color::Red;
+        set_color(&mut node.left, Color::Black);
+        set_color(&mut node.right, Color::Black);
+        return;
+    }
+
+    // An inner grandchild is rotated to the outside first
+    let child = node.child_mut(side);
+    if is_red(child.as_ref().unwrap().child(other)) {
+        rotate(child, side, metrics);
+    }
+
+    rotate(tree, other, metrics);
+
+    let node = tree.as_mut().unwrap();
+    node.color = Color::Black;
+    set_color(node.child_mut(other), Color::Red);
+}
+
+/// Removes the value `cmp` returns `Ordering::Equal` for and returns it. `cmp` compares the
+/// searched value with the value of a node.
+pub fn take_by<T, F: FnMut(&T) -> Ordering>(
+    root: &mut RBTree<T>,
+    mut cmp: F,
+    metrics: &mut Metrics,
+) -> Option<T> {
+    let (taken, _) = take_below(root, &mut cmp, metrics)?;
+    set_color(root, Color::Black);
+    Some(taken)
+}
+
+/// Returns the removed value and whether the black height of `tree` decreased.
+fn take_below<T, F: FnMut(&T) -> Ordering>(
+    tree: &mut RBTree<T>,
+    cmp: &mut F,
+    metrics: &mut Metrics,
+) -> Option<(T, bool)> {
+    let node = tree.as_mut()?;
+    metrics.count_comparison();
+
+    let side = match cmp(&node.value) {
+        Ordering::Less => Side::Left,
+        Ordering::Greater => Side::Right,
+        Ordering::Equal if node.left.is_some() && node.right.is_some() => {
+            // The inorder successor takes the place of the value
+            let (successor, shrunk) = take_min(&mut node.right, metrics);
+            let taken = replace(&mut node.value, successor);
+
+            return Some((taken, shrunk && fix_shrunk(tree, Side::Right, metrics)));
+        }
+        Ordering::Equal => return Some(unlink(tree)),
+    };
+
+    let (taken, shrunk) = take_below(node.child_mut(side), cmp, metrics)?;
+
+    Some((taken, shrunk && fix_shrunk(tree, side, metrics)))
+}
+
+/// Removes the smallest value of a non-empty tree. Returns it and whether the black height
+/// of `tree` decreased.
+fn take_min<T>(tree: &mut RBTree<T>, metrics: &mut Metrics) -> (T, bool) {
+    let node = tree.as_mut().unwrap();
+
+    if node.left.is_none() {
+        return unlink(tree);
+    }
+
+    let (taken, shrunk) = take_min(&mut node.left, metrics);
+
+    (taken, shrunk && fix_shrunk(tree, Side::Left, metrics))
+}
+
+/// Removes the root of `tree`, which has at most one child. Such a child is a red leaf and
+/// takes the place of the black root. Returns the value and whether the black height decreased.
+fn unlink<T>(tree: &mut RBTree<T>) -> (T, bool) {
+    let mut node = tree.take().unwrap();
+
+    match node.left.take().or_else(|| node.right.take()) {
+        Some(mut child) => {
+            child.color = Color::Black;
+            *tree = Some(child);
+            (node.value, false)
+        }
+        None => (node.value, node.color == Color::Black),
+    }
+}
+
+/// Repairs the tree after the black height of the subtree on `side` decreased by one.
+/// Returns whether the black height of the whole `tree` decreased.
+fn fix_shrunk<T>(tree: &mut RBTree<T>, side: Side, metrics: &mut Metrics) -> bool {
+    let other = side.opposite();
+    let node = tree.as_mut().unwrap();
+
+    // A red sibling is rotated up, leaving a black sibling below the now red parent
+    if is_red(node.child(other)) {
+        rotate(tree, side, metrics);
+
+        let node = tree.as_mut().unwrap();
+        node.color = Color::Black;
+
+        let lowered = node.child_mut(side);
+        set_color(lowered, Color::Red);
+        fix_shrunk(lowered, side, metrics);
+
+        return false;
+    }
+
+    // The other side has a higher black height, so the sibling exists
+    let parent_color = node.color;
+    let sibling = node.child_mut(other).as_mut().unwrap();
+    let near_red = is_red(sibling.child(side));
+    let far_red = is_red(sibling.child(other));
+
+    // Black sibling with black children, turn it red to even the black heights
+    if !near_red && !far_red {
+        sibling.color = Color::Red;
+        node.color = Color::Black;
+
+        return parent_color == Color::Black;
+    }
+
+    // A red inner nephew is rotated to the outside first
+    if !far_red {
+        let sibling = node.child_mut(other);
+        rotate(sibling, other, metrics);
+        set_color(sibling, Color::Black);
+        set_color(sibling.as_mut().unwrap().child_mut(other), Color::Red);
+    }
+
+    // A red outer nephew, the sibling takes the place of the parent
+    rotate(tree, side, metrics);
+
+    let node = tree.as_mut().unwrap();
+    node.color = parent_color;
+    set_color(&mut node.left, Color::Black);
+    set_color(&mut node.right, Color::Black);
+
+    false
+}
+
+/// Checks all red-black invariants and the order of the values. Returns the black height.
+pub fn check<T: Ord>(root: &RBTree<T>) -> Result<usize, Violation> {
+    if is_red(root) {
+        return Err(Violation::RedRoot);
+    }
+
+    check_below(root, None, None)
+}
+
+/// Checks the subtree, whose values all have to lie between `lower` and `upper`.
+fn check_below<T: Ord>(
+    tree: &RBTree<T>,
+    lower: Option<&T>,
+    upper: Option<&T>,
+) -> Result<usize, Violation> {
+    let node = match tree {
+        Some(node) => node,
+        None => return Ok(1),
+    };
+
+    if node.color == Color::Red && (is_red(&node.left) || is_red(&node.right)) {
+        return Err(Violation::RedRed);
+    }
+
+    let above_lower = lower.is_none_or(|lower| node.value > *lower);
+    let below_upper = upper.is_none_or(|upper| node.value < *upper);
+    if !above_lower || !below_upper {
+        return Err(Violation::Unordered);
+    }
+
+    let left_height = check_below(&node.left, lower, Some(&node.value))?;
+    if left_height != check_below(&node.right, Some(&node.value), upper)? {
+        return Err(Violation::BlackHeight);
+    }
+
+    Ok(left_height + (node.color == Color::Black) as usize)
+}
+
+/// An in-order Iterator over the nodes of a tree.
+pub struct InorderIter<'a, T> {
+    stack: Vec<&'a RBNode<T>>,
+}
+
+impl<'a, T> InorderIter<'a, T> {
+    pub fn new(tree: &'a RBTree<T>) -> Self {
+        InorderIter::starting_at(tree, |_| true)
+    }
+
+    /// Starts at the first value `after_start` returns `true` for. `after_start` has to be
+    /// monotonic in the values.
+    pub fn starting_at<F: FnMut(&T) -> bool>(tree: &'a RBTree<T>, mut after_start: F) -> Self {
+        let mut stack = Vec::new();
+        let mut current = tree;
+
+        while let Some(node) = current {
+            if after_start(&node.value) {
+                stack.push(&**node);
+                current = &node.left;
+            } else {
+                current = &node.right;
+            }
+        }
+
+        InorderIter { stack }
+    }
+
+    /// Stops the iteration.
+    pub fn finish(&mut self) {
+        self.stack.clear();
+    }
+}
+
+impl<'a, T> Iterator for InorderIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        let mut current = &node.right;
+
+        while let Some(right) = current {
+            self.stack.push(right);
+            current = &right.left;
+        }
+
+        Some(&node.value)
+    }
+}
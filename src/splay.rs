@@ -0,0 +1,386 @@
+//! A safe splay tree.
+//!
+//! The `splay tree` is a self-adjusting binary search tree: every access moves
+//! the accessed element to the root. Frequently used elements therefore stay
+//! close to the root, which pays off for skewed access patterns. Operations
+//! take amortized `O(log n)`, although a single one may walk a long path.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use super::binary_tree::{Link, Node};
+use super::stack::Stack;
+use super::visualize::{DisplayNode, Visualize};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::iter::FromIterator;
+
+/// A splay tree build from Nodes. This struct represents a splay tree holding a root node.
+pub struct SplayTree<T> {
+    head: Link<T>,
+    len: usize,
+}
+
+/// An Iterator visiting a splay tree inorder, containing a stack with the nodes on the path
+/// to the next element. Iterating does not splay. Instances are created by [`SplayTree::iter()`].
+pub struct Iter<'a, T: 'a> {
+    visited: Stack<&'a Node<T>>,
+}
+
+impl<T: Ord> SplayTree<T> {
+    /// Creates a new and empty `SplayTree`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let splay_tree = SplayTree::<()>::new();
+    /// assert!(splay_tree.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        SplayTree { head: None, len: 0 }
+    }
+
+    /// Returns the number of elements in the tree.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Returns the element at the root, which is the one accessed last.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// splay_tree.insert(1);
+    /// splay_tree.insert(2);
+    /// assert_eq!(splay_tree.root(), Some(&2));
+    ///
+    /// splay_tree.contains(1);
+    /// assert_eq!(splay_tree.root(), Some(&1));
+    /// ```
+    pub fn root(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+
+    /// Inserts a new element into the tree and returns whether it was not present before.
+    /// The new element becomes the root.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// assert!(splay_tree.insert(1));
+    /// assert!(!splay_tree.insert(1));
+    /// assert_eq!(splay_tree.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        let mut node = Box::new(Node::new(value));
+
+        if let Some(mut root) = self.head.take() {
+            root = Node::splay(root, &node.value);
+
+            match node.value.cmp(&root.value) {
+                Ordering::Equal => {
+                    self.head = Some(root);
+                    return false;
+                }
+                // The root is the closest element, so it splits the tree at the new value
+                Ordering::Less => {
+                    node.left = root.left.take();
+                    node.right = Some(root);
+                }
+                Ordering::Greater => {
+                    node.right = root.right.take();
+                    node.left = Some(root);
+                }
+            }
+        }
+
+        self.head = Some(node);
+        self.len += 1;
+        true
+    }
+
+    /// Removes an element from the tree and returns whether it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// splay_tree.insert(1);
+    ///
+    /// assert!(splay_tree.remove(&1));
+    /// assert!(!splay_tree.remove(&1));
+    /// ```
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Removes an element from the tree and returns it. After splaying it to the root, the
+    /// largest element of its left subtree is splayed up to take its place.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// splay_tree.insert(1);
+    ///
+    /// assert_eq!(splay_tree.take(&1), Some(1));
+    /// assert_eq!(splay_tree.take(&1), None);
+    /// ```
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        let mut root = Node::splay(self.head.take()?, value);
+
+        if root.value != *value {
+            self.head = Some(root);
+            return None;
+        }
+
+        self.head = match root.left.take() {
+            None => root.right.take(),
+            Some(left) => {
+                // Every element on the left is smaller, so the largest ends up on top
+                // without a right child
+                let mut left = Node::splay(left, value);
+                left.right = root.right.take();
+                Some(left)
+            }
+        };
+
+        self.len -= 1;
+        Some(root.value)
+    }
+
+    /// Searches for an element in the tree and returns whether the tree contains it. The
+    /// element, or the last one visited if it's missing, is splayed to the root.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// splay_tree.insert(1);
+    ///
+    /// assert!(splay_tree.contains(1));
+    /// assert!(!splay_tree.contains(2));
+    /// ```
+    pub fn contains(&mut self, value: T) -> bool {
+        match self.head.take() {
+            None => false,
+            Some(root) => {
+                let root = Node::splay(root, &value);
+                let found = root.value == value;
+                self.head = Some(root);
+                found
+            }
+        }
+    }
+
+    /// Returns an `Iterator` over the elements of the tree in ascending order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::splay::SplayTree;
+    /// let mut splay_tree = SplayTree::new();
+    /// splay_tree.insert(10);
+    /// splay_tree.insert(8);
+    /// splay_tree.insert(11);
+    ///
+    /// assert_eq!(splay_tree.iter().collect::<Vec<_>>(), vec![&8, &10, &11]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            visited: Stack::new(),
+        };
+        iter.push_left(&self.head);
+        iter
+    }
+}
+
+impl<T: Ord> Default for SplayTree<T> {
+    fn default() -> Self {
+        SplayTree::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for SplayTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut splay_tree = SplayTree::new();
+
+        for value in iter {
+            splay_tree.insert(value);
+        }
+
+        splay_tree
+    }
+}
+
+impl<T> Drop for SplayTree<T> {
+    /// Splay trees can degenerate into long paths, so the nodes are freed iteratively.
+    fn drop(&mut self) {
+        Node::drop_tree(self.head.take());
+    }
+}
+
+impl<T: Ord + Display> Visualize for SplayTree<T> {
+    fn display_tree(&self) -> Option<DisplayNode> {
+        self.head.as_deref().map(Node::display_node)
+    }
+}
+
+impl<T: Ord> Node<T> {
+    /// Top-down splaying: moves `value`, or the last node on its search path, to the root
+    /// in a single pass. Nodes passed on the way are hung into a left tree, holding everything
+    /// smaller, and a right tree, holding everything greater. Both get attached to the new
+    /// root at the end.
+    fn splay(mut root: Box<Node<T>>, value: &T) -> Box<Node<T>> {
+        let mut left_tree: Link<T> = None;
+        let mut right_tree: Link<T> = None;
+        // The free slots where the next node is attached, the rightmost of the left tree and
+        // the leftmost of the right tree
+        let mut left_slot = &mut left_tree;
+        let mut right_slot = &mut right_tree;
+
+        loop {
+            match value.cmp(&root.value) {
+                Ordering::Equal => break,
+                Ordering::Less => {
+                    let mut child = match root.left.take() {
+                        Some(child) => child,
+                        None => break,
+                    };
+
+                    if *value < child.value {
+                        // Zig-zig, rotate right first
+                        root.left = child.right.take();
+                        child.right = Some(root);
+                        root = child;
+
+                        child = match root.left.take() {
+                            Some(child) => child,
+                            None => break,
+                        };
+                    }
+
+                    *right_slot = Some(root);
+                    right_slot = &mut right_slot.as_mut().unwrap().left;
+                    root = child;
+                }
+                Ordering::Greater => {
+                    let mut child = match root.right.take() {
+                        Some(child) => child,
+                        None => break,
+                    };
+
+                    if *value > child.value {
+                        // Zag-zag, rotate left first
+                        root.right = child.left.take();
+                        child.left = Some(root);
+                        root = child;
+
+                        child = match root.right.take() {
+                            Some(child) => child,
+                            None => break,
+                        };
+                    }
+
+                    *left_slot = Some(root);
+                    left_slot = &mut left_slot.as_mut().unwrap().right;
+                    root = child;
+                }
+            }
+        }
+
+        *left_slot = root.left.take();
+        *right_slot = root.right.take();
+        root.left = left_tree;
+        root.right = right_tree;
+
+        root
+    }
+}
+
+impl<'a, T: Eq> Iter<'a, T> {
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.visited.push(node.as_ref());
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, T: Eq> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.visited.pop()?;
+        self.push_left(&node.right);
+
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{thread_rng, Rng};
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = thread_rng();
+        let mut sut = SplayTree::new();
+        let mut model = BTreeSet::new();
+
+        for _ in 0..5000 {
+            let value = rng.gen_range(0, 500);
+            match rng.gen_range(0, 3) {
+                0 => assert_eq!(sut.insert(value), model.insert(value)),
+                1 => assert_eq!(sut.take(&value), model.take(&value)),
+                _ => assert_eq!(sut.contains(value), model.contains(&value)),
+            }
+        }
+
+        assert_eq!(sut.len(), model.len());
+        assert!(sut.iter().eq(model.iter()));
+    }
+
+    #[test]
+    fn test_access_moves_to_root() {
+        let mut sut = (0..100).collect::<SplayTree<_>>();
+
+        for value in [42, 0, 99, 42, 17].iter() {
+            assert!(sut.contains(*value));
+            assert_eq!(sut.root(), Some(value));
+        }
+
+        // A missing element splays a neighbour up
+        assert!(!sut.contains(100));
+        assert_eq!(sut.root(), Some(&99));
+
+        sut.remove(&99);
+        assert_eq!(sut.root(), Some(&98));
+        assert!(sut.iter().copied().eq(0..99));
+    }
+
+    #[test]
+    fn test_splay_halves_depth() {
+        // Sorted inserts build a path, splaying its deepest node roughly halves its depth
+        let mut sut = (0..1024).collect::<SplayTree<_>>();
+        assert_eq!(sut.display_tree().unwrap().height, 1024);
+
+        sut.contains(0);
+        assert_eq!(sut.root(), Some(&0));
+        assert!(sut.display_tree().unwrap().height <= 514);
+        assert!(sut.iter().copied().eq(0..1024));
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // Neither splaying nor dropping a path shaped tree may recurse
+        let mut sut = SplayTree::new();
+        for value in 0..1_000_000 {
+            sut.insert(value);
+        }
+
+        assert!(sut.contains(0));
+        assert!(sut.contains(999_999));
+        assert_eq!(sut.len(), 1_000_000);
+    }
+}
@@ -91,13 +91,13 @@ impl<T: Eq> Stack<T> {
     /// assert!(stack.contains(1));
     /// ```
     pub fn contains(&self, value: T) -> bool {
-        return if self.is_empty() {
+        if self.is_empty() {
             false
         } else if self.first.as_ref().unwrap().value == value {
             true
         } else {
             self.first.as_ref().unwrap().contains(value)
-        };
+        }
     }
 
     /// Pops the first element of a stack. Remember that the stacks first element is always the last one that got inserted (LIFO-Principle).
@@ -111,7 +111,7 @@ impl<T: Eq> Stack<T> {
     /// ```
     pub fn pop(&mut self) -> Option<T> {
         if self.is_empty() {
-            return None;
+            None
         } else {
             let mut old_head = self.first.take();
             self.first = old_head.as_mut().unwrap().next.take();
@@ -133,7 +133,7 @@ impl<T: Eq> Stack<T> {
     }
 
     /// Returns a non-owning iterator for the stack which iterates over the element in the LIFO way.
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         Iter { head: &self.first }
     }
 }
@@ -146,6 +146,18 @@ impl<T: Eq> Default for Stack<T> {
     }
 }
 
+impl<T> Drop for Stack<T> {
+    /// Unlinks the nodes one after another, dropping the boxes in a chain would recurse once
+    /// per node.
+    fn drop(&mut self) {
+        let mut current = self.first.take();
+
+        while let Some(mut node) = current {
+            current = node.next.take();
+        }
+    }
+}
+
 impl<T: Eq + Ord> From<LinkedList<T>> for Stack<T> {
     fn from(list: LinkedList<T>) -> Self {
         list.into_iter().collect()
@@ -170,14 +182,14 @@ impl<T: Eq> Node<T> {
 
     #[inline]
     fn contains(&self, value: T) -> bool {
-        return if self.value == value {
+        if self.value == value {
             true
         } else {
             match &self.next {
                 Some(node) => node.contains(value),
                 None => false,
             }
-        };
+        }
     }
 }
 
@@ -203,7 +215,7 @@ impl<'a, T> Iterator for Iter<'a, T> {
     }
 }
 
-impl<'a, T: Eq> Iterator for IntoIter<T> {
+impl<T: Eq> Iterator for IntoIter<T> {
     type Item = T;
 
     /// Returns the next element of a IntoIter.
@@ -212,9 +224,10 @@ impl<'a, T: Eq> Iterator for IntoIter<T> {
     }
 }
 
+#[cfg(test)]
 macro_rules! stack {
     () => {
-        Stack::new();
+        Stack::new()
     };
     ($($elem:expr),+) => {{
         let mut res = Stack::new();
@@ -267,18 +280,18 @@ mod test {
     #[test]
     fn test_contains() {
         let mut sut = Stack::new();
-        assert_eq!(sut.contains(42), false);
+        assert!(!sut.contains(42));
         sut.push(42);
-        assert_eq!(sut.contains(42), true);
+        assert!(sut.contains(42));
         sut.push(43);
         sut.push(44);
         sut.push(45);
         sut.push(46);
-        assert_eq!(sut.contains(43), true);
-        assert_eq!(sut.contains(44), true);
-        assert_eq!(sut.contains(45), true);
-        assert_eq!(sut.contains(46), true);
-        assert_eq!(sut.contains(47), false);
+        assert!(sut.contains(43));
+        assert!(sut.contains(44));
+        assert!(sut.contains(45));
+        assert!(sut.contains(46));
+        assert!(!sut.contains(47));
     }
 
     #[test]
@@ -322,6 +335,13 @@ mod test {
         assert_eq!(sut.size(), 0);
     }
 
+    #[test]
+    fn test_drop_long_stack() {
+        let count = if cfg!(miri) { 1_000 } else { 1_000_000 };
+        let sut: Stack<_> = (0..count).collect();
+        assert_eq!(sut.size(), count);
+    }
+
     #[test]
     fn test_from() {
         let mut list = LinkedList::new();
@@ -0,0 +1,546 @@
+//! A safe treap.
+//!
+//! The `treap` is a binary search tree on its values and a max-heap on random
+//! priorities given to every node on insertion. The random priorities keep the
+//! expected height at `O(log n)`, whatever order the values are inserted in.
+//! All operations are build from `split` and `merge`.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use super::stack::Stack;
+use super::visualize::{DisplayNode, Visualize};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::iter::FromIterator;
+
+/// A treap build from Nodes, drawing the priorities of new nodes from `R`.
+pub struct Treap<T, R = StdRng> {
+    head: Link<T>,
+    rng: R,
+}
+
+/// A Link between Nodes.
+type Link<T> = Option<Box<Node<T>>>;
+
+/// A Node in a treap which holds a reference to the left and right Nodes, a value, its
+/// priority and the number of nodes in its subtree.
+#[derive(Debug, Eq, PartialEq)]
+struct Node<T> {
+    left: Link<T>,
+    right: Link<T>,
+    value: T,
+    priority: u64,
+    size: usize,
+}
+
+/// An Iterator visiting a treap inorder, containing a stack with the nodes on the path
+/// to the next element. Instances are created by [`Treap::iter()`].
+pub struct Iter<'a, T: 'a> {
+    visited: Stack<&'a Node<T>>,
+}
+
+impl<T: Ord> Treap<T> {
+    /// Creates a new and empty `Treap` with an `StdRng` seeded from the operating system.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let treap = Treap::<()>::new();
+    /// assert!(treap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Treap::with_rng(StdRng::from_entropy())
+    }
+
+    /// Creates a new and empty `Treap` with an `StdRng` seeded from `seed`. Treaps with the
+    /// same seed and the same operations end up in the same shape.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(42);
+    /// treap.insert(1);
+    /// assert!(treap.contains(1));
+    /// ```
+    pub fn with_seed(seed: u64) -> Self {
+        Treap::with_rng(StdRng::seed_from_u64(seed))
+    }
+}
+
+impl<T: Ord, R: Rng> Treap<T, R> {
+    /// Creates a new and empty `Treap` drawing the priorities from `rng`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// use rand::rngs::mock::StepRng;
+    /// // Ascending priorities, every new node becomes the root
+    /// let mut treap = Treap::with_rng(StepRng::new(0, 1));
+    /// treap.insert(2);
+    /// treap.insert(1);
+    /// assert_eq!(treap.iter().collect::<Vec<_>>(), vec![&1, &2]);
+    /// ```
+    pub fn with_rng(rng: R) -> Self {
+        Treap { head: None, rng }
+    }
+
+    /// Returns the number of elements in the treap. Every node knows the size of its
+    /// subtree, so this takes `O(1)`.
+    pub fn len(&self) -> usize {
+        Node::size(&self.head)
+    }
+
+    /// Checks if the treap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.head.is_none()
+    }
+
+    /// Inserts a new element into the treap and returns whether it was not present before.
+    /// The treap is split at the new value and merged back together with the new node
+    /// in the middle.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(1);
+    /// assert!(treap.insert(1));
+    /// assert!(!treap.insert(1));
+    /// assert_eq!(treap.len(), 1);
+    /// ```
+    pub fn insert(&mut self, value: T) -> bool {
+        if Node::contains(&self.head, &value) {
+            return false;
+        }
+
+        let node = Box::new(Node::new(value, self.rng.gen()));
+        let (less, greater) = Node::split(self.head.take(), &node.value);
+        self.head = Node::merge(Node::merge(less, Some(node)), greater);
+        true
+    }
+
+    /// Removes an element from the treap and returns whether it was present.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(1);
+    /// treap.insert(1);
+    ///
+    /// assert!(treap.remove(&1));
+    /// assert!(!treap.remove(&1));
+    /// ```
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.take(value).is_some()
+    }
+
+    /// Removes an element from the treap and returns it. The children of the removed node
+    /// are merged into its place.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(1);
+    /// treap.insert(1);
+    ///
+    /// assert_eq!(treap.take(&1), Some(1));
+    /// assert_eq!(treap.take(&1), None);
+    /// ```
+    pub fn take(&mut self, value: &T) -> Option<T> {
+        Node::take(&mut self.head, value)
+    }
+
+    /// Searches for an element in the treap and returns whether the treap contains it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(1);
+    /// treap.insert(1);
+    ///
+    /// assert!(treap.contains(1));
+    /// assert!(!treap.contains(2));
+    /// ```
+    pub fn contains(&self, value: T) -> bool {
+        Node::contains(&self.head, &value)
+    }
+
+    /// Moves all elements greater than or equal to `value` into a new treap and returns it.
+    /// The new treap seeds its generator from the one of this treap. Takes expected `O(log n)`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = (0..10).collect::<Treap<_>>();
+    /// let upper = treap.split_off(&4);
+    ///
+    /// assert_eq!(treap.iter().collect::<Vec<_>>(), vec![&0, &1, &2, &3]);
+    /// assert_eq!(upper.len(), 6);
+    /// ```
+    pub fn split_off(&mut self, value: &T) -> Self
+    where
+        R: SeedableRng,
+    {
+        let (less, greater) = Node::split(self.head.take(), value);
+        self.head = less;
+
+        Treap {
+            head: greater,
+            rng: R::from_rng(&mut self.rng).expect("seeding from another generator failed"),
+        }
+    }
+
+    /// Moves all elements of `other` into this treap. Every element of `other` has to be
+    /// greater than every element of this treap. Takes expected `O(log n)`.
+    ///
+    /// Panics if the elements of the treaps are not ordered.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = (0..4).collect::<Treap<_>>();
+    /// treap.merge((4..10).collect());
+    ///
+    /// assert!(treap.iter().eq((0..10).collect::<Vec<_>>().iter()));
+    /// ```
+    pub fn merge(&mut self, mut other: Self) {
+        if let (Some(max), Some(min)) = (Node::max(&self.head), Node::min(&other.head)) {
+            assert!(
+                max < min,
+                "the merged treap has to hold the greater elements"
+            );
+        }
+
+        self.head = Node::merge(self.head.take(), other.head.take());
+    }
+
+    /// Returns the number of levels of the treap, 0 for an empty treap.
+    pub fn height(&self) -> usize {
+        let mut height = 0;
+        let mut visited = Stack::new();
+
+        if let Some(head) = self.head.as_deref() {
+            visited.push((head, 1));
+        }
+
+        while let Some((node, depth)) = visited.pop() {
+            height = height.max(depth);
+            if let Some(left) = node.left.as_deref() {
+                visited.push((left, depth + 1));
+            }
+            if let Some(right) = node.right.as_deref() {
+                visited.push((right, depth + 1));
+            }
+        }
+
+        height
+    }
+
+    /// Returns an `Iterator` over the elements of the treap in ascending order.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::treap::Treap;
+    /// let mut treap = Treap::with_seed(1);
+    /// treap.insert(10);
+    /// treap.insert(8);
+    /// treap.insert(11);
+    ///
+    /// assert_eq!(treap.iter().collect::<Vec<_>>(), vec![&8, &10, &11]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            visited: Stack::new(),
+        };
+        iter.push_left(&self.head);
+        iter
+    }
+}
+
+impl<T: Ord> Default for Treap<T> {
+    fn default() -> Self {
+        Treap::new()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for Treap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut treap = Treap::new();
+
+        for value in iter {
+            treap.insert(value);
+        }
+
+        treap
+    }
+}
+
+impl<T, R> Drop for Treap<T, R> {
+    /// Priorities from a poor generator can turn the treap into a long path, so the nodes
+    /// are freed iteratively. Left children are rotated up until the current node has none
+    /// and can be dropped.
+    fn drop(&mut self) {
+        let mut current = self.head.take();
+
+        while let Some(mut node) = current {
+            current = match node.left.take() {
+                Some(mut left) => {
+                    node.left = left.right.take();
+                    left.right = Some(node);
+                    Some(left)
+                }
+                None => node.right.take(),
+            };
+        }
+    }
+}
+
+impl<T: Ord + Display, R: Rng> Visualize for Treap<T, R> {
+    fn display_tree(&self) -> Option<DisplayNode> {
+        self.head.as_deref().map(Node::display_node)
+    }
+}
+
+impl<T: Ord> Node<T> {
+    fn new(value: T, priority: u64) -> Self {
+        Node {
+            left: None,
+            right: None,
+            value,
+            priority,
+            size: 1,
+        }
+    }
+
+    fn size(link: &Link<T>) -> usize {
+        link.as_ref().map_or(0, |node| node.size)
+    }
+
+    /// Recomputes the size after the children changed.
+    fn update(&mut self) {
+        self.size = 1 + Node::size(&self.left) + Node::size(&self.right);
+    }
+
+    /// Splits the treap below `link` into the elements less than `value` and the ones
+    /// greater than or equal to it.
+    fn split(link: Link<T>, value: &T) -> (Link<T>, Link<T>) {
+        match link {
+            None => (None, None),
+            Some(mut node) => {
+                if node.value < *value {
+                    let (less, greater) = Node::split(node.right.take(), value);
+                    node.right = less;
+                    node.update();
+                    (Some(node), greater)
+                } else {
+                    let (less, greater) = Node::split(node.left.take(), value);
+                    node.left = greater;
+                    node.update();
+                    (less, Some(node))
+                }
+            }
+        }
+    }
+
+    /// Merges two treaps where every element of `left` is less than every element of `right`.
+    /// The node with the higher priority stays on top.
+    fn merge(left: Link<T>, right: Link<T>) -> Link<T> {
+        match (left, right) {
+            (None, right) => right,
+            (left, None) => left,
+            (Some(mut left), Some(mut right)) => {
+                if left.priority > right.priority {
+                    left.right = Node::merge(left.right.take(), Some(right));
+                    left.update();
+                    Some(left)
+                } else {
+                    right.left = Node::merge(Some(left), right.left.take());
+                    right.update();
+                    Some(right)
+                }
+            }
+        }
+    }
+
+    fn take(link: &mut Link<T>, value: &T) -> Option<T> {
+        let node = link.as_mut()?;
+
+        let taken = match value.cmp(&node.value) {
+            Ordering::Less => Node::take(&mut node.left, value)?,
+            Ordering::Greater => Node::take(&mut node.right, value)?,
+            Ordering::Equal => {
+                let mut node = link.take().unwrap();
+                *link = Node::merge(node.left.take(), node.right.take());
+                return Some(node.value);
+            }
+        };
+
+        node.update();
+        Some(taken)
+    }
+
+    fn contains(link: &Link<T>, value: &T) -> bool {
+        let mut current = link.as_deref();
+
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => current = node.left.as_deref(),
+                Ordering::Equal => return true,
+                Ordering::Greater => current = node.right.as_deref(),
+            }
+        }
+
+        false
+    }
+
+    fn min(mut link: &Link<T>) -> Option<&T> {
+        while link.as_ref()?.left.is_some() {
+            link = &link.as_ref().unwrap().left;
+        }
+
+        link.as_ref().map(|node| &node.value)
+    }
+
+    fn max(mut link: &Link<T>) -> Option<&T> {
+        while link.as_ref()?.right.is_some() {
+            link = &link.as_ref().unwrap().right;
+        }
+
+        link.as_ref().map(|node| &node.value)
+    }
+}
+
+impl<T: Display> Node<T> {
+    fn display_node(&self) -> DisplayNode {
+        DisplayNode::from_tree(
+            self,
+            |node| node.value.to_string(),
+            |node| (node.left.as_deref(), node.right.as_deref()),
+        )
+    }
+}
+
+impl<'a, T: Eq> Iter<'a, T> {
+    fn push_left(&mut self, mut link: &'a Link<T>) {
+        while let Some(node) = link {
+            self.visited.push(node.as_ref());
+            link = &node.left;
+        }
+    }
+}
+
+impl<'a, T: Eq> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.visited.pop()?;
+        self.push_left(&node.right);
+
+        Some(&node.value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::rngs::mock::StepRng;
+    use rand::seq::SliceRandom;
+    use rand::thread_rng;
+    use std::collections::BTreeSet;
+
+    /// Checks the heap order on the priorities and the cached subtree sizes.
+    fn assert_treap<T: Ord>(link: &Link<T>) -> usize {
+        match link {
+            None => 0,
+            Some(node) => {
+                for child in [&node.left, &node.right].iter().copied().flatten() {
+                    assert!(child.priority <= node.priority);
+                }
+
+                let size = 1 + assert_treap(&node.left) + assert_treap(&node.right);
+                assert_eq!(node.size, size);
+                size
+            }
+        }
+    }
+
+    #[test]
+    fn test_against_btree_set() {
+        let mut rng = thread_rng();
+        let mut sut = Treap::with_seed(7);
+        let mut model = BTreeSet::new();
+
+        for _ in 0..5000 {
+            let value = rng.gen_range(0, 500);
+            if rng.gen() {
+                assert_eq!(sut.insert(value), model.insert(value));
+            } else {
+                assert_eq!(sut.take(&value), model.take(&value));
+            }
+            assert_eq!(sut.contains(value), model.contains(&value));
+        }
+
+        assert_eq!(assert_treap(&sut.head), model.len());
+        assert_eq!(sut.len(), model.len());
+        assert!(sut.iter().eq(model.iter()));
+    }
+
+    #[test]
+    fn test_same_seed_same_shape() {
+        let mut values: Vec<u32> = (0..200).collect();
+        values.shuffle(&mut thread_rng());
+
+        let mut first = Treap::with_seed(3);
+        let mut second = Treap::with_seed(3);
+        for value in values {
+            first.insert(value);
+            second.insert(value);
+        }
+
+        assert_eq!(first.head, second.head);
+        assert_eq!(first.to_ascii(), second.to_ascii());
+    }
+
+    #[test]
+    fn test_sorted_insert_stays_flat() {
+        let mut sut = Treap::with_seed(11);
+        for value in 0..100_000 {
+            sut.insert(value);
+        }
+
+        assert_eq!(sut.len(), 100_000);
+        assert!(sut.height() < 60);
+        assert!(sut.iter().copied().eq(0..100_000));
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // Ascending priorities put every new node on top, sorted inserts build a path
+        let mut sut = Treap::with_rng(StepRng::new(0, 1));
+        for value in 0..1_000_000 {
+            sut.insert(value);
+        }
+
+        assert_eq!(sut.height(), 1_000_000);
+    }
+
+    #[test]
+    fn test_split_off_and_merge() {
+        let mut sut = (0..1000).collect::<Treap<_>>();
+
+        let mut middle = sut.split_off(&600);
+        let top = middle.split_off(&800);
+        assert_eq!(assert_treap(&sut.head), 600);
+        assert_eq!(assert_treap(&middle.head), 200);
+        assert_eq!(assert_treap(&top.head), 200);
+        assert!(middle.iter().copied().eq(600..800));
+
+        middle.merge(top);
+        sut.merge(middle);
+        assert_eq!(assert_treap(&sut.head), 1000);
+        assert!(sut.iter().copied().eq(0..1000));
+
+        let empty = sut.split_off(&1000);
+        assert!(empty.is_empty());
+        sut.merge(empty);
+        assert_eq!(sut.len(), 1000);
+    }
+
+    #[test]
+    #[should_panic(expected = "the merged treap has to hold the greater elements")]
+    fn test_merge_unordered() {
+        let mut sut = (0..10).collect::<Treap<_>>();
+        sut.merge((5..15).collect());
+    }
+}
@@ -0,0 +1,1836 @@
+//! An unrolled linked list.
+//!
+//! A node of the [`LinkedList`](crate::linked_list::LinkedList) holds a single element
+//! next to its link, so small elements pay more for the link than for themselves and
+//! every element costs a pointer chase. The `UnrolledList` keeps several elements per
+//! node in a buffer that is allocated once with a fixed capacity. The links are shared
+//! by all elements of a node and iterating scans contiguous memory.
+//!
+//! Full nodes split when an element gets inserted, nodes that fall below half of their
+//! capacity borrow from or merge with a neighbour when an element gets removed. The
+//! nodes are stored in one arena and refer to their neighbours by index.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::{Index, IndexMut};
+
+/// The node capacity used by [`UnrolledList::new()`].
+pub const DEFAULT_NODE_CAPACITY: usize = 32;
+
+/// A doubly linked list whose nodes hold up to `node_capacity` elements each. No node is
+/// empty and all nodes except the first and the last one are at least half full.
+pub struct UnrolledList<T> {
+    nodes: Vec<Node<T>>,
+    /// Indices of nodes that got merged away, reused before the arena grows.
+    free: Vec<usize>,
+    head: Option<usize>,
+    tail: Option<usize>,
+    len: usize,
+    node_capacity: usize,
+}
+
+struct Node<T> {
+    /// Allocated with the node capacity, the buffer never grows.
+    values: Vec<T>,
+    prev: Option<usize>,
+    next: Option<usize>,
+}
+
+/// A double-ended Iterator over the elements of an `UnrolledList`.
+/// Instances are created by [`UnrolledList::iter()`].
+pub struct Iter<'a, T: 'a> {
+    nodes: &'a [Node<T>],
+    /// The node and offset of the next element from the front. The offset may be the
+    /// length of the node, then the element is the first one of the following node.
+    front: (usize, usize),
+    /// The node and offset behind the next element from the back. The offset may be 0,
+    /// then the element is the last one of the previous node.
+    back: (usize, usize),
+    len: usize,
+}
+
+/// A double-ended Iterator over mutable references to the elements of an `UnrolledList`.
+/// Instances are created by [`UnrolledList::iter_mut()`].
+pub struct IterMut<'a, T: 'a> {
+    nodes: *mut Node<T>,
+    /// See [`Iter`].
+    front: (usize, usize),
+    back: (usize, usize),
+    len: usize,
+    marker: PhantomData<&'a mut Node<T>>,
+}
+
+/// An owning double-ended Iterator over the elements of an `UnrolledList`. It takes whole
+/// nodes off the list, so no elements get shifted.
+/// Instances are created by [`UnrolledList::into_iter()`].
+pub struct IntoIter<T> {
+    front: std::vec::IntoIter<T>,
+    list: UnrolledList<T>,
+    back: std::vec::IntoIter<T>,
+}
+
+/// An Iterator over consecutive chunks of an `UnrolledList`, each being an [`Iter`] over
+/// at most `size` elements. Instances are created by [`UnrolledList::chunks()`].
+pub struct Chunks<'a, T: 'a> {
+    iter: Iter<'a, T>,
+    size: usize,
+}
+
+impl<T> UnrolledList<T> {
+    /// Creates a new and empty `UnrolledList` with the [`DEFAULT_NODE_CAPACITY`].
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::<()>::new();
+    /// assert!(unrolled_list.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        UnrolledList::with_node_capacity(DEFAULT_NODE_CAPACITY)
+    }
+
+    /// Creates a new and empty `UnrolledList` whose nodes hold at most `node_capacity`
+    /// elements.
+    ///
+    /// Panics if `node_capacity` is less than 2.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::with_node_capacity(4);
+    /// for value in 0..10 {
+    ///     unrolled_list.push_back(value);
+    /// }
+    /// assert_eq!(unrolled_list.get(7), Some(&7));
+    /// ```
+    pub fn with_node_capacity(node_capacity: usize) -> Self {
+        assert!(
+            node_capacity >= 2,
+            "the node capacity has to be at least 2, got {}",
+            node_capacity
+        );
+
+        UnrolledList {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: None,
+            tail: None,
+            len: 0,
+            node_capacity,
+        }
+    }
+
+    /// Returns the maximum number of elements per node.
+    pub fn node_capacity(&self) -> usize {
+        self.node_capacity
+    }
+
+    /// Checks if the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of elements in the list.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Appends an element to the back of the list, like [`UnrolledList::push_back()`].
+    pub fn append(&mut self, val: T) -> bool {
+        self.push_back(val);
+        true
+    }
+
+    /// Adds an element to the front of the list. If the first node is full, a new node
+    /// gets linked in front of it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::new();
+    /// unrolled_list.push_front(2);
+    /// unrolled_list.push_front(1);
+    ///
+    /// assert_eq!(unrolled_list.front(), Some(&1));
+    /// assert_eq!(unrolled_list.back(), Some(&2));
+    /// ```
+    pub fn push_front(&mut self, val: T) {
+        match self.head {
+            Some(head) if self.nodes[head].values.len() < self.node_capacity => {
+                self.nodes[head].values.insert(0, val);
+            }
+            _ => {
+                let values = self.buffer_with(val);
+                self.link_node_after(None, values);
+            }
+        }
+
+        self.len += 1;
+    }
+
+    /// Adds an element to the back of the list. If the last node is full, a new node gets
+    /// linked behind it, so pushing to the back fills every node up.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::new();
+    /// unrolled_list.push_back(1);
+    /// unrolled_list.push_back(2);
+    ///
+    /// assert_eq!(unrolled_list.front(), Some(&1));
+    /// assert_eq!(unrolled_list.back(), Some(&2));
+    /// ```
+    pub fn push_back(&mut self, val: T) {
+        match self.tail {
+            Some(tail) if self.nodes[tail].values.len() < self.node_capacity => {
+                self.nodes[tail].values.push(val);
+            }
+            tail => {
+                let values = self.buffer_with(val);
+                self.link_node_after(tail, values);
+            }
+        }
+
+        self.len += 1;
+    }
+
+    /// Removes the first element and returns it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2]);
+    ///
+    /// assert_eq!(unrolled_list.pop_front(), Some(1));
+    /// assert_eq!(unrolled_list.pop_front(), Some(2));
+    /// assert_eq!(unrolled_list.pop_front(), None);
+    /// ```
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    /// Removes the last element and returns it. The nodes know their predecessor, so
+    /// unlike [`LinkedList::pop_back()`](crate::linked_list::LinkedList::pop_back) this
+    /// doesn't walk the list.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2]);
+    ///
+    /// assert_eq!(unrolled_list.pop_back(), Some(2));
+    /// assert_eq!(unrolled_list.pop_back(), Some(1));
+    /// assert_eq!(unrolled_list.pop_back(), None);
+    /// ```
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.remove(self.len.checked_sub(1)?)
+    }
+
+    /// Returns a reference to the first element.
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|head| &self.nodes[head].values[0])
+    }
+
+    /// Returns a mutable reference to the first element.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        let head = self.head?;
+        self.nodes[head].values.first_mut()
+    }
+
+    /// Returns a reference to the last element.
+    pub fn back(&self) -> Option<&T> {
+        self.tail.and_then(|tail| self.nodes[tail].values.last())
+    }
+
+    /// Returns a mutable reference to the last element.
+    pub fn back_mut(&mut self) -> Option<&mut T> {
+        let tail = self.tail?;
+        self.nodes[tail].values.last_mut()
+    }
+
+    /// Removes the element at `index` and returns it, or `None` if `index` is out of range.
+    /// If its node drops below half of its capacity, it borrows an element from a
+    /// neighbour or gets merged with it.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(unrolled_list.remove(1), Some(2));
+    /// assert_eq!(unrolled_list.remove(2), None);
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&1, &3]);
+    /// ```
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (id, offset) = self.locate(index);
+        let value = self.nodes[id].values.remove(offset);
+        self.len -= 1;
+        self.rebalance(id);
+
+        Some(value)
+    }
+
+    /// Returns a reference to the element at `index`, or `None` if `index` is out of range.
+    /// Whole nodes are skipped from the closer end of the list.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(unrolled_list.get(2), Some(&3));
+    /// assert_eq!(unrolled_list.get(3), None);
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (id, offset) = self.locate(index);
+        Some(&self.nodes[id].values[offset])
+    }
+
+    /// Returns a mutable reference to the element at `index`, or `None` if `index` is out
+    /// of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (id, offset) = self.locate(index);
+        Some(&mut self.nodes[id].values[offset])
+    }
+
+    /// Inserts an element at `index`, shifting all elements after it. A full node gets
+    /// split into two half full ones first.
+    ///
+    /// Panics if `index` is greater than the length.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 3]);
+    /// unrolled_list.insert(1, 2);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&1, &2, &3]);
+    /// ```
+    pub fn insert(&mut self, index: usize, val: T) {
+        if index > self.len {
+            panic!(
+                "insertion index {} is out of range for a list of length {}",
+                index, self.len
+            );
+        }
+
+        if index == self.len {
+            self.push_back(val);
+        } else {
+            let (id, offset) = self.locate(index);
+            self.insert_at(id, offset, val);
+        }
+    }
+
+    /// Swaps the elements at `i` and `j`.
+    ///
+    /// Panics if one of the indices is out of range.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    /// unrolled_list.swap(0, 2);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    pub fn swap(&mut self, i: usize, j: usize) {
+        for index in [i, j].iter() {
+            if *index >= self.len {
+                panic!(
+                    "swap index {} is out of range for a list of length {}",
+                    index, self.len
+                );
+            }
+        }
+
+        let (first, second) = (self.locate(i.min(j)), self.locate(i.max(j)));
+        if first.0 == second.0 {
+            self.nodes[first.0].values.swap(first.1, second.1);
+            return;
+        }
+
+        let (low, high) = (first.0.min(second.0), first.0.max(second.0));
+        let (below, above) = self.nodes.split_at_mut(high);
+        let (low_node, high_node) = (&mut below[low], &mut above[0]);
+        let (low_offset, high_offset) = if first.0 == low {
+            (first.1, second.1)
+        } else {
+            (second.1, first.1)
+        };
+
+        mem::swap(
+            &mut low_node.values[low_offset],
+            &mut high_node.values[high_offset],
+        );
+    }
+
+    /// Shortens the list to `len` elements, dropping the rest. Does nothing if the list is
+    /// not longer than `len`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    /// unrolled_list.truncate(1);
+    ///
+    /// assert_eq!(unrolled_list.len(), 1);
+    /// assert_eq!(unrolled_list.back(), Some(&1));
+    /// ```
+    pub fn truncate(&mut self, len: usize) {
+        while self.len > len {
+            let tail = self.tail.unwrap();
+            let tail_len = self.nodes[tail].values.len();
+
+            if self.len - tail_len >= len {
+                self.unlink_node(tail);
+                self.len -= tail_len;
+            } else {
+                // The last node is allowed to be less than half full
+                self.nodes[tail]
+                    .values
+                    .truncate(len - (self.len - tail_len));
+                self.len = len;
+            }
+        }
+    }
+
+    /// Sorts the list with a comparator function. The sort is stable and takes O(n) extra
+    /// memory, see [`UnrolledList::sort()`].
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![2.5, -1.0, 0.5]);
+    /// unrolled_list.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&-1.0, &0.5, &2.5]);
+    /// ```
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&mut self, compare: F) {
+        let mut values = self.take_values();
+        values.sort_by(compare);
+        self.extend(values);
+    }
+
+    /// Sorts the list by the key `f` extracts from each element. The sort is stable.
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&mut self, mut f: F) {
+        self.sort_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    /// Checks if `in_order` holds for every pair of neighbouring elements. Like
+    /// [`UnrolledList::is_sorted()`] an empty list doesn't count as sorted.
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut in_order: F) -> bool {
+        let mut iter = self.iter();
+        let mut before = match iter.next() {
+            Some(first) => first,
+            None => return false,
+        };
+
+        for value in iter {
+            if !in_order(before, value) {
+                return false;
+            }
+            before = value;
+        }
+
+        true
+    }
+
+    /// Removes consecutive elements for which `same` returns `true`, keeping the first of
+    /// them. `same` gets the later element first, like [`Vec::dedup_by()`]. The elements
+    /// get removed within their nodes, which are refilled from the front afterwards, so
+    /// no buffer gets allocated.
+    pub fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, mut same: F) {
+        // The node holding the last element kept so far
+        let mut last: Option<usize> = None;
+        let mut current = self.head;
+
+        while let Some(id) = current {
+            let mut values = mem::take(&mut self.nodes[id].values);
+            let len_before = values.len();
+
+            // The leading duplicates of a node compare with the last element of another one
+            if let Some(before) = last.and_then(|last| self.nodes[last].values.last_mut()) {
+                let duplicates = values
+                    .iter_mut()
+                    .position(|value| !same(value, before))
+                    .unwrap_or(values.len());
+                values.drain(..duplicates);
+            }
+            values.dedup_by(&mut same);
+
+            self.len -= len_before - values.len();
+            if !values.is_empty() {
+                last = Some(id);
+            }
+            self.nodes[id].values = values;
+            current = self.nodes[id].next;
+        }
+
+        self.compact();
+    }
+
+    /// Removes consecutive elements with the same key, keeping the first of them.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![10, 11, 20, 12, 13]);
+    /// unrolled_list.dedup_by_key(|value| *value / 10);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&10, &20, &12]);
+    /// ```
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(&mut self, mut key: F) {
+        self.dedup_by(|a, b| key(a) == key(b));
+    }
+
+    /// Returns a double-ended `Iterator` over the elements of the list.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(unrolled_list.iter().rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            nodes: &self.nodes,
+            front: (self.head.unwrap_or(0), 0),
+            back: self.end(),
+            len: self.len,
+        }
+    }
+
+    /// Returns a double-ended `Iterator` over mutable references to the elements.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+    /// for value in unrolled_list.iter_mut() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&10, &20, &30]);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            front: (self.head.unwrap_or(0), 0),
+            back: self.end(),
+            len: self.len,
+            nodes: self.nodes.as_mut_ptr(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Reverses the list in place by reversing the order of the nodes and the elements
+    /// within each node. No element moves to another node.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3, 4]);
+    /// unrolled_list.reverse();
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&4, &3, &2, &1]);
+    /// ```
+    pub fn reverse(&mut self) {
+        let mut current = self.head;
+
+        while let Some(id) = current {
+            let node = &mut self.nodes[id];
+            node.values.reverse();
+            mem::swap(&mut node.prev, &mut node.next);
+            current = node.prev;
+        }
+
+        mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    /// Moves the first `k` elements to the back of the list. `k` is taken modulo the
+    /// length, and at most half of the elements get moved from one end to the other.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3, 4, 5]);
+    /// unrolled_list.rotate_left(2);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&3, &4, &5, &1, &2]);
+    /// ```
+    pub fn rotate_left(&mut self, k: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let k = k % self.len;
+        if k <= self.len / 2 {
+            for _ in 0..k {
+                let value = self.pop_front().unwrap();
+                self.push_back(value);
+            }
+        } else {
+            for _ in k..self.len {
+                let value = self.pop_back().unwrap();
+                self.push_front(value);
+            }
+        }
+    }
+
+    /// Moves the last `k` elements to the front of the list. `k` is taken modulo the
+    /// length.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 3, 4, 5]);
+    /// unrolled_list.rotate_right(2);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&4, &5, &1, &2, &3]);
+    /// ```
+    pub fn rotate_right(&mut self, k: usize) {
+        if !self.is_empty() {
+            self.rotate_left(self.len - k % self.len);
+        }
+    }
+
+    /// Returns the middle element, the one at `len / 2`.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// assert_eq!(UnrolledList::from([1, 2, 3, 4]).middle(), Some(&3));
+    /// assert_eq!(UnrolledList::<u8>::new().middle(), None);
+    /// ```
+    pub fn middle(&self) -> Option<&T> {
+        self.get(self.len / 2)
+    }
+
+    /// Returns the element `k` places before the last one, so `nth_from_end(0)` is the
+    /// last element.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::from([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(unrolled_list.nth_from_end(0), Some(&4));
+    /// assert_eq!(unrolled_list.nth_from_end(4), None);
+    /// ```
+    pub fn nth_from_end(&self, k: usize) -> Option<&T> {
+        if k >= self.len {
+            return None;
+        }
+
+        self.get(self.len - 1 - k)
+    }
+
+    /// Interleaves the elements of `other` with the elements of this list, starting with
+    /// this one. Once one of the lists runs out, the rest of the other follows as it is.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from([1, 2]);
+    /// unrolled_list.interleave(UnrolledList::from([10, 20, 30]));
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&1, &10, &2, &20, &30]);
+    /// ```
+    pub fn interleave(&mut self, other: UnrolledList<T>) {
+        let mut first = self.take_values().into_iter();
+        let mut second = other.into_iter();
+
+        for value in first.by_ref() {
+            self.push_back(value);
+            match second.next() {
+                Some(value) => self.push_back(value),
+                None => break,
+            }
+        }
+
+        // At most one of them has elements left
+        self.extend(first);
+        self.extend(second);
+    }
+
+    /// Returns an `Iterator` over chunks of `size` elements, the last chunk may be shorter.
+    /// Each chunk is an [`Iter`] into the list itself, nothing gets copied.
+    ///
+    /// Panics if `size` is zero.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<Vec<_>> = unrolled_list.chunks(2).map(|chunk| chunk.collect()).collect();
+    ///
+    /// assert_eq!(chunks, vec![vec![&1, &2], vec![&3, &4], vec![&5]]);
+    /// ```
+    pub fn chunks(&self, size: usize) -> Chunks<'_, T> {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Chunks {
+            iter: self.iter(),
+            size,
+        }
+    }
+
+    /// Reverses every group of `k` consecutive elements in place, the last group may be
+    /// shorter and gets reversed as well.
+    ///
+    /// Panics if `k` is zero.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from([1, 2, 3, 4, 5, 6, 7, 8]);
+    /// unrolled_list.reverse_in_groups(3);
+    ///
+    /// assert_eq!(
+    ///     unrolled_list.iter().collect::<Vec<_>>(),
+    ///     vec![&3, &2, &1, &6, &5, &4, &8, &7]
+    /// );
+    /// ```
+    pub fn reverse_in_groups(&mut self, k: usize) {
+        assert!(k != 0, "group size must be non-zero");
+
+        let mut iter = self.iter_mut();
+        while iter.len > 0 {
+            let mut group = iter.split_front(k.min(iter.len));
+            while let (Some(first), Some(last)) = (group.next(), group.next_back()) {
+                mem::swap(first, last);
+            }
+        }
+    }
+
+    /// Returns the node and offset of the element at `index`, which has to be in range.
+    fn locate(&self, index: usize) -> (usize, usize) {
+        if index < self.len / 2 {
+            let mut id = self.head.unwrap();
+            let mut index = index;
+
+            while index >= self.nodes[id].values.len() {
+                index -= self.nodes[id].values.len();
+                id = self.nodes[id].next.unwrap();
+            }
+
+            (id, index)
+        } else {
+            let mut id = self.tail.unwrap();
+            let mut from_back = self.len - index;
+
+            while from_back > self.nodes[id].values.len() {
+                from_back -= self.nodes[id].values.len();
+                id = self.nodes[id].prev.unwrap();
+            }
+
+            (id, self.nodes[id].values.len() - from_back)
+        }
+    }
+
+    /// Returns the position behind the last element.
+    fn end(&self) -> (usize, usize) {
+        self.tail
+            .map_or((0, 0), |tail| (tail, self.nodes[tail].values.len()))
+    }
+
+    /// Returns an empty buffer with the node capacity.
+    fn buffer(&self) -> Vec<T> {
+        Vec::with_capacity(self.node_capacity)
+    }
+
+    fn buffer_with(&self, val: T) -> Vec<T> {
+        let mut values = self.buffer();
+        values.push(val);
+        values
+    }
+
+    /// Inserts an element at `offset` of the node `id`. A full node moves the upper half
+    /// of its elements into a new node behind it first.
+    fn insert_at(&mut self, id: usize, offset: usize, val: T) {
+        self.len += 1;
+
+        if self.nodes[id].values.len() < self.node_capacity {
+            self.nodes[id].values.insert(offset, val);
+            return;
+        }
+
+        let middle = self.node_capacity.div_ceil(2);
+        let mut upper = self.buffer();
+        upper.extend(self.nodes[id].values.drain(middle..));
+        let upper_id = self.link_node_after(Some(id), upper);
+
+        if offset <= middle {
+            self.nodes[id].values.insert(offset, val);
+        } else {
+            self.nodes[upper_id].values.insert(offset - middle, val);
+        }
+    }
+
+    /// Restores the fill of the node `id` after an element got removed from it. A node
+    /// below half of the capacity borrows one element from its neighbour, or gets merged
+    /// with it if both fit into one node.
+    fn rebalance(&mut self, id: usize) {
+        let len = self.nodes[id].values.len();
+        if len == 0 {
+            self.unlink_node(id);
+            return;
+        } else if len >= self.node_capacity / 2 {
+            return;
+        }
+
+        let (left, right) = match (self.nodes[id].prev, self.nodes[id].next) {
+            (_, Some(next)) => (id, next),
+            (Some(prev), None) => (prev, id),
+            (None, None) => return,
+        };
+
+        if self.nodes[left].values.len() + self.nodes[right].values.len() <= self.node_capacity {
+            let mut values = self.unlink_node(right);
+            self.nodes[left].values.append(&mut values);
+        } else if left == id {
+            let value = self.nodes[right].values.remove(0);
+            self.nodes[left].values.push(value);
+        } else {
+            let value = self.nodes[left].values.pop().unwrap();
+            self.nodes[right].values.insert(0, value);
+        }
+    }
+
+    /// Links a new node with `values` behind the node `prev`, or in front of the first
+    /// node if `prev` is `None`. Returns the index of the new node.
+    fn link_node_after(&mut self, prev: Option<usize>, values: Vec<T>) -> usize {
+        let next = match prev {
+            Some(prev) => self.nodes[prev].next,
+            None => self.head,
+        };
+        let node = Node { values, prev, next };
+
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+
+        match prev {
+            Some(prev) => self.nodes[prev].next = Some(id),
+            None => self.head = Some(id),
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = Some(id),
+            None => self.tail = Some(id),
+        }
+
+        id
+    }
+
+    /// Unlinks the node `id` and returns its elements. The length is left to the caller.
+    fn unlink_node(&mut self, id: usize) -> Vec<T> {
+        let Node { prev, next, .. } = self.nodes[id];
+
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+
+        let values = mem::take(&mut self.nodes[id].values);
+        if self.head.is_none() {
+            // Without nodes the arena can go as a whole
+            self.nodes.clear();
+            self.free.clear();
+        } else {
+            self.free.push(id);
+        }
+
+        values
+    }
+
+    /// Fills every node but the last one up with elements of the nodes behind it, after
+    /// elements got removed from the nodes in place. Nodes that run empty get unlinked.
+    /// The length is left to the caller.
+    fn compact(&mut self) {
+        let mut current = self.head;
+
+        while let Some(id) = current {
+            while self.nodes[id].values.len() < self.node_capacity {
+                let next = match self.nodes[id].next {
+                    Some(next) => next,
+                    None => break,
+                };
+
+                let count = (self.node_capacity - self.nodes[id].values.len())
+                    .min(self.nodes[next].values.len());
+                let mut values = mem::take(&mut self.nodes[next].values);
+                self.nodes[id].values.extend(values.drain(..count));
+                self.nodes[next].values = values;
+
+                if self.nodes[next].values.is_empty() {
+                    self.unlink_node(next);
+                }
+            }
+
+            current = self.nodes[id].next;
+            if self.nodes[id].values.is_empty() {
+                // Only the last node can be left empty
+                self.unlink_node(id);
+            }
+        }
+    }
+
+    /// Moves all elements into a `Vec`, leaving the list empty.
+    fn take_values(&mut self) -> Vec<T> {
+        let mut values = Vec::with_capacity(self.len);
+
+        while let Some(head) = self.head {
+            values.append(&mut self.unlink_node(head));
+        }
+        self.len = 0;
+
+        values
+    }
+}
+
+impl<T: Clone> UnrolledList<T> {
+    /// Returns a reversed copy of the list and leaves the list itself untouched.
+    pub fn reversed(&self) -> Self {
+        let mut reversed = UnrolledList::with_node_capacity(self.node_capacity);
+        reversed.extend(self.iter().rev().cloned());
+        reversed
+    }
+}
+
+impl<T: PartialEq> UnrolledList<T> {
+    /// Checks if an `UnrolledList` contains a given element.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let unrolled_list = UnrolledList::from(vec![1, 2]);
+    ///
+    /// assert!(unrolled_list.contains(2));
+    /// assert!(!unrolled_list.contains(3));
+    /// ```
+    pub fn contains(&self, val: T) -> bool {
+        self.iter().any(|value| *value == val)
+    }
+
+    /// Removes consecutive equal elements, so a sorted list keeps every element once.
+    pub fn dedup(&mut self) {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Returns the index of the first element equal to `val`.
+    pub fn position(&self, val: &T) -> Option<usize> {
+        self.iter().position(|value| value == val)
+    }
+
+    /// Removes the first element equal to `val` and returns it.
+    pub fn remove_first(&mut self, val: &T) -> Option<T> {
+        let index = self.position(val)?;
+        self.remove(index)
+    }
+
+    /// Removes all elements equal to `val` and returns how many got removed. Like
+    /// [`UnrolledList::dedup_by()`] this works within the nodes.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 2, 1, 3, 1]);
+    ///
+    /// assert_eq!(unrolled_list.remove_all(&1), 3);
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&2, &3]);
+    /// ```
+    pub fn remove_all(&mut self, val: &T) -> usize {
+        let len_before = self.len;
+        let mut current = self.head;
+
+        while let Some(id) = current {
+            let values = &mut self.nodes[id].values;
+            let node_len = values.len();
+            values.retain(|value| value != val);
+
+            self.len -= node_len - values.len();
+            current = self.nodes[id].next;
+        }
+        self.compact();
+
+        len_before - self.len
+    }
+
+    /// Checks if the list reads the same forwards and backwards.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// assert!(UnrolledList::from([1, 2, 3, 2, 1]).is_palindrome());
+    /// assert!(!UnrolledList::from([1, 2, 3]).is_palindrome());
+    /// ```
+    pub fn is_palindrome(&self) -> bool {
+        let half = self.len / 2;
+        self.iter().take(half).eq(self.iter().rev().take(half))
+    }
+}
+
+impl<T: PartialOrd> UnrolledList<T> {
+    /// Moves all elements less than `pivot` before all other elements. The partition is
+    /// stable, both parts keep their order. Returns the number of elements less than
+    /// `pivot`. Both parts are collected in a buffer of their own before they get refilled
+    /// into the nodes, which takes O(n) extra memory.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from([3, 5, 8, 5, 10, 2, 1]);
+    ///
+    /// assert_eq!(unrolled_list.partition(&5), 3);
+    /// assert_eq!(
+    ///     unrolled_list.iter().collect::<Vec<_>>(),
+    ///     vec![&3, &2, &1, &5, &8, &5, &10]
+    /// );
+    /// ```
+    pub fn partition(&mut self, pivot: &T) -> usize {
+        let (less, rest): (Vec<T>, Vec<T>) = self
+            .take_values()
+            .into_iter()
+            .partition(|value| *value < *pivot);
+
+        let count = less.len();
+        self.extend(less);
+        self.extend(rest);
+
+        count
+    }
+}
+
+impl<T: Ord> UnrolledList<T> {
+    /// Checks if the elements are in ascending order. An empty list doesn't count as sorted.
+    pub fn is_sorted(&self) -> bool {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Sorts the list in ascending order. The sort is stable. The elements get sorted
+    /// within one buffer and are refilled into nodes which are full, except for the
+    /// last one. The buffer takes O(n) extra memory, which the stable sort of a slice
+    /// needs for merging anyway.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![3, 1, 2]);
+    /// unrolled_list.sort();
+    ///
+    /// assert!(unrolled_list.is_sorted());
+    /// ```
+    pub fn sort(&mut self) {
+        self.sort_by(T::cmp);
+    }
+
+    /// Merges two sorted lists into one sorted list with the node capacity of `a`. On ties
+    /// the elements of `a` come first.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let merged = UnrolledList::merge_sorted(
+    ///     UnrolledList::from(vec![1, 4, 5]),
+    ///     UnrolledList::from(vec![2, 3, 6]),
+    /// );
+    ///
+    /// assert_eq!(merged.iter().collect::<Vec<_>>(), vec![&1, &2, &3, &4, &5, &6]);
+    /// ```
+    pub fn merge_sorted(a: Self, b: Self) -> Self {
+        let mut merged = UnrolledList::with_node_capacity(a.node_capacity);
+        let mut first = a.into_iter().peekable();
+        let mut second = b.into_iter().peekable();
+
+        while let (Some(x), Some(y)) = (first.peek(), second.peek()) {
+            let value = if x <= y { first.next() } else { second.next() };
+            merged.push_back(value.unwrap());
+        }
+
+        // At most one of them has elements left, all larger than the merged ones
+        merged.extend(first);
+        merged.extend(second);
+
+        merged
+    }
+
+    /// Inserts an element into a sorted list, behind all elements not greater than it, so
+    /// the list stays sorted. Only the last element of each node is compared until the
+    /// node holding the place is found.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::unrolled_list::UnrolledList;
+    /// let mut unrolled_list = UnrolledList::from(vec![1, 3, 5]);
+    /// unrolled_list.insert_sorted(4);
+    /// unrolled_list.insert_sorted(0);
+    ///
+    /// assert_eq!(unrolled_list.iter().collect::<Vec<_>>(), vec![&0, &1, &3, &4, &5]);
+    /// ```
+    pub fn insert_sorted(&mut self, val: T) {
+        let mut current = self.head;
+
+        while let Some(id) = current {
+            let values = &self.nodes[id].values;
+            if *values.last().unwrap() > val {
+                let offset = values.partition_point(|value| *value <= val);
+                return self.insert_at(id, offset, val);
+            }
+            current = self.nodes[id].next;
+        }
+
+        self.push_back(val);
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Splits off an iterator over the next `count` elements from the front, which have
+    /// to be there, and moves this one behind them.
+    fn split_front(&mut self, count: usize) -> Iter<'a, T> {
+        let (mut id, mut end) = self.front;
+        let mut count_left = count;
+
+        while end + count_left > self.nodes[id].values.len() {
+            count_left -= self.nodes[id].values.len() - end;
+            id = self.nodes[id].next.unwrap();
+            end = 0;
+        }
+        end += count_left;
+
+        let front = mem::replace(&mut self.front, (id, end));
+        self.len -= count;
+
+        Iter {
+            nodes: self.nodes,
+            front,
+            back: (id, end),
+            len: count,
+        }
+    }
+}
+
+impl<'a, T> IterMut<'a, T> {
+    /// Returns the node at `id`. Only the buffer pointer and links of the node are read
+    /// through it, the handed out elements live in the buffers.
+    fn node(&self, id: usize) -> &Node<T> {
+        // SAFETY: `id` is a node of the list which the iterator borrows mutably
+        unsafe { &*self.nodes.add(id) }
+    }
+
+    /// Returns the element at `offset` of the node `id`.
+    fn value(&mut self, id: usize, offset: usize) -> &'a mut T {
+        // SAFETY: the iterator borrows the list mutably and the length check of the callers
+        // makes sure no element is handed out twice, from the front and from the back. The
+        // element is reached through the buffer pointer, without borrowing the other
+        // elements of the node.
+        unsafe { &mut *(*self.nodes.add(id)).values.as_mut_ptr().add(offset) }
+    }
+
+    /// See [`Iter::split_front()`].
+    fn split_front(&mut self, count: usize) -> IterMut<'a, T> {
+        let (mut id, mut end) = self.front;
+        let mut count_left = count;
+
+        while end + count_left > self.node(id).values.len() {
+            count_left -= self.node(id).values.len() - end;
+            id = self.node(id).next.unwrap();
+            end = 0;
+        }
+        end += count_left;
+
+        let front = mem::replace(&mut self.front, (id, end));
+        self.len -= count;
+
+        IterMut {
+            nodes: self.nodes,
+            front,
+            back: (id, end),
+            len: count,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for UnrolledList<T> {
+    /// Creates an empty `UnrolledList<T>`.
+    fn default() -> Self {
+        UnrolledList::new()
+    }
+}
+
+impl<T: Clone> Clone for UnrolledList<T> {
+    /// Clones the list with the same node capacity, with its nodes packed full.
+    fn clone(&self) -> Self {
+        let mut clone = UnrolledList::with_node_capacity(self.node_capacity);
+        clone.extend(self.iter().cloned());
+        clone
+    }
+}
+
+impl<T> FromIterator<T> for UnrolledList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut list = UnrolledList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl<T> Extend<T> for UnrolledList<T> {
+    /// Appends the elements to the back of the list, filling the last node up first.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push_back(value);
+        }
+    }
+}
+
+impl<'a, T: 'a + Copy> Extend<&'a T> for UnrolledList<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+/// Creates an `UnrolledList` from a `Vec`.
+/// # Example
+/// ```rust
+/// use data_structure_with_colin::unrolled_list::UnrolledList;
+/// let unrolled_list = UnrolledList::from(vec![1, 2, 3]);
+///
+/// assert_eq!(unrolled_list.len(), 3);
+///```
+impl<T> From<Vec<T>> for UnrolledList<T> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for UnrolledList<T> {
+    fn from(array: [T; N]) -> Self {
+        UnrolledList::from_iter(array)
+    }
+}
+
+/// Collects the elements of an `UnrolledList` into a `Vec`, front to back.
+/// # Example
+/// ```rust
+/// use data_structure_with_colin::unrolled_list::UnrolledList;
+/// let v: Vec<_> = UnrolledList::from([1, 2, 3]).into();
+///
+/// assert_eq!(v, vec![1, 2, 3]);
+///```
+impl<T> From<UnrolledList<T>> for Vec<T> {
+    fn from(mut list: UnrolledList<T>) -> Self {
+        list.take_values()
+    }
+}
+
+impl<T> Index<usize> for UnrolledList<T> {
+    type Output = T;
+
+    /// Returns the element at `index`.
+    ///
+    /// Panics if `index` is out of range.
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!(
+                "index {} is out of range for a list of length {}",
+                index, self.len
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for UnrolledList<T> {
+    /// Returns the element at `index` mutably.
+    ///
+    /// Panics if `index` is out of range.
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len;
+
+        match self.get_mut(index) {
+            Some(value) => value,
+            None => panic!(
+                "index {} is out of range for a list of length {}",
+                index, len
+            ),
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for UnrolledList<T> {
+    /// Compares the elements only, not how they are spread over the nodes.
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Eq> Eq for UnrolledList<T> {}
+
+/// Compares lists lexicographically, like slices.
+impl<T: PartialOrd> PartialOrd for UnrolledList<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord> Ord for UnrolledList<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash> Hash for UnrolledList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len);
+        for value in self.iter() {
+            value.hash(state);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for UnrolledList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Formats the list as `[a -> b -> c]`.
+impl<T: fmt::Display> fmt::Display for UnrolledList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[")?;
+        for (index, value) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, " -> ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T> IntoIterator for UnrolledList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consumes the list into an iterator over the lists values.
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            front: Vec::new().into_iter(),
+            list: self,
+            back: Vec::new().into_iter(),
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a UnrolledList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut UnrolledList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (mut id, mut offset) = self.front;
+        if offset == self.nodes[id].values.len() {
+            id = self.nodes[id].next.unwrap();
+            offset = 0;
+        }
+
+        self.front = (id, offset + 1);
+        self.len -= 1;
+        Some(&self.nodes[id].values[offset])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (mut id, mut end) = self.back;
+        if end == 0 {
+            id = self.nodes[id].prev.unwrap();
+            end = self.nodes[id].values.len();
+        }
+
+        self.back = (id, end - 1);
+        self.len -= 1;
+        Some(&self.nodes[id].values[end - 1])
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (mut id, mut offset) = self.front;
+        if offset == self.node(id).values.len() {
+            id = self.node(id).next.unwrap();
+            offset = 0;
+        }
+
+        self.front = (id, offset + 1);
+        self.len -= 1;
+        Some(self.value(id, offset))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let (mut id, mut end) = self.back;
+        if end == 0 {
+            id = self.node(id).prev.unwrap();
+            end = self.node(id).values.len();
+        }
+
+        self.back = (id, end - 1);
+        self.len -= 1;
+        Some(self.value(id, end - 1))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.front.next() {
+                return Some(value);
+            }
+
+            match self.list.head {
+                Some(head) => {
+                    let values = self.list.unlink_node(head);
+                    self.list.len -= values.len();
+                    self.front = values.into_iter();
+                }
+                None => return self.back.next(),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.front.len() + self.list.len + self.back.len();
+        (len, Some(len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.back.next_back() {
+                return Some(value);
+            }
+
+            match self.list.tail {
+                Some(tail) => {
+                    let values = self.list.unlink_node(tail);
+                    self.list.len -= values.len();
+                    self.back = values.into_iter();
+                }
+                None => return self.front.next_back(),
+            }
+        }
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = Iter<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.iter.len == 0 {
+            return None;
+        }
+
+        let count = self.iter.len.min(self.size);
+        Some(self.iter.split_front(count))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.iter.len.div_ceil(self.size);
+        (count, Some(count))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Chunks<'a, T> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::VecDeque;
+
+    /// Checks the links in both directions, that no node is empty or over capacity, that
+    /// all nodes but the first and last are at least half full and that the arena holds
+    /// nothing but the linked and the free nodes. Returns the number of elements.
+    fn check<T>(list: &UnrolledList<T>) -> usize {
+        let mut ids = Vec::new();
+        let mut prev = None;
+        let mut current = list.head;
+
+        while let Some(id) = current {
+            let node = &list.nodes[id];
+            assert_eq!(node.prev, prev);
+            assert!(!node.values.is_empty());
+            assert!(node.values.len() <= list.node_capacity);
+
+            ids.push(id);
+            prev = Some(id);
+            current = node.next;
+        }
+        assert_eq!(list.tail, prev);
+
+        if ids.len() > 2 {
+            for id in &ids[1..ids.len() - 1] {
+                assert!(list.nodes[*id].values.len() >= list.node_capacity / 2);
+            }
+        }
+        assert_eq!(list.nodes.len(), ids.len() + list.free.len());
+
+        let count = ids.iter().map(|id| list.nodes[*id].values.len()).sum();
+        assert_eq!(count, list.len());
+        count
+    }
+
+    fn assert_list_eq(list: &UnrolledList<u32>, expected: &VecDeque<u32>) {
+        check(list);
+        assert!(list.iter().eq(expected.iter()));
+        assert!(list.iter().rev().eq(expected.iter().rev()));
+        assert_eq!(list.front(), expected.front());
+        assert_eq!(list.back(), expected.back());
+    }
+
+    #[test]
+    fn test_push_and_pop_at_both_ends() {
+        let mut sut = UnrolledList::with_node_capacity(4);
+        for value in 0..10 {
+            sut.push_back(value);
+            sut.push_front(value);
+        }
+        check(&sut);
+        assert_eq!(sut.len(), 20);
+        assert_eq!(sut.front(), Some(&9));
+        assert_eq!(sut.back(), Some(&9));
+
+        for value in (0..10).rev() {
+            assert_eq!(sut.pop_front(), Some(value));
+            assert_eq!(sut.pop_back(), Some(value));
+            check(&sut);
+        }
+        assert!(sut.is_empty());
+        assert_eq!(sut.pop_front(), None);
+        assert_eq!(sut.pop_back(), None);
+        assert!(sut.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_push_back_fills_nodes_up() {
+        let count = if cfg!(miri) { 100 } else { 1000 };
+        let sut: UnrolledList<u32> = (0..count).collect();
+        check(&sut);
+        assert_eq!(sut.nodes.len(), count as usize / DEFAULT_NODE_CAPACITY + 1);
+    }
+
+    /// Applies random operations to lists of several node capacities and to a `VecDeque`.
+    #[test]
+    fn test_random_operations_against_vec_deque() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let steps = if cfg!(miri) { 300 } else { 3000 };
+
+        for node_capacity in [2, 3, 4, 5, 8, 16].iter() {
+            let mut sut = UnrolledList::with_node_capacity(*node_capacity);
+            let mut expected = VecDeque::new();
+
+            for step in 0..steps {
+                let value = rng.gen::<u32>();
+                let index = rng.gen_range(0, expected.len() + 1);
+
+                match rng.gen_range(0, 8) {
+                    0 => {
+                        sut.push_front(value);
+                        expected.push_front(value);
+                    }
+                    1 => {
+                        sut.push_back(value);
+                        expected.push_back(value);
+                    }
+                    2 => assert_eq!(sut.pop_front(), expected.pop_front()),
+                    3 => assert_eq!(sut.pop_back(), expected.pop_back()),
+                    4 | 5 => {
+                        sut.insert(index, value);
+                        expected.insert(index, value);
+                    }
+                    6 => assert_eq!(sut.remove(index), expected.remove(index)),
+                    _ => assert_eq!(sut.get(index), expected.get(index)),
+                }
+
+                check(&sut);
+                if step % 100 == 0 {
+                    assert_list_eq(&sut, &expected);
+                }
+            }
+
+            assert_list_eq(&sut, &expected);
+        }
+    }
+
+    #[test]
+    fn test_iterators_meet_in_the_middle() {
+        let mut sut = UnrolledList::with_node_capacity(3);
+        sut.extend(0..10);
+
+        let mut iter = sut.iter();
+        assert_eq!(iter.len(), 10);
+        assert_eq!(iter.next(), Some(&0));
+        assert_eq!(iter.next_back(), Some(&9));
+        assert_eq!(iter.len(), 8);
+        assert_eq!(
+            iter.collect::<Vec<_>>(),
+            (1..9).collect::<Vec<_>>().iter().collect::<Vec<_>>()
+        );
+
+        let mut iter_mut = sut.iter_mut();
+        *iter_mut.next_back().unwrap() = 90;
+        *iter_mut.next().unwrap() = 100;
+        for value in iter_mut {
+            *value += 10;
+        }
+        assert_eq!(
+            Vec::from(sut),
+            vec![100, 11, 12, 13, 14, 15, 16, 17, 18, 90]
+        );
+
+        let mut into_iter = UnrolledList::from([1, 2, 3, 4, 5]).into_iter();
+        assert_eq!(into_iter.next_back(), Some(5));
+        assert_eq!(into_iter.next(), Some(1));
+        assert_eq!(into_iter.len(), 3);
+        assert_eq!(into_iter.rev().collect::<Vec<_>>(), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn test_swap_and_truncate() {
+        let mut sut = UnrolledList::with_node_capacity(4);
+        sut.extend(0..20);
+
+        sut.swap(1, 17);
+        sut.swap(3, 2);
+        sut.swap(5, 5);
+        assert_eq!(sut[1], 17);
+        assert_eq!(sut[17], 1);
+        assert_eq!((sut[2], sut[3]), (3, 2));
+
+        sut.truncate(30);
+        assert_eq!(sut.len(), 20);
+        sut.truncate(6);
+        check(&sut);
+        assert_eq!(Vec::from(sut.clone()), vec![0, 17, 3, 2, 4, 5]);
+        sut.truncate(0);
+        assert!(sut.is_empty());
+        assert!(sut.nodes.is_empty());
+    }
+
+    #[test]
+    fn test_reorder_against_vec() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let rounds = if cfg!(miri) { 20 } else { 200 };
+
+        for _ in 0..rounds {
+            let values: Vec<u32> = (0..rng.gen_range(0, 60))
+                .map(|_| rng.gen_range(0, 5))
+                .collect();
+            let mut sut = UnrolledList::with_node_capacity(rng.gen_range(2, 6));
+            sut.extend(values.iter());
+
+            let k = rng.gen_range(1, 8);
+            let mut expected = values.clone();
+
+            sut.reverse();
+            expected.reverse();
+            check(&sut);
+            assert!(sut.iter().eq(expected.iter()));
+
+            sut.rotate_left(k);
+            if !expected.is_empty() {
+                let len = expected.len();
+                expected.rotate_left(k % len);
+            }
+            check(&sut);
+            assert!(sut.iter().eq(expected.iter()));
+
+            sut.reverse_in_groups(k);
+            let expected: Vec<u32> = expected
+                .chunks(k)
+                .flat_map(|chunk| chunk.iter().rev().copied())
+                .collect();
+            assert!(sut.iter().eq(expected.iter()));
+            assert!(sut
+                .chunks(k)
+                .map(|chunk| chunk.len())
+                .eq(expected.chunks(k).map(|chunk| chunk.len())));
+
+            let pivot = rng.gen_range(0, 5);
+            let count = expected.iter().filter(|value| **value < pivot).count();
+            assert_eq!(sut.partition(&pivot), count);
+            check(&sut);
+            assert!(sut.iter().take(count).all(|value| *value < pivot));
+
+            assert_eq!(sut.is_palindrome(), sut.iter().eq(sut.iter().rev()));
+            assert_eq!(sut.middle(), sut.get(sut.len() / 2));
+            for k in 0..=sut.len() {
+                assert_eq!(sut.nth_from_end(k), sut.iter().rev().nth(k));
+            }
+            assert_eq!(sut.nth_from_end(usize::MAX), None);
+        }
+    }
+
+    #[test]
+    fn test_sorting_helpers_against_vec() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let count = if cfg!(miri) { 100 } else { 500 };
+        let values: Vec<(u8, usize)> = (0..count).map(|i| (rng.gen_range(0, 10), i)).collect();
+
+        let mut sut: UnrolledList<(u8, usize)> = UnrolledList::with_node_capacity(5);
+        sut.extend(values.iter());
+        let mut expected = values.clone();
+        sut.sort_by_key(|pair| pair.0);
+        expected.sort_by_key(|pair| pair.0);
+        check(&sut);
+        assert!(sut.iter().eq(expected.iter()));
+
+        sut.dedup_by_key(|pair| pair.0);
+        expected.dedup_by_key(|pair| pair.0);
+        check(&sut);
+        assert!(sut.iter().eq(expected.iter()));
+
+        let mut sut = UnrolledList::with_node_capacity(4);
+        let mut expected = Vec::new();
+        for _ in 0..count {
+            let value = rng.gen_range(0, 50);
+            sut.insert_sorted(value);
+            expected.push(value);
+        }
+        expected.sort();
+        check(&sut);
+        assert!(sut.iter().eq(expected.iter()));
+
+        let merged = UnrolledList::merge_sorted(sut, (0..50).collect());
+        expected.extend(0..50);
+        expected.sort();
+        check(&merged);
+        assert_eq!(merged.node_capacity(), 4);
+        assert!(merged.iter().eq(expected.iter()));
+    }
+
+    #[test]
+    fn test_filtering_in_place_against_vec() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let rounds = if cfg!(miri) { 3 } else { 20 };
+
+        for node_capacity in [2, 3, 4, 5, 8].iter() {
+            for _ in 0..rounds {
+                let len = rng.gen_range(0, 200);
+                let values: Vec<u32> = (0..len).map(|_| rng.gen_range(0, 4)).collect();
+
+                let mut sut: UnrolledList<u32> = UnrolledList::with_node_capacity(*node_capacity);
+                sut.extend(values.iter());
+                let mut expected = values.clone();
+                sut.dedup();
+                expected.dedup();
+                check(&sut);
+                assert!(sut.iter().eq(expected.iter()));
+
+                let mut sut: UnrolledList<u32> = UnrolledList::with_node_capacity(*node_capacity);
+                sut.extend(values.iter());
+                let mut expected = values;
+                let removed = sut.remove_all(&0);
+                expected.retain(|value| *value != 0);
+                assert_eq!(removed, len - expected.len());
+                check(&sut);
+                assert!(sut.iter().eq(expected.iter()));
+                assert!(sut.iter().rev().eq(expected.iter().rev()));
+
+                // Whole nodes of the same value run empty
+                sut.extend(vec![3; 3 * *node_capacity]);
+                expected.extend(vec![3; 3 * *node_capacity]);
+                sut.dedup();
+                expected.dedup();
+                check(&sut);
+                assert!(sut.iter().eq(expected.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_traits() {
+        let sut = UnrolledList::from([1, 2, 3]);
+        let mut clone = sut.clone();
+        clone[0] = 10;
+
+        assert_eq!(sut.to_string(), "[1 -> 2 -> 3]");
+        assert_eq!(format!("{:?}", sut), "[1, 2, 3]");
+        assert!(sut < clone);
+        assert_ne!(sut, clone);
+        assert_eq!(sut.reversed(), UnrolledList::from(vec![3, 2, 1]));
+        assert_eq!(UnrolledList::<u8>::default().to_string(), "[]");
+
+        let mut small = UnrolledList::with_node_capacity(2);
+        small.extend(&[1, 2, 3]);
+        assert_eq!(small, sut);
+        assert_eq!(small.clone().node_capacity(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "insertion index 4 is out of range for a list of length 3")]
+    fn test_insert_out_of_range_panics() {
+        UnrolledList::from([1, 2, 3]).insert(4, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "the node capacity has to be at least 2, got 1")]
+    fn test_node_capacity_of_one_panics() {
+        UnrolledList::<u8>::with_node_capacity(1);
+    }
+}
@@ -0,0 +1,395 @@
+//! Renders binary trees for debugging.
+//!
+//! A tree describes its shape through [`Visualize::display_tree`] and gets a
+//! Graphviz (DOT) and an ASCII rendering for free. Both renderings only depend
+//! on the shape and the values of the tree, so they can be compared in
+//! snapshot tests.
+//!
+//! NOTE: This was written for a learning purpose.
+
+use std::fmt::Write;
+
+/// A snapshot of a single node, holding everything that gets rendered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayNode {
+    pub label: String,
+    pub height: usize,
+    /// Height of the left minus height of the right subtree.
+    pub balance_factor: isize,
+    pub left: Option<Box<DisplayNode>>,
+    pub right: Option<Box<DisplayNode>>,
+}
+
+impl DisplayNode {
+    /// Creates a node from its label and children, computing height and balance factor
+    /// from the children.
+    pub fn new(
+        label: String,
+        left: Option<Box<DisplayNode>>,
+        right: Option<Box<DisplayNode>>,
+    ) -> Self {
+        let left_height = left.as_ref().map_or(0, |node| node.height);
+        let right_height = right.as_ref().map_or(0, |node| node.height);
+
+        DisplayNode {
+            label,
+            height: 1 + left_height.max(right_height),
+            balance_factor: left_height as isize - right_height as isize,
+            left,
+            right,
+        }
+    }
+
+    /// Builds the snapshot of a whole tree without recursing, so a degenerate tree doesn't
+    /// overflow the stack. `label` renders a node and `children` returns its left and
+    /// right child.
+    pub fn from_tree<N, L, C>(root: &N, mut label: L, mut children: C) -> Self
+    where
+        L: FnMut(&N) -> String,
+        C: FnMut(&N) -> (Option<&N>, Option<&N>),
+    {
+        // A node is visited twice, the second time both of its subtrees are built
+        let mut pending = vec![(Some(root), false)];
+        let mut built: Vec<Option<Box<DisplayNode>>> = Vec::new();
+
+        while let Some((node, children_built)) = pending.pop() {
+            let node = match node {
+                Some(node) => node,
+                None => {
+                    built.push(None);
+                    continue;
+                }
+            };
+
+            if children_built {
+                let right = built.pop().unwrap();
+                let left = built.pop().unwrap();
+                built.push(Some(Box::new(DisplayNode::new(label(node), left, right))));
+            } else {
+                let (left, right) = children(node);
+                pending.push((Some(node), true));
+                pending.push((right, false));
+                pending.push((left, false));
+            }
+        }
+
+        *built.pop().unwrap().unwrap()
+    }
+}
+
+impl Drop for DisplayNode {
+    /// The snapshot of a degenerate tree is a long path, so the nodes are freed iteratively.
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<DisplayNode>> = self
+            .left
+            .take()
+            .into_iter()
+            .chain(self.right.take())
+            .collect();
+
+        while let Some(mut node) = stack.pop() {
+            stack.extend(node.left.take());
+            stack.extend(node.right.take());
+        }
+    }
+}
+
+/// Trees that can be rendered as a DOT graph or as ASCII art.
+pub trait Visualize {
+    /// Returns the shape of the tree, `None` for an empty tree.
+    fn display_tree(&self) -> Option<DisplayNode>;
+
+    /// Renders the tree as a Graphviz graph. Nodes are named `n0`, `n1`, ... in preorder
+    /// and labeled with their value, height and balance factor. Missing children of
+    /// inner nodes are drawn as points, so left and right can be told apart.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// use data_structure_with_colin::visualize::Visualize;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(2);
+    /// binary_tree.insert(3);
+    ///
+    /// assert_eq!(
+    ///     binary_tree.to_dot(),
+    ///     "digraph {\n    \
+    ///         n0 [label=\"2\\nh=2 bf=-1\"];\n    \
+    ///         nil0 [shape=point];\n    \
+    ///         n0 -> nil0;\n    \
+    ///         n1 [label=\"3\\nh=1 bf=0\"];\n    \
+    ///         n0 -> n1;\n\
+    ///     }\n"
+    /// );
+    /// ```
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+
+        if let Some(root) = self.display_tree() {
+            write_dot_nodes(&mut dot, &root);
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the tree as ASCII art, one node per line with the root at the top.
+    /// # Example
+    /// ```rust
+    /// use data_structure_with_colin::binary_tree::BinaryTree;
+    /// use data_structure_with_colin::visualize::Visualize;
+    /// let mut binary_tree = BinaryTree::new();
+    /// binary_tree.insert(2);
+    /// binary_tree.insert(1);
+    /// binary_tree.insert(3);
+    ///
+    /// assert_eq!(
+    ///     binary_tree.to_ascii(),
+    ///     "2 (h=2, bf=0)\n\
+    ///      |-- L: 1 (h=1, bf=0)\n\
+    ///      `-- R: 3 (h=1, bf=0)\n"
+    /// );
+    /// ```
+    fn to_ascii(&self) -> String {
+        let mut ascii = String::new();
+
+        match self.display_tree() {
+            Some(root) => {
+                let _ = writeln!(ascii, "{}", node_text(&root));
+                write_ascii_children(&mut ascii, &root);
+            }
+            None => ascii.push_str("(empty)\n"),
+        }
+
+        ascii
+    }
+}
+
+fn node_text(node: &DisplayNode) -> String {
+    format!(
+        "{} (h={}, bf={})",
+        node.label, node.height, node.balance_factor
+    )
+}
+
+/// Escapes a label for a quoted DOT string.
+fn escape_dot(label: &str) -> String {
+    label
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// What is left to write for a subtree, see [`write_dot_nodes`].
+enum DotStep<'a> {
+    /// A node and its subtree, `parent` is the id of the node above it.
+    Node(&'a DisplayNode, Option<usize>),
+    /// The point standing in for a missing child.
+    Nil(usize, usize),
+    /// The edge from a parent to a child, written after the subtree of the child.
+    Edge(usize, usize),
+}
+
+/// Writes the nodes in preorder, the ids count up in the same order. The steps are kept
+/// on a stack instead of recursing, so a degenerate tree doesn't overflow the stack.
+fn write_dot_nodes(dot: &mut String, root: &DisplayNode) {
+    let mut steps = vec![DotStep::Node(root, None)];
+    let mut next_id = 0;
+
+    while let Some(step) = steps.pop() {
+        let (node, parent) = match step {
+            DotStep::Node(node, parent) => (node, parent),
+            DotStep::Nil(id, index) => {
+                let nil = format!("nil{}", id * 2 + index);
+                let _ = writeln!(dot, "    {} [shape=point];", nil);
+                let _ = writeln!(dot, "    n{} -> {};", id, nil);
+                continue;
+            }
+            DotStep::Edge(parent, child) => {
+                let _ = writeln!(dot, "    n{} -> n{};", parent, child);
+                continue;
+            }
+        };
+
+        let id = next_id;
+        next_id += 1;
+
+        let _ = writeln!(
+            dot,
+            "    n{} [label=\"{}\\nh={} bf={}\"];",
+            id,
+            escape_dot(&node.label),
+            node.height,
+            node.balance_factor
+        );
+
+        if let Some(parent) = parent {
+            steps.push(DotStep::Edge(parent, id));
+        }
+        if node.left.is_none() && node.right.is_none() {
+            continue;
+        }
+
+        // Pushed in reverse, so the left side comes first
+        for (index, child) in [&node.left, &node.right].iter().enumerate().rev() {
+            match child {
+                Some(child) => steps.push(DotStep::Node(child, Some(id))),
+                None => steps.push(DotStep::Nil(id, index)),
+            }
+        }
+    }
+}
+
+/// Writes the children of `root` and their subtrees in preorder, each line indented by
+/// the branches above it. Uses a stack instead of recursing.
+fn write_ascii_children(ascii: &mut String, root: &DisplayNode) {
+    let mut stack = Vec::new();
+    push_ascii_children(&mut stack, root, "");
+
+    while let Some((side, node, prefix, is_last)) = stack.pop() {
+        let (branch, indent) = if is_last {
+            ("`-- ", "    ")
+        } else {
+            ("|-- ", "|   ")
+        };
+
+        let _ = writeln!(ascii, "{}{}{}: {}", prefix, branch, side, node_text(node));
+        push_ascii_children(&mut stack, node, &format!("{}{}", prefix, indent));
+    }
+}
+
+/// Pushes the children of `node` with the prefix of their lines, the last one first.
+fn push_ascii_children<'a>(
+    stack: &mut Vec<(&'static str, &'a DisplayNode, String, bool)>,
+    node: &'a DisplayNode,
+    prefix: &str,
+) {
+    let children: Vec<(&str, &DisplayNode)> = [("L", &node.left), ("R", &node.right)]
+        .iter()
+        .filter_map(|(side, child)| child.as_deref().map(|child| (*side, child)))
+        .collect();
+
+    for (index, (side, child)) in children.iter().enumerate().rev() {
+        let is_last = index + 1 == children.len();
+        stack.push((side, child, prefix.to_string(), is_last));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::avl_tree::set::AVLTreeSet;
+    use crate::binary_tree::BinaryTree;
+
+    #[test]
+    fn test_empty() {
+        let sut = BinaryTree::<u32>::new();
+        assert_eq!(sut.display_tree(), None);
+        assert_eq!(sut.to_dot(), "digraph {\n}\n");
+        assert_eq!(sut.to_ascii(), "(empty)\n");
+    }
+
+    #[test]
+    fn test_binary_tree_ascii() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(11);
+        sut.insert(9);
+        sut.insert(12);
+        sut.insert(7);
+
+        assert_eq!(
+            sut.to_ascii(),
+            "10 (h=3, bf=0)\n\
+             |-- L: 8 (h=2, bf=0)\n\
+             |   |-- L: 7 (h=1, bf=0)\n\
+             |   `-- R: 9 (h=1, bf=0)\n\
+             `-- R: 11 (h=2, bf=-1)\n    \
+                 `-- R: 12 (h=1, bf=0)\n"
+        );
+    }
+
+    #[test]
+    fn test_binary_tree_dot() {
+        let mut sut = BinaryTree::new();
+        sut.insert(10);
+        sut.insert(8);
+        sut.insert(9);
+
+        assert_eq!(
+            sut.to_dot(),
+            "digraph {\n    \
+                 n0 [label=\"10\\nh=3 bf=2\"];\n    \
+                 n1 [label=\"8\\nh=2 bf=-1\"];\n    \
+                 nil2 [shape=point];\n    \
+                 n1 -> nil2;\n    \
+                 n2 [label=\"9\\nh=1 bf=0\"];\n    \
+                 n1 -> n2;\n    \
+                 n0 -> n1;\n    \
+                 nil1 [shape=point];\n    \
+                 n0 -> nil1;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_avl_tree_set() {
+        // Sorted input is rotated into a perfect tree
+        let sut = (1..=7).collect::<AVLTreeSet<_>>();
+
+        assert_eq!(
+            sut.to_ascii(),
+            "4 (h=3, bf=0)\n\
+             |-- L: 2 (h=2, bf=0)\n\
+             |   |-- L: 1 (h=1, bf=0)\n\
+             |   `-- R: 3 (h=1, bf=0)\n\
+             `-- R: 6 (h=2, bf=0)\n    \
+                 |-- L: 5 (h=1, bf=0)\n    \
+                 `-- R: 7 (h=1, bf=0)\n"
+        );
+        assert_eq!(
+            sut.to_dot(),
+            "digraph {\n    \
+                 n0 [label=\"4\\nh=3 bf=0\"];\n    \
+                 n1 [label=\"2\\nh=2 bf=0\"];\n    \
+                 n2 [label=\"1\\nh=1 bf=0\"];\n    \
+                 n1 -> n2;\n    \
+                 n3 [label=\"3\\nh=1 bf=0\"];\n    \
+                 n1 -> n3;\n    \
+                 n0 -> n1;\n    \
+                 n4 [label=\"6\\nh=2 bf=0\"];\n    \
+                 n5 [label=\"5\\nh=1 bf=0\"];\n    \
+                 n4 -> n5;\n    \
+                 n6 [label=\"7\\nh=1 bf=0\"];\n    \
+                 n4 -> n6;\n    \
+                 n0 -> n4;\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn test_deep_tree() {
+        // Sorted input degenerates to a list, rendering it must not recurse
+        let mut sut = BinaryTree::new();
+        for value in 0..20_000 {
+            sut.insert(value);
+        }
+
+        let root = sut.display_tree().unwrap();
+        assert_eq!((root.height, root.balance_factor), (20_000, -19_999));
+        assert_eq!(sut.to_dot().matches("->").count(), 2 * 19_999);
+        assert_eq!(sut.to_ascii().lines().count(), 20_000);
+        assert!(sut.to_ascii().ends_with("`-- R: 19999 (h=1, bf=0)\n"));
+    }
+
+    #[test]
+    fn test_dot_escapes_labels() {
+        let mut sut = BinaryTree::new();
+        sut.insert(String::from("say \"hi\""));
+
+        assert_eq!(
+            sut.to_dot(),
+            "digraph {\n    n0 [label=\"say \\\"hi\\\"\\nh=1 bf=0\"];\n}\n"
+        );
+    }
+}